@@ -40,6 +40,9 @@ async fn run_example() -> Result<(), Box<dyn std::error::Error>> {
         // Protocol::RaydiumCpmm,
         // Protocol::RaydiumClmm,
         // Protocol::RaydiumAmmV4,
+        // Protocol::OrcaWhirlpool,
+        // Protocol::MeteoraPools,
+        // Protocol::MeteoraDlmm,
     ];
 
     println!("📊 Protocols to monitor: {:?}", protocols);
@@ -55,7 +58,7 @@ async fn run_example() -> Result<(), Box<dyn std::error::Error>> {
     let event_filter = EventTypeFilter::include_only(vec![EventType::PumpFunTrade, EventType::PumpFunCreate]);
 
     // 使用无锁 ArrayQueue（零拷贝模式）
-    let queue = grpc.subscribe_dex_events(
+    let (queue, _handle) = grpc.subscribe_dex_events(
         vec![transaction_filter],
         vec![account_filter],
         Some(event_filter),
@@ -49,7 +49,7 @@ async fn run_dynamic_subscription_example() -> Result<(), Box<dyn std::error::Er
     ]);
 
     println!("🎧 Starting initial subscription...");
-    let queue = grpc
+    let (queue, handle) = grpc
         .subscribe_dex_events(vec![transaction_filter], vec![account_filter], Some(event_filter))
         .await?;
 
@@ -116,7 +116,7 @@ async fn run_dynamic_subscription_example() -> Result<(), Box<dyn std::error::Er
     println!("🛑 Stopping subscription gracefully...");
     println!("========================================");
 
-    grpc.stop().await;
+    handle.stop_and_join().await?;
 
     println!("✅ Dynamic subscription example completed successfully!");
     println!("\n🎉 Summary:");
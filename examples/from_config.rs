@@ -0,0 +1,29 @@
+use sol_parser_sdk::grpc::SubscriptionConfig;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    // 配置文件路径，默认使用 examples/subscription.toml
+    let path = std::env::args().nth(1).unwrap_or_else(|| "examples/subscription.toml".to_string());
+    let config = SubscriptionConfig::from_file(&path)?;
+    println!("📄 Loaded {} - endpoint: {}, protocols: {:?}", path, config.endpoint, config.protocols);
+
+    let (grpc, queue, handle) = config.subscribe().await?;
+    println!("🎧 Subscribed, press Ctrl+C to stop...");
+
+    let consumer = tokio::spawn(async move {
+        loop {
+            match queue.pop() {
+                Some(event) => println!("{:?}", event),
+                None => tokio::time::sleep(std::time::Duration::from_millis(1)).await,
+            }
+        }
+    });
+
+    tokio::signal::ctrl_c().await?;
+    handle.stop_and_join().await?;
+    consumer.abort();
+    println!("{:#?}", grpc.metrics());
+    Ok(())
+}
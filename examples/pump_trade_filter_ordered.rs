@@ -68,7 +68,7 @@ async fn run_example() -> Result<(), Box<dyn std::error::Error>> {
     println!("🎯 Event Filter: Buy, Sell, BuyExactSolIn, Create");
     println!("🎧 Starting subscription (ordered mode)...\n");
 
-    let queue = grpc
+    let (queue, _handle) = grpc
         .subscribe_dex_events(vec![transaction_filter], vec![account_filter], Some(event_filter))
         .await?;
 
@@ -7,7 +7,7 @@ use crate::core::events::EventMetadata;
 use crate::grpc::EventTypeFilter;
 use crate::DexEvent;
 pub use nonce::parse_nonce_account;
-use program_ids::*;
+use crate::grpc::types::Protocol;
 pub use pumpswap::{
     parse_global_config as parse_pumpswap_global_config, parse_pool as parse_pumpswap_pool,
 };
@@ -44,7 +44,7 @@ pub fn parse_account_unified(
         }
     }
 
-    if account.owner == PUMPSWAP_PROGRAM_ID {
+    if crate::grpc::program_ids::protocol_of(&account.owner) == Some(Protocol::PumpSwap) {
         if let Some(filter) = event_type_filter {
             if filter.should_include(crate::grpc::EventType::AccountPumpSwapGlobalConfig)
                 || filter.should_include(crate::grpc::EventType::AccountPumpSwapPool)
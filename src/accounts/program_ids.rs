@@ -7,8 +7,8 @@ use solana_sdk::pubkey::Pubkey;
 
 // ==================== DEX 协议程序 ID ====================
 
-/// PumpSwap 程序 ID
-pub const PUMPSWAP_PROGRAM_ID: Pubkey = pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+/// PumpSwap 程序 ID（mainnet，来自 `grpc::program_ids` 注册表）
+pub const PUMPSWAP_PROGRAM_ID: Pubkey = crate::grpc::program_ids::PUMPSWAP_PROGRAM;
 
 // ==================== 系统程序 ID ====================
 
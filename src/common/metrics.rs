@@ -2,6 +2,7 @@ use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
 use super::constants::*;
+use crate::DexEvent;
 
 /// Event type enumeration
 #[derive(Debug, Clone, Copy)]
@@ -272,6 +273,96 @@ impl PerformanceMetrics {
     }
 }
 
+/// 延迟直方图桶上界（微秒），另有一个 +Inf 桶
+pub const LATENCY_BUCKETS_US: [f64; 17] = [
+    5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 5_000.0, 10_000.0, 25_000.0,
+    50_000.0, 100_000.0, 250_000.0, 500_000.0, 1_000_000.0,
+];
+
+/// 固定桶延迟直方图（无锁）
+#[derive(Debug)]
+struct AtomicHistogram {
+    counts: [AtomicU64; LATENCY_BUCKETS_US.len() + 1],
+    sum_us: AtomicU64,
+}
+
+impl AtomicHistogram {
+    fn new() -> Self {
+        Self { counts: std::array::from_fn(|_| AtomicU64::new(0)), sum_us: AtomicU64::new(0) }
+    }
+
+    #[inline]
+    fn observe(&self, value_us: f64) {
+        let index = LATENCY_BUCKETS_US.iter().position(|le| value_us <= *le).unwrap_or(LATENCY_BUCKETS_US.len());
+        self.counts[index].fetch_add(1, Ordering::Relaxed);
+        self.sum_us.fetch_add(value_us as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        let mut cumulative = 0;
+        let buckets = LATENCY_BUCKETS_US
+            .iter()
+            .chain(std::iter::once(&f64::INFINITY))
+            .zip(self.counts.iter())
+            .map(|(le, count)| {
+                cumulative += count.load(Ordering::Relaxed);
+                (*le, cumulative)
+            })
+            .collect();
+        HistogramSnapshot { buckets, count: cumulative, sum_us: self.sum_us.load(Ordering::Relaxed) as f64 }
+    }
+}
+
+/// 直方图快照
+#[derive(Debug, Clone, Default)]
+pub struct HistogramSnapshot {
+    /// (桶上界 μs, 累计样本数)，最后一个桶上界为 +Inf
+    pub buckets: Vec<(f64, u64)>,
+    pub count: u64,
+    pub sum_us: f64,
+}
+
+impl HistogramSnapshot {
+    /// 分位数估计（q 取 0~1），返回所在桶的上界；落在 +Inf 桶时返回最大的有限上界
+    pub fn percentile(&self, q: f64) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let rank = (q.clamp(0.0, 1.0) * self.count as f64).ceil().max(1.0) as u64;
+        let le = self.buckets.iter().find(|(_, c)| *c >= rank).map_or(f64::INFINITY, |(le, _)| *le);
+        if le.is_finite() { le } else { LATENCY_BUCKETS_US[LATENCY_BUCKETS_US.len() - 1] }
+    }
+}
+
+/// 指标快照（`YellowstoneGrpc::metrics()`）
+#[derive(Debug, Clone)]
+pub struct MetricsSnapshot {
+    pub uptime: std::time::Duration,
+    /// 收到的交易更新；events_processed 为解析出的事件数，processing_stats 为解析耗时
+    pub transactions: EventMetricsSnapshot,
+    /// 收到的账户更新
+    pub accounts: EventMetricsSnapshot,
+    /// 收到的区块 / 区块元数据更新
+    pub block_metas: EventMetricsSnapshot,
+    /// 按 DexEvent 类型统计的已输出事件数（只含非零项）
+    pub events_by_kind: Vec<(&'static str, u64)>,
+    /// 按协议统计的已输出事件数（只含非零项）
+    pub events_by_protocol: Vec<(&'static str, u64)>,
+    /// gRPC 接收到写入输出的延迟（含有序模式的缓冲时间）
+    pub parse_latency: ProcessingTimeStats,
+    pub parse_latency_histogram: HistogramSnapshot,
+    /// block_time_us 到 grpc_recv_us 的端到端延迟
+    pub end_to_end_latency: HistogramSnapshot,
+    /// 重连次数
+    pub reconnects: u64,
+    /// 最近一次写入后的输出积压（含暂存事件）
+    pub queue_depth: u64,
+    pub max_queue_depth: u64,
+    pub dropped_events: u64,
+    /// 因 slot dead 或在 finalized 之前未确认而丢弃的暂扣 / 缓冲事件（ForkPolicy 非 Emit）
+    pub fork_discarded_events: u64,
+}
+
 /// High-performance metrics system
 #[derive(Debug)]
 pub struct HighPerformanceMetrics {
@@ -280,6 +371,15 @@ pub struct HighPerformanceMetrics {
     processing_stats: AtomicProcessingTimeStats,
     // 丢弃事件指标
     dropped_events_count: AtomicU64,
+    // 输出指标：按 DexEvent 类型计数、接收到输出的延迟、输出积压
+    events_by_kind: Box<[AtomicU64]>,
+    output_latency: AtomicProcessingTimeStats,
+    output_latency_histogram: AtomicHistogram,
+    end_to_end_latency: AtomicHistogram,
+    queue_depth: AtomicU64,
+    max_queue_depth: AtomicU64,
+    reconnects: AtomicU64,
+    fork_discarded_events: AtomicU64,
 }
 
 impl HighPerformanceMetrics {
@@ -298,6 +398,14 @@ impl HighPerformanceMetrics {
             processing_stats: AtomicProcessingTimeStats::new(),
             // 初始化丢弃事件指标
             dropped_events_count: AtomicU64::new(0),
+            events_by_kind: DexEvent::KIND_NAMES.iter().map(|_| AtomicU64::new(0)).collect(),
+            output_latency: AtomicProcessingTimeStats::new(),
+            output_latency_histogram: AtomicHistogram::new(),
+            end_to_end_latency: AtomicHistogram::new(),
+            queue_depth: AtomicU64::new(0),
+            max_queue_depth: AtomicU64::new(0),
+            reconnects: AtomicU64::new(0),
+            fork_discarded_events: AtomicU64::new(0),
         }
     }
 
@@ -381,10 +489,15 @@ impl MetricsManager {
             if !self.enable_metrics {
                 return;
             }
+            // 在 tokio 运行时之外创建时（如同步构造客户端）不启动后台任务
+            let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+                self.background_task_running.store(false, Ordering::Relaxed);
+                return;
+            };
 
             let metrics = self.metrics.clone();
 
-            tokio::spawn(async move {
+            runtime.spawn(async move {
                 let mut interval = tokio::time::interval(std::time::Duration::from_millis(500));
 
                 loop {
@@ -401,6 +514,91 @@ impl MetricsManager {
         }
     }
 
+    /// 是否启用指标
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enable_metrics
+    }
+
+    /// 记录写入输出的事件（kind 为 `DexEvent::kind_index()`）
+    ///
+    /// latency_us: gRPC 接收到写入输出；end_to_end_us: block_time_us 到 grpc_recv_us
+    #[inline]
+    pub fn record_output(
+        &self,
+        kind: usize,
+        latency_us: Option<f64>,
+        end_to_end_us: Option<f64>,
+        queue_depth: usize,
+    ) {
+        if !self.enable_metrics {
+            return;
+        }
+        let metrics = &self.metrics;
+        metrics.events_by_kind[kind].fetch_add(1, Ordering::Relaxed);
+        if let Some(latency_us) = latency_us {
+            metrics.output_latency.update(latency_us, 1);
+            metrics.output_latency_histogram.observe(latency_us);
+        }
+        if let Some(end_to_end_us) = end_to_end_us {
+            metrics.end_to_end_latency.observe(end_to_end_us);
+        }
+        let depth = queue_depth as u64;
+        metrics.queue_depth.store(depth, Ordering::Relaxed);
+        metrics.max_queue_depth.fetch_max(depth, Ordering::Relaxed);
+    }
+
+    /// 记录一次重连
+    #[inline]
+    pub fn record_reconnect(&self) {
+        if self.enable_metrics {
+            self.metrics.reconnects.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// 记录分叉丢弃的事件数
+    #[inline]
+    pub fn record_fork_discarded(&self, count: u64) {
+        if self.enable_metrics && count > 0 {
+            self.metrics.fork_discarded_events.fetch_add(count, Ordering::Relaxed);
+        }
+    }
+
+    /// 指标快照
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let metrics = &self.metrics;
+        let events_by_kind: Vec<(&'static str, u64)> = DexEvent::KIND_NAMES
+            .iter()
+            .zip(metrics.events_by_kind.iter())
+            .map(|(name, count)| (*name, count.load(Ordering::Relaxed)))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        let mut events_by_protocol: Vec<(&'static str, u64)> = Vec::new();
+        for (kind, count) in &events_by_kind {
+            let Some(protocol) = DexEvent::protocol_of_kind(kind) else { continue };
+            match events_by_protocol.iter_mut().find(|(p, _)| *p == protocol) {
+                Some((_, total)) => *total += count,
+                None => events_by_protocol.push((protocol, *count)),
+            }
+        }
+        MetricsSnapshot {
+            uptime: self.get_uptime(),
+            transactions: self.get_event_metrics(EventType::Transaction),
+            accounts: self.get_event_metrics(EventType::Account),
+            block_metas: self.get_event_metrics(EventType::BlockMeta),
+            events_by_kind,
+            events_by_protocol,
+            parse_latency: metrics.output_latency.get_stats(),
+            parse_latency_histogram: metrics.output_latency_histogram.snapshot(),
+            end_to_end_latency: metrics.end_to_end_latency.snapshot(),
+            reconnects: metrics.reconnects.load(Ordering::Relaxed),
+            queue_depth: metrics.queue_depth.load(Ordering::Relaxed),
+            max_queue_depth: metrics.max_queue_depth.load(Ordering::Relaxed),
+            dropped_events: metrics.get_dropped_events_count(),
+            fork_discarded_events: metrics.fork_discarded_events.load(Ordering::Relaxed),
+        }
+    }
+
     /// 记录处理次数（非阻塞）
     #[inline]
     pub fn record_process(&self, event_type: EventType) {
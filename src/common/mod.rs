@@ -3,6 +3,9 @@ pub mod metrics;
 pub mod constants;
 pub mod subscription;
 pub mod simd_utils;
+pub mod types;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
 
 // 重新导出主要类型
 pub use metrics::*;
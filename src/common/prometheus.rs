@@ -0,0 +1,224 @@
+//! Prometheus / OpenMetrics 导出（`prometheus` feature）
+//!
+//! `render` 把一个或多个 `MetricsSnapshot` 转为 Prometheus 文本格式，
+//! `serve` 启动一个最小的 HTTP 服务，在 `GET /metrics` 上返回采集结果。
+
+use super::metrics::{HistogramSnapshot, MetricsSnapshot};
+use std::fmt::Write;
+use std::net::SocketAddr;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+/// 请求头上限，超过后返回 431
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+/// accept 失败（如文件描述符耗尽）后的等待时间
+const ACCEPT_ERROR_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// (指标名, 说明, 取值)
+type Scalar = (&'static str, &'static str, fn(&MetricsSnapshot) -> u64);
+
+/// 渲染为 Prometheus 文本格式，每个快照以 `stream` 标签区分
+pub fn render(streams: &[(&str, MetricsSnapshot)]) -> String {
+    let mut out = String::new();
+
+    header(&mut out, "sol_parser_uptime_seconds", "gauge", "Time since the metrics manager was created");
+    for (stream, m) in streams {
+        let _ = writeln!(out, "sol_parser_uptime_seconds{{stream=\"{}\"}} {}", escape(stream), m.uptime.as_secs_f64());
+    }
+
+    header(&mut out, "sol_parser_updates_total", "counter", "gRPC updates received by type");
+    for (stream, m) in streams {
+        for (kind, snapshot) in [("transaction", &m.transactions), ("account", &m.accounts), ("block_meta", &m.block_metas)] {
+            let _ = writeln!(
+                out,
+                "sol_parser_updates_total{{stream=\"{}\",type=\"{}\"}} {}",
+                escape(stream), kind, snapshot.process_count
+            );
+        }
+    }
+
+    header(&mut out, "sol_parser_events_total", "counter", "Events pushed to the output by DexEvent variant");
+    for (stream, m) in streams {
+        for (kind, count) in &m.events_by_kind {
+            let protocol = crate::DexEvent::protocol_of_kind(kind).unwrap_or("");
+            let _ = writeln!(
+                out,
+                "sol_parser_events_total{{stream=\"{}\",kind=\"{}\",protocol=\"{}\"}} {}",
+                escape(stream), kind, protocol, count
+            );
+        }
+    }
+
+    header(&mut out, "sol_parser_parse_latency_us", "histogram", "Latency from gRPC receive to output push in microseconds");
+    for (stream, m) in streams {
+        histogram(&mut out, "sol_parser_parse_latency_us", stream, &m.parse_latency_histogram);
+    }
+
+    header(&mut out, "sol_parser_end_to_end_latency_us", "histogram", "Latency from block_time_us to grpc_recv_us in microseconds");
+    for (stream, m) in streams {
+        histogram(&mut out, "sol_parser_end_to_end_latency_us", stream, &m.end_to_end_latency);
+    }
+
+    let counters: [Scalar; 3] = [
+        ("sol_parser_reconnects_total", "Stream reconnects", |m| m.reconnects),
+        ("sol_parser_dropped_events_total", "Events dropped because the output was full or closed", |m| m.dropped_events),
+        ("sol_parser_fork_discarded_events_total", "Withheld or buffered events discarded for dead or abandoned slots", |m| m.fork_discarded_events),
+    ];
+    for (name, help, value) in counters {
+        header(&mut out, name, "counter", help);
+        for (stream, m) in streams {
+            let _ = writeln!(out, "{}{{stream=\"{}\"}} {}", name, escape(stream), value(m));
+        }
+    }
+
+    let gauges: [Scalar; 2] = [
+        ("sol_parser_queue_depth", "Output backlog after the last push", |m| m.queue_depth),
+        ("sol_parser_queue_depth_max", "Peak output backlog", |m| m.max_queue_depth),
+    ];
+    for (name, help, value) in gauges {
+        header(&mut out, name, "gauge", help);
+        for (stream, m) in streams {
+            let _ = writeln!(out, "{}{{stream=\"{}\"}} {}", name, escape(stream), value(m));
+        }
+    }
+    out
+}
+
+/// 启动 HTTP 导出服务，每次请求调用 `collect` 生成响应
+///
+/// 返回实际监听地址（端口为 0 时由系统分配）和服务任务。
+pub async fn serve<F>(addr: SocketAddr, collect: F) -> std::io::Result<(SocketAddr, JoinHandle<()>)>
+where
+    F: Fn() -> String + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+    let collect = std::sync::Arc::new(collect);
+    let handle = tokio::spawn(async move {
+        loop {
+            let mut socket = match listener.accept().await {
+                Ok((socket, _)) => socket,
+                Err(e) => {
+                    log::warn!("metrics exporter accept failed: {}", e);
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    continue;
+                }
+            };
+            let collect = std::sync::Arc::clone(&collect);
+            tokio::spawn(async move {
+                let Some(head) = read_request_head(&mut socket).await else {
+                    let response = "HTTP/1.1 431 Request Header Fields Too Large\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                    return;
+                };
+                let request = String::from_utf8_lossy(&head);
+                let path = request.split_whitespace().nth(1).unwrap_or("");
+                let response = if request.starts_with("GET ") && (path == "/metrics" || path.starts_with("/metrics?")) {
+                    let body = collect();
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        CONTENT_TYPE, body.len(), body
+                    )
+                } else {
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+                let _ = socket.shutdown().await;
+            });
+        }
+    });
+    Ok((local_addr, handle))
+}
+
+/// 读取到 `\r\n\r\n` 为止的请求头；超过 `MAX_REQUEST_HEAD` 返回 None，连接提前关闭时返回已读部分
+async fn read_request_head(socket: &mut tokio::net::TcpStream) -> Option<Vec<u8>> {
+    let mut head = Vec::with_capacity(1024);
+    let mut buf = [0u8; 1024];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap_or(0);
+        if n == 0 {
+            return Some(head);
+        }
+        head.extend_from_slice(&buf[..n]);
+        if memchr::memmem::find(&head, b"\r\n\r\n").is_some() {
+            return Some(head);
+        }
+        if head.len() > MAX_REQUEST_HEAD {
+            return None;
+        }
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn histogram(out: &mut String, name: &str, stream: &str, h: &HistogramSnapshot) {
+    let stream = escape(stream);
+    for (le, count) in &h.buckets {
+        let le = if le.is_finite() { le.to_string() } else { "+Inf".to_string() };
+        let _ = writeln!(out, "{}_bucket{{stream=\"{}\",le=\"{}\"}} {}", name, stream, le, count);
+    }
+    let _ = writeln!(out, "{}_sum{{stream=\"{}\"}} {}", name, stream, h.sum_us);
+    let _ = writeln!(out, "{}_count{{stream=\"{}\"}} {}", name, stream, h.count);
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::MetricsManager;
+
+    #[tokio::test]
+    async fn test_render_and_serve() {
+        let manager = MetricsManager::new(true, "test".to_string());
+        manager.record_output(0, Some(42.0), Some(3_000.0), 7);
+        manager.record_reconnect();
+        let text = render(&[("main", manager.snapshot())]);
+        let kind = crate::DexEvent::KIND_NAMES[0];
+        assert!(text.contains(&format!("sol_parser_events_total{{stream=\"main\",kind=\"{}\",protocol=\"PumpFun\"}} 1", kind)));
+        assert!(text.contains("sol_parser_parse_latency_us_bucket{stream=\"main\",le=\"50\"} 1"));
+        assert!(text.contains("sol_parser_parse_latency_us_bucket{stream=\"main\",le=\"25\"} 0"));
+        assert!(text.contains("sol_parser_end_to_end_latency_us_count{stream=\"main\"} 1"));
+        assert!(text.contains("sol_parser_reconnects_total{stream=\"main\"} 1"));
+        assert!(text.contains("sol_parser_queue_depth{stream=\"main\"} 7"));
+        assert_eq!(manager.snapshot().parse_latency_histogram.percentile(0.99), 50.0);
+
+        let (addr, handle) = serve("127.0.0.1:0".parse().unwrap(), move || {
+            render(&[("main", manager.snapshot())])
+        })
+        .await
+        .unwrap();
+        let mut socket = tokio::net::TcpStream::connect(addr).await.unwrap();
+        socket.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        socket.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("# TYPE sol_parser_parse_latency_us histogram"));
+
+        // 请求头分多次到达
+        let mut socket = tokio::net::TcpStream::connect(addr).await.unwrap();
+        socket.write_all(b"GET /metrics HTTP/1.1\r\n").await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        socket.write_all(b"Host: localhost\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        socket.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+
+        // 超长请求头
+        let mut socket = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let long_header = format!("GET /metrics HTTP/1.1\r\nX-Pad: {}\r\n", "a".repeat(MAX_REQUEST_HEAD));
+        socket.write_all(long_header.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        socket.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 431"));
+        handle.abort();
+    }
+}
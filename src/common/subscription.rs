@@ -1,3 +1,5 @@
+use std::sync::Arc;
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
 /// Subscription handle for managing and stopping subscriptions
@@ -5,6 +7,8 @@ pub struct SubscriptionHandle {
     stream_handle: JoinHandle<()>,
     event_handle: Option<JoinHandle<()>>,
     metrics_handle: Option<JoinHandle<()>>,
+    /// Graceful shutdown signal (stream task drains buffers before exiting)
+    shutdown_tx: Option<Arc<watch::Sender<bool>>>,
 }
 
 impl SubscriptionHandle {
@@ -14,29 +18,89 @@ impl SubscriptionHandle {
         event_handle: Option<JoinHandle<()>>,
         metrics_handle: Option<JoinHandle<()>>,
     ) -> Self {
-        Self { stream_handle, event_handle, metrics_handle }
+        Self { stream_handle, event_handle, metrics_handle, shutdown_tx: None }
     }
 
-    /// Stop subscription and abort all related tasks
-    pub fn stop(self) {
+    /// Attach a graceful shutdown signal; `stop()` will use it instead of aborting
+    pub fn with_shutdown(mut self, shutdown_tx: Arc<watch::Sender<bool>>) -> Self {
+        self.shutdown_tx = Some(shutdown_tx);
+        self
+    }
+
+    /// Stop subscription
+    ///
+    /// With a shutdown signal the stream task flushes buffered (Ordered / StreamingOrdered /
+    /// MicroBatch) events and exits on its own (the metrics task is aborted);
+    /// otherwise all related tasks are aborted.
+    pub fn stop(&self) {
+        match self.shutdown_tx {
+            Some(ref tx) => {
+                let _ = tx.send(true);
+                if let Some(ref handle) = self.metrics_handle {
+                    handle.abort();
+                }
+            }
+            None => self.abort(),
+        }
+    }
+
+    /// Abort all related tasks immediately (buffered events are discarded)
+    pub fn abort(&self) {
         self.stream_handle.abort();
-        if let Some(handle) = self.event_handle {
+        if let Some(ref handle) = self.event_handle {
             handle.abort();
         }
-        if let Some(handle) = self.metrics_handle {
+        if let Some(ref handle) = self.metrics_handle {
             handle.abort();
         }
     }
 
+    /// Whether the stream task has exited
+    pub fn is_finished(&self) -> bool {
+        self.stream_handle.is_finished()
+    }
+
     /// Asynchronously wait for all tasks to complete
+    ///
+    /// Returns the first `JoinError` (a panicked or aborted task). The metrics task being
+    /// cancelled by `stop()` is not an error.
     pub async fn join(self) -> Result<(), tokio::task::JoinError> {
-        let _ = self.stream_handle.await;
+        self.stream_handle.await?;
         if let Some(handle) = self.event_handle {
-            let _ = handle.await;
+            handle.await?;
         }
         if let Some(handle) = self.metrics_handle {
-            let _ = handle.await;
+            match handle.await {
+                Err(e) if !e.is_cancelled() => return Err(e),
+                _ => {}
+            }
         }
         Ok(())
     }
+
+    /// Stop gracefully and wait until buffered events are drained and all tasks have exited
+    pub async fn stop_and_join(self) -> Result<(), tokio::task::JoinError> {
+        self.stop();
+        self.join().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_join_propagates_task_panic() {
+        let handle = SubscriptionHandle::new(tokio::spawn(async { panic!("stream task failed") }), None, None);
+        assert!(handle.join().await.unwrap_err().is_panic());
+
+        // stop() 中止的指标任务不算错误
+        let (tx, mut rx) = watch::channel(false);
+        let stream = tokio::spawn(async move {
+            let _ = rx.wait_for(|stop| *stop).await;
+        });
+        let metrics = tokio::spawn(std::future::pending::<()>());
+        let handle = SubscriptionHandle::new(stream, None, Some(metrics)).with_shutdown(Arc::new(tx));
+        handle.stop_and_join().await.unwrap();
+    }
 }
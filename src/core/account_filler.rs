@@ -5,6 +5,8 @@
 //! 只填充那些会变化的账户，排除系统程序等常量账户
 
 use crate::core::events::*;
+use crate::grpc::program_ids;
+use crate::grpc::types::Protocol;
 use crate::instr::utils::get_instruction_account_getter;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
@@ -28,10 +30,7 @@ pub fn fill_accounts_from_transaction_data(
     match event {
         // PumpFun 事件填充
         DexEvent::PumpFunTrade(ref mut trade_event) => {
-            if let Some(invoke) = program_invokes
-                .get(crate::grpc::program_ids::PUMPFUN_PROGRAM_ID)
-                .as_ref()
-                .and_then(|v| v.last())
+            if let Some(invoke) = program_ids::last_invoke(program_invokes, Protocol::PumpFun)
             {
                 if let Some(get_account) = get_instruction_account_getter(
                     meta,
@@ -46,10 +45,7 @@ pub fn fill_accounts_from_transaction_data(
             }
         }
         DexEvent::PumpSwapBuy(ref mut event) => {
-            if let Some(invoke) = program_invokes
-                .get(crate::grpc::program_ids::PUMPSWAP_PROGRAM_ID)
-                .as_ref()
-                .and_then(|v| v.last())
+            if let Some(invoke) = program_ids::last_invoke(program_invokes, Protocol::PumpSwap)
             {
                 if let Some(get_account) = get_instruction_account_getter(
                     meta,
@@ -64,10 +60,7 @@ pub fn fill_accounts_from_transaction_data(
             }
         }
         DexEvent::PumpSwapSell(ref mut event) => {
-            if let Some(invoke) = program_invokes
-                .get(crate::grpc::program_ids::PUMPSWAP_PROGRAM_ID)
-                .as_ref()
-                .and_then(|v| v.last())
+            if let Some(invoke) = program_ids::last_invoke(program_invokes, Protocol::PumpSwap)
             {
                 if let Some(get_account) = get_instruction_account_getter(
                     meta,
@@ -82,10 +75,7 @@ pub fn fill_accounts_from_transaction_data(
             }
         }
         DexEvent::MeteoraDammV2Swap(ref mut event) => {
-            if let Some(invoke) = program_invokes
-                .get(crate::grpc::program_ids::METEORA_DAMM_V2_PROGRAM_ID)
-                .as_ref()
-                .and_then(|v| v.last())
+            if let Some(invoke) = program_ids::last_invoke(program_invokes, Protocol::MeteoraDammV2)
             {
                 if let Some(get_account) = get_instruction_account_getter(
                     meta,
@@ -118,9 +108,7 @@ pub fn fill_accounts_with_owned_keys(
     match event {
         // PumpFun 事件填充
         DexEvent::PumpFunTrade(ref mut trade_event) => {
-            if let Some(invoke) = program_invokes
-                .get(&crate::grpc::program_ids::PUMPFUN_PROGRAM)
-                .and_then(|v| v.last())
+            if let Some(invoke) = program_ids::last_invoke_by_key(program_invokes, Protocol::PumpFun)
             {
                 if let Some(get_account) = get_instruction_account_getter(
                     meta,
@@ -135,9 +123,7 @@ pub fn fill_accounts_with_owned_keys(
             }
         }
         DexEvent::PumpSwapBuy(ref mut event) => {
-            if let Some(invoke) = program_invokes
-                .get(&crate::grpc::program_ids::PUMPSWAP_PROGRAM)
-                .and_then(|v| v.last())
+            if let Some(invoke) = program_ids::last_invoke_by_key(program_invokes, Protocol::PumpSwap)
             {
                 if let Some(get_account) = get_instruction_account_getter(
                     meta,
@@ -152,9 +138,7 @@ pub fn fill_accounts_with_owned_keys(
             }
         }
         DexEvent::PumpSwapSell(ref mut event) => {
-            if let Some(invoke) = program_invokes
-                .get(&crate::grpc::program_ids::PUMPSWAP_PROGRAM)
-                .and_then(|v| v.last())
+            if let Some(invoke) = program_ids::last_invoke_by_key(program_invokes, Protocol::PumpSwap)
             {
                 if let Some(get_account) = get_instruction_account_getter(
                     meta,
@@ -169,9 +153,7 @@ pub fn fill_accounts_with_owned_keys(
             }
         }
         DexEvent::MeteoraDammV2Swap(ref mut event) => {
-            if let Some(invoke) = program_invokes
-                .get(&crate::grpc::program_ids::METEORA_DAMM_V2_PROGRAM)
-                .and_then(|v| v.last())
+            if let Some(invoke) = program_ids::last_invoke_by_key(program_invokes, Protocol::MeteoraDammV2)
             {
                 if let Some(get_account) = get_instruction_account_getter(
                     meta,
@@ -185,6 +167,34 @@ pub fn fill_accounts_with_owned_keys(
                 }
             }
         }
+        DexEvent::BonkTrade(ref mut event) => {
+            if let Some(invoke) = program_ids::last_invoke_by_key(program_invokes, Protocol::Bonk) {
+                if let Some(get_account) = get_instruction_account_getter(
+                    meta,
+                    transaction,
+                    account_keys,
+                    loaded_writable_addresses,
+                    loaded_readonly_addresses,
+                    invoke,
+                ) {
+                    bonk::fill_trade_accounts(event, &get_account);
+                }
+            }
+        }
+        DexEvent::BonkPoolCreate(ref mut event) => {
+            if let Some(invoke) = program_ids::last_invoke_by_key(program_invokes, Protocol::Bonk) {
+                if let Some(get_account) = get_instruction_account_getter(
+                    meta,
+                    transaction,
+                    account_keys,
+                    loaded_writable_addresses,
+                    loaded_readonly_addresses,
+                    invoke,
+                ) {
+                    bonk::fill_pool_create_accounts(event, &get_account);
+                }
+            }
+        }
         _ => {} // 其他事件类型TODO
     }
 }
@@ -236,6 +246,9 @@ pub fn fill_accounts_from_instruction_data(event: &mut DexEvent, instruction_acc
         DexEvent::BonkTrade(ref mut trade_event) => {
             bonk::fill_trade_accounts(trade_event, &get_account);
         }
+        DexEvent::BonkPoolCreate(ref mut event) => {
+            bonk::fill_pool_create_accounts(event, &get_account);
+        }
 
         // 其他事件类型暂时不处理
         _ => {}
@@ -613,13 +626,48 @@ pub mod bonk {
     use super::*;
 
     /// 填充 Bonk Trade 事件账户
+    /// 基于 LaunchLab IDL buy/sell_exact_in/out 指令账户映射:
+    /// 0: payer, 2: global_config, 3: platform_config, 4: pool_state,
+    /// 5: user_base_token, 6: user_quote_token, 7: base_vault, 8: quote_vault,
+    /// 9: base_token_mint, 10: quote_token_mint, 11: base_token_program, 12: quote_token_program
     pub fn fill_trade_accounts(trade_event: &mut BonkTradeEvent, get_account: &AccountGetter<'_>) {
-        // 基于Bonk IDL swap指令账户映射
-        if trade_event.user == Pubkey::default() {
-            trade_event.user = get_account(0);
+        let fields = [
+            (&mut trade_event.user, 0),
+            (&mut trade_event.global_config, 2),
+            (&mut trade_event.platform_config, 3),
+            (&mut trade_event.pool_state, 4),
+            (&mut trade_event.user_base_token, 5),
+            (&mut trade_event.user_quote_token, 6),
+            (&mut trade_event.base_vault, 7),
+            (&mut trade_event.quote_vault, 8),
+            (&mut trade_event.base_token_mint, 9),
+            (&mut trade_event.quote_token_mint, 10),
+            (&mut trade_event.base_token_program, 11),
+            (&mut trade_event.quote_token_program, 12),
+        ];
+        for (field, index) in fields {
+            if *field == Pubkey::default() {
+                *field = get_account(index);
+            }
         }
-        if trade_event.pool_state == Pubkey::default() {
-            trade_event.pool_state = get_account(1);
+    }
+
+    /// 填充 Bonk PoolCreate 事件账户
+    /// 基于 LaunchLab IDL initialize 指令账户映射:
+    /// 0: payer, 3: platform_config, 6: base_mint, 7: quote_mint, 8: base_vault, 9: quote_vault
+    pub fn fill_pool_create_accounts(event: &mut BonkPoolCreateEvent, get_account: &AccountGetter<'_>) {
+        let fields = [
+            (&mut event.payer, 0),
+            (&mut event.platform_config, 3),
+            (&mut event.base_mint, 6),
+            (&mut event.quote_mint, 7),
+            (&mut event.base_vault, 8),
+            (&mut event.quote_vault, 9),
+        ];
+        for (field, index) in fields {
+            if *field == Pubkey::default() {
+                *field = get_account(index);
+            }
         }
     }
 }
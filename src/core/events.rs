@@ -6,6 +6,8 @@
 use serde::{Deserialize, Serialize};
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
 
+pub use super::timing::StageTimestamps;
+
 /// 基础元数据 - 所有事件共享的字段
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct EventMetadata {
@@ -14,24 +16,82 @@ pub struct EventMetadata {
     pub tx_index: u64, // 交易在slot中的索引，参考solana-streamer
     pub block_time_us: i64,
     pub grpc_recv_us: i64,
+    /// 交易执行失败（meta.err 非空）；仅在订阅包含失败交易时可能为 true
+    #[serde(default)]
+    pub is_failed: bool,
+    /// 分阶段时间戳（`stage-timing` feature，未开启时为零大小类型）
+    #[cfg_attr(feature = "stage-timing", serde(default))]
+    #[cfg_attr(not(feature = "stage-timing"), serde(skip))]
+    pub timing: StageTimestamps,
 }
 
-/// Block Meta Event
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Block Meta Event（需开启 ClientConfig::block_meta）
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BlockMetaEvent {
     pub metadata: EventMetadata,
+    pub blockhash: String,
+    pub parent_slot: u64,
+    pub parent_blockhash: String,
+    pub block_height: Option<u64>,
+    /// 链上出块时间（Unix 秒），metadata.block_time_us 为其微秒值
+    pub block_time: Option<i64>,
+    pub executed_transaction_count: u64,
 }
 
-/// Bonk Pool Create Event
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Slot 状态事件（SlotConfirmed / SlotFinalized / SlotDead）
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SlotStatusEvent {
+    pub metadata: EventMetadata,
+    pub parent: Option<u64>,
+    /// 仅 SlotDead：节点给出的原因（slot 在 finalized 之前未确认而被放弃时为 None）
+    pub dead_error: Option<String>,
+    /// 仅 SlotDead（`ForkPolicy::Retract`）：该 slot 已输出事件的交易签名，消费者应撤销。
+    /// 更晚的 slot finalized 时仍未确认的 slot 也会以 SlotDead 撤销
+    pub retracted_signatures: Vec<Signature>,
+}
+
+/// 断线续传缺口事件：[from_slot, to_slot) 内的交易可能缺失
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SlotGapEvent {
+    pub metadata: EventMetadata,
+    /// 续传起点（断线前最后输出事件的 slot）
+    pub from_slot: u64,
+    /// 重连后实际开始的 slot
+    pub to_slot: u64,
+    pub reason: String,
+}
+
+/// Bonk Pool Create Event - 基于 Raydium LaunchLab IDL
+///
+/// 字段来源标记:
+/// - [EVENT]: 来自 PoolCreateEvent（self-CPI 事件指令）
+/// - [INSTRUCTION]: 来自 initialize / initialize_v2 指令账户
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BonkPoolCreateEvent {
     pub metadata: EventMetadata,
-    pub base_mint_param: BaseMintParam,
+
+    // === IDL PoolCreateEvent 事件字段 ===
     pub pool_state: Pubkey,
     pub creator: Pubkey,
+    /// 全局配置（IDL 中的 config）
+    pub global_config: Pubkey,
+    pub base_mint_param: BaseMintParam,
+    pub curve_param: BonkCurveParams,
+    pub vesting_param: BonkVestingParams,
+    /// 迁移后 AMM 的创建者手续费收取方式，旧版事件中没有该字段
+    pub amm_fee_on: Option<BonkAmmCreatorFeeOn>,
+
+    // === 指令账户字段 ===
+    pub payer: Pubkey,                 // 0: payer
+    pub platform_config: Pubkey,       // 3: platform_config
+    pub base_mint: Pubkey,             // 6: base_mint
+    pub quote_mint: Pubkey,            // 7: quote_mint
+    pub base_vault: Pubkey,            // 8: base_vault
+    pub quote_vault: Pubkey,           // 9: quote_vault
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// LaunchLab MintParams
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BaseMintParam {
     pub symbol: String,
     pub name: String,
@@ -39,34 +99,144 @@ pub struct BaseMintParam {
     pub decimals: u8,
 }
 
-/// Bonk Trade Event
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// LaunchLab 曲线类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BonkCurveType {
+    #[default]
+    Constant,
+    Fixed,
+    Linear,
+}
+
+/// LaunchLab CurveParams（Fixed / Linear 曲线没有 total_base_sell，为 0）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BonkCurveParams {
+    pub curve_type: BonkCurveType,
+    pub supply: u64,
+    pub total_base_sell: u64,
+    pub total_quote_fund_raising: u64,
+    /// 0: 迁移到 AMM v4，1: 迁移到 CPSwap
+    pub migrate_type: u8,
+}
+
+/// LaunchLab VestingParams
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BonkVestingParams {
+    pub total_locked_amount: u64,
+    pub cliff_period: u64,
+    pub unlock_period: u64,
+}
+
+/// LaunchLab AmmCreatorFeeOn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BonkAmmCreatorFeeOn {
+    QuoteToken,
+    BothToken,
+}
+
+/// Bonk Trade Event - 基于 Raydium LaunchLab IDL
+///
+/// 字段来源标记:
+/// - [EVENT]: 来自 TradeEvent（self-CPI 事件指令）
+/// - [INSTRUCTION]: 来自 buy/sell_exact_in/out 指令；仅解析外层指令时，
+///   amount_in / amount_out 为指令中的滑点限制值
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BonkTradeEvent {
     pub metadata: EventMetadata,
-    // === 事件核心字段 ===
+
+    // === IDL TradeEvent 事件字段 ===
     pub pool_state: Pubkey,
-    pub user: Pubkey,
+    pub total_base_sell: u64,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base_before: u64,
+    pub real_quote_before: u64,
+    pub real_base_after: u64,
+    pub real_quote_after: u64,
     pub amount_in: u64,
     pub amount_out: u64,
-    pub is_buy: bool,
+    pub protocol_fee: u64,
+    pub platform_fee: u64,
+    /// 旧版事件中没有该字段，为 0
+    pub creator_fee: u64,
+    pub share_fee: u64,
     pub trade_direction: TradeDirection,
+    pub pool_status: BonkPoolStatus,
     pub exact_in: bool,
+    pub is_buy: bool,
+
+    // === 指令参数 ===
+    pub share_fee_rate: u64,
+
+    // === 指令账户字段 ===
+    pub user: Pubkey,                  // 0: payer
+    pub global_config: Pubkey,         // 2: global_config
+    pub platform_config: Pubkey,       // 3: platform_config
+    pub user_base_token: Pubkey,       // 5: user_base_token
+    pub user_quote_token: Pubkey,      // 6: user_quote_token
+    pub base_vault: Pubkey,            // 7: base_vault
+    pub quote_vault: Pubkey,           // 8: quote_vault
+    pub base_token_mint: Pubkey,       // 9: base_token_mint
+    pub quote_token_mint: Pubkey,      // 10: quote_token_mint
+    pub base_token_program: Pubkey,    // 11: base_token_program
+    pub quote_token_program: Pubkey,   // 12: quote_token_program
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum TradeDirection {
+    #[default]
     Buy,
     Sell,
 }
 
-/// Bonk Migrate AMM Event
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// LaunchLab PoolStatus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BonkPoolStatus {
+    /// 募集中
+    #[default]
+    Fund,
+    /// 募集完成，等待迁移
+    Migrate,
+    /// 已迁移
+    Trade,
+}
+
+/// LaunchLab 迁移目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BonkMigrateTarget {
+    /// migrate_to_amm：Raydium AMM v4（OpenBook 市场）
+    #[default]
+    Amm,
+    /// migrate_to_cpswap：Raydium CPMM
+    CpSwap,
+}
+
+/// Bonk Migrate AMM Event - 来自 migrate_to_amm / migrate_to_cpswap 指令（LaunchLab 不发出迁移事件）
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BonkMigrateAmmEvent {
     pub metadata: EventMetadata,
+    pub migrate_target: BonkMigrateTarget,
+    /// LaunchLab pool_state
     pub old_pool: Pubkey,
+    /// AMM v4 池 / CPSwap 池
     pub new_pool: Pubkey,
+    /// payer
     pub user: Pubkey,
+    /// 指令中不含流动性数量，为 0
     pub liquidity_amount: u64,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub global_config: Pubkey,
+    /// 仅 CPSwap 迁移
+    pub platform_config: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    /// 仅 AMM 迁移：OpenBook 市场
+    pub market: Pubkey,
+    pub base_lot_size: u64,
+    pub quote_lot_size: u64,
+    pub market_vault_signer_nonce: u8,
 }
 
 /// PumpFun Trade Event - 基于官方IDL定义
@@ -1109,7 +1279,7 @@ pub struct NonceAccountEvent {
 // ====================== Orca Whirlpool Events ======================
 
 /// Orca Whirlpool Swap Event (基于 TradedEvent，不是 SwapEvent)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct OrcaWhirlpoolSwapEvent {
     pub metadata: EventMetadata,
 
@@ -1139,6 +1309,19 @@ pub struct OrcaWhirlpoolSwapEvent {
     // pub tick_array_0: Pubkey,       // 7: tickArray0
     // pub tick_array_1: Pubkey,       // 8: tickArray1
     // pub tick_array_2: Pubkey,       // 9: tickArray2
+
+    // === 路由与 Token-2022 字段（指令解析填充，并合并到同池的 Traded 日志事件） ===
+    /// two_hop_swap 中的跳数：0 = 单池 swap，1 / 2 = 第一 / 第二跳
+    pub hop: u8,
+    /// 同一 two_hop_swap 两跳共享的路由 ID（单池 swap 为 0）
+    pub route_id: u64,
+    pub token_program_a: Pubkey,
+    pub token_program_b: Pubkey,
+    /// memo 程序（仅 V2 指令）
+    pub memo_program: Pubkey,
+    /// token A / B 的 transfer hook 账户（V2 指令的 remaining accounts）
+    pub transfer_hook_accounts_a: Vec<Pubkey>,
+    pub transfer_hook_accounts_b: Vec<Pubkey>,
 }
 
 /// Orca Whirlpool Liquidity Increased Event
@@ -1514,6 +1697,123 @@ pub enum DexEvent {
     // 区块元数据事件
     BlockMeta(BlockMetaEvent),
 
+    // Slot 状态事件（需开启 ClientConfig::slot_status 或 ForkPolicy::Retract）
+    SlotConfirmed(SlotStatusEvent),
+    SlotFinalized(SlotStatusEvent),
+    SlotDead(SlotStatusEvent),
+
+    // 断线续传缺口（服务端无法回放到断线位置）
+    SlotGap(SlotGapEvent),
+
     // 错误事件
     Error(String),
 }
+
+/// 为所有带 metadata 的变体生成访问器
+macro_rules! dex_event_metadata {
+    ($self:ident, $($variant:ident),* $(,)?) => {
+        match $self {
+            $(DexEvent::$variant(e) => Some(&e.metadata),)*
+            DexEvent::Error(_) => None,
+        }
+    };
+    (mut $self:ident, $($variant:ident),* $(,)?) => {
+        match $self {
+            $(DexEvent::$variant(e) => Some(&mut e.metadata),)*
+            DexEvent::Error(_) => None,
+        }
+    };
+}
+
+macro_rules! dex_event_variants {
+    ($mac:ident!($($prefix:tt)*)) => {
+        $mac! {
+            $($prefix)*
+            PumpFunCreate, PumpFunTrade, PumpFunBuy, PumpFunSell, PumpFunBuyExactSolIn, PumpFunMigrate,
+            PumpSwapTrade, PumpSwapBuy, PumpSwapSell, PumpSwapCreatePool, PumpSwapLiquidityAdded,
+            PumpSwapLiquidityRemoved,
+            MeteoraDammV2Swap, MeteoraDammV2CreatePosition, MeteoraDammV2ClosePosition,
+            MeteoraDammV2AddLiquidity, MeteoraDammV2RemoveLiquidity,
+            BonkTrade, BonkPoolCreate, BonkMigrateAmm,
+            RaydiumClmmSwap, RaydiumClmmCreatePool, RaydiumClmmOpenPosition,
+            RaydiumClmmOpenPositionWithTokenExtNft, RaydiumClmmClosePosition,
+            RaydiumClmmIncreaseLiquidity, RaydiumClmmDecreaseLiquidity, RaydiumClmmCollectFee,
+            RaydiumCpmmSwap, RaydiumCpmmDeposit, RaydiumCpmmWithdraw, RaydiumCpmmInitialize,
+            RaydiumAmmV4Swap, RaydiumAmmV4Deposit, RaydiumAmmV4Initialize2, RaydiumAmmV4Withdraw,
+            RaydiumAmmV4WithdrawPnl,
+            OrcaWhirlpoolSwap, OrcaWhirlpoolLiquidityIncreased, OrcaWhirlpoolLiquidityDecreased,
+            OrcaWhirlpoolPoolInitialized,
+            MeteoraPoolsSwap, MeteoraPoolsAddLiquidity, MeteoraPoolsRemoveLiquidity,
+            MeteoraPoolsBootstrapLiquidity, MeteoraPoolsPoolCreated, MeteoraPoolsSetPoolFees,
+            MeteoraDlmmSwap, MeteoraDlmmAddLiquidity, MeteoraDlmmRemoveLiquidity,
+            MeteoraDlmmInitializePool, MeteoraDlmmInitializeBinArray, MeteoraDlmmCreatePosition,
+            MeteoraDlmmClosePosition, MeteoraDlmmClaimFee,
+            TokenInfo, TokenAccount, NonceAccount, PumpSwapGlobalConfigAccount, PumpSwapPoolAccount,
+            BlockMeta,
+            SlotConfirmed, SlotFinalized, SlotDead, SlotGap,
+        }
+    };
+}
+
+macro_rules! dex_event_kinds {
+    ($($variant:ident),* $(,)?) => {
+        #[derive(Clone, Copy)]
+        enum DexEventKind {
+            $($variant,)*
+            Error,
+        }
+
+        impl DexEvent {
+            /// 全部事件类型名，下标与 `kind_index()` 对应
+            pub const KIND_NAMES: &'static [&'static str] = &[$(stringify!($variant),)* "Error"];
+
+            /// 事件类型序号（用于按类型计数）
+            #[inline]
+            pub fn kind_index(&self) -> usize {
+                match self {
+                    $(DexEvent::$variant(_) => DexEventKind::$variant as usize,)*
+                    DexEvent::Error(_) => DexEventKind::Error as usize,
+                }
+            }
+        }
+    };
+}
+
+dex_event_variants!(dex_event_kinds!());
+
+/// 协议名：事件类型名以协议名开头
+const PROTOCOL_NAMES: &[&str] = &[
+    "PumpFun", "PumpSwap", "Bonk", "RaydiumClmm", "RaydiumCpmm", "RaydiumAmmV4",
+    "OrcaWhirlpool", "MeteoraDammV2", "MeteoraPools", "MeteoraDlmm",
+];
+
+impl DexEvent {
+    /// 事件类型名（变体名）
+    #[inline]
+    pub fn kind_name(&self) -> &'static str {
+        Self::KIND_NAMES[self.kind_index()]
+    }
+
+    /// 事件所属协议；区块、slot、通用账户等事件返回 None
+    #[inline]
+    pub fn protocol(&self) -> Option<&'static str> {
+        Self::protocol_of_kind(self.kind_name())
+    }
+
+    /// 按事件类型名查协议
+    pub fn protocol_of_kind(kind: &str) -> Option<&'static str> {
+        PROTOCOL_NAMES.iter().copied().find(|p| kind.starts_with(p))
+    }
+
+    /// 事件元数据（`Error` 变体没有元数据）
+    #[inline]
+    pub fn metadata(&self) -> Option<&EventMetadata> {
+        dex_event_variants!(dex_event_metadata!(self,))
+    }
+
+    /// 可变事件元数据
+    #[inline]
+    pub fn metadata_mut(&mut self) -> Option<&mut EventMetadata> {
+        dex_event_variants!(dex_event_metadata!(mut self,))
+    }
+}
@@ -11,12 +11,17 @@ pub mod events;          // 事件定义
 pub mod unified_parser;  // 统一解析器 - 单一入口
 pub mod account_filler;  // 账户填充器 - 从指令数据填充事件账户
 pub mod common_filler;
+pub mod timing;          // 分阶段延迟时间戳（stage-timing feature）
 
 // 主要导出 - 核心事件处理功能
 pub use events::*;
+pub use timing::LatencyBreakdown;
 pub use unified_parser::{
-    parse_transaction_events, parse_logs_only, parse_transaction_with_listener, EventListener,
-    parse_transaction_events_streaming, parse_logs_streaming, parse_transaction_with_streaming_listener, StreamingEventListener
+    parse_transaction_events, parse_transaction_events_with_slot_filter, parse_logs_only, parse_transaction_with_listener,
+    EventListener,
+    parse_transaction_events_streaming, parse_logs_streaming, parse_transaction_with_streaming_listener, StreamingEventListener,
+    parse_confirmed_transaction, parse_confirmed_transaction_with_filter, parse_confirmed_transaction_with_slot_filter,
+    parse_versioned_transaction, parse_versioned_transaction_with_filter,
 };
 
 pub use crate::accounts::{
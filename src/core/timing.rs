@@ -0,0 +1,155 @@
+//! 分阶段延迟时间戳（`stage-timing` feature）
+//!
+//! 开启后 `EventMetadata::timing` 记录解析开始 / 结束、离开排序缓冲、写入输出四个时间点，
+//! `EventMetadata::latency_breakdown()` 按阶段拆分延迟。
+//! 未开启时 `StageTimestamps` 是零大小类型，记录函数为空操作，热路径没有额外开销。
+
+use super::events::{DexEvent, EventMetadata};
+use serde::{Deserialize, Serialize};
+
+/// 事件在本地各阶段的时间戳（微秒，0 表示未记录）
+#[cfg(feature = "stage-timing")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageTimestamps {
+    pub parse_start_us: i64,
+    pub parse_end_us: i64,
+    /// 离开排序缓冲、进入输出
+    pub buffer_release_us: i64,
+    /// 写入队列 / 通道
+    pub queue_push_us: i64,
+}
+
+/// 事件在本地各阶段的时间戳（未开启 `stage-timing`，不记录）
+#[cfg(not(feature = "stage-timing"))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageTimestamps;
+
+/// 各阶段耗时（微秒），缺少时间戳的阶段为 0
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyBreakdown {
+    /// block_time_us → grpc_recv_us（服务端时间戳到本地接收）
+    pub network_us: i64,
+    /// grpc_recv_us → parse_start（接收后等待解析）
+    pub dispatch_us: i64,
+    /// parse_start → parse_end
+    pub parse_us: i64,
+    /// parse_end → buffer_release（有序模式的排序缓冲）
+    pub ordering_us: i64,
+    /// buffer_release → queue_push（输出写满时的暂存）
+    pub output_us: i64,
+    /// queue_push → 调用时刻（等待消费者）
+    pub consumer_us: i64,
+    /// grpc_recv_us → 调用时刻
+    pub local_total_us: i64,
+}
+
+/// 当前时间（微秒）；未开启 feature 时返回 0，不读时钟
+#[inline(always)]
+pub(crate) fn now_us() -> i64 {
+    #[cfg(feature = "stage-timing")]
+    {
+        crate::grpc::client::get_timestamp_us()
+    }
+    #[cfg(not(feature = "stage-timing"))]
+    {
+        0
+    }
+}
+
+/// 记录解析开始 / 结束
+#[inline(always)]
+pub(crate) fn mark_parsed(events: &mut [DexEvent], start_us: i64) {
+    #[cfg(feature = "stage-timing")]
+    {
+        let end_us = now_us();
+        for m in events.iter_mut().filter_map(DexEvent::metadata_mut) {
+            m.timing.parse_start_us = start_us;
+            m.timing.parse_end_us = end_us;
+        }
+    }
+    #[cfg(not(feature = "stage-timing"))]
+    let _ = (events, start_us);
+}
+
+/// 记录离开排序缓冲
+#[inline(always)]
+pub(crate) fn mark_buffer_release(event: &mut DexEvent) {
+    #[cfg(feature = "stage-timing")]
+    if let Some(m) = event.metadata_mut() {
+        m.timing.buffer_release_us = now_us();
+    }
+    #[cfg(not(feature = "stage-timing"))]
+    let _ = event;
+}
+
+/// 记录写入输出
+#[inline(always)]
+pub(crate) fn mark_queue_push(event: &mut DexEvent) {
+    #[cfg(feature = "stage-timing")]
+    if let Some(m) = event.metadata_mut() {
+        m.timing.queue_push_us = now_us();
+    }
+    #[cfg(not(feature = "stage-timing"))]
+    let _ = event;
+}
+
+#[inline]
+fn span(from: i64, to: i64) -> i64 {
+    if from > 0 && to > 0 { (to - from).max(0) } else { 0 }
+}
+
+impl EventMetadata {
+    /// 按阶段拆分延迟，消费者收到事件后调用
+    ///
+    /// 未开启 `stage-timing` 时只有 `network_us` 和 `local_total_us`。
+    pub fn latency_breakdown(&self) -> LatencyBreakdown {
+        let now = crate::grpc::client::get_timestamp_us();
+        #[allow(unused_mut)]
+        let mut breakdown = LatencyBreakdown {
+            network_us: span(self.block_time_us, self.grpc_recv_us),
+            local_total_us: span(self.grpc_recv_us, now),
+            ..Default::default()
+        };
+        #[cfg(feature = "stage-timing")]
+        {
+            let t = &self.timing;
+            breakdown.dispatch_us = span(self.grpc_recv_us, t.parse_start_us);
+            breakdown.parse_us = span(t.parse_start_us, t.parse_end_us);
+            breakdown.ordering_us = span(t.parse_end_us, t.buffer_release_us);
+            breakdown.output_us = span(t.buffer_release_us, t.queue_push_us);
+            breakdown.consumer_us = span(t.queue_push_us, now);
+        }
+        breakdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::PumpFunTradeEvent;
+
+    #[test]
+    fn test_latency_breakdown() {
+        let mut event = DexEvent::PumpFunTrade(PumpFunTradeEvent::default());
+        let m = event.metadata_mut().unwrap();
+        m.block_time_us = 1_000;
+        m.grpc_recv_us = 1_500;
+        let start = m.grpc_recv_us + 10;
+        mark_parsed(std::slice::from_mut(&mut event), start);
+        mark_buffer_release(&mut event);
+        mark_queue_push(&mut event);
+
+        let b = event.metadata().unwrap().latency_breakdown();
+        assert_eq!(b.network_us, 500);
+        assert!(b.local_total_us > 0);
+        #[cfg(feature = "stage-timing")]
+        {
+            assert_eq!(b.dispatch_us, 10);
+            assert!(b.parse_us > 0 && b.consumer_us >= 0);
+            let t = event.metadata().unwrap().timing;
+            assert!(t.parse_end_us <= t.buffer_release_us && t.buffer_release_us <= t.queue_push_us);
+        }
+        #[cfg(not(feature = "stage-timing"))]
+        assert_eq!(std::mem::size_of::<StageTimestamps>(), 0);
+    }
+}
@@ -3,7 +3,9 @@
 //! 提供完整的交易解析能力，支持指令和日志数据处理
 
 use crate::core::events::*;
-use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use crate::grpc::types::{EventTypeFilter, SlotFilter};
+use solana_sdk::{pubkey::Pubkey, signature::Signature, transaction::VersionedTransaction};
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionStatusMeta};
 
 /// 主要解析函数 - 解析完整交易并返回所有 DEX 事件
 ///
@@ -13,8 +15,11 @@ use solana_sdk::{pubkey::Pubkey, signature::Signature};
 /// - logs: 交易日志
 /// - signature: 交易签名
 /// - slot: 区块高度
+/// - tx_index: 交易在区块中的索引
 /// - block_time_us: 区块时间
 /// - program_id: 程序 ID
+///
+/// 事件顺序与 gRPC 路径一致：日志事件在前，指令事件在后
 pub fn parse_transaction_events(
     instruction_data: &[u8],
     accounts: &[Pubkey],
@@ -25,26 +30,38 @@ pub fn parse_transaction_events(
     block_time_us: Option<i64>,
     program_id: &Pubkey,
 ) -> Vec<DexEvent> {
-    let mut instruction_events = Vec::new();
-    let mut log_events = Vec::new();
-
-    // 1. 解析指令事件
-    // if let Some(instr_event) = crate::instr::parse_instruction_unified(
-    //     instruction_data, accounts, signature, slot, tx_index, block_time_us, program_id
-    // ) {
-    //     instruction_events.push(instr_event);
-    // }
+    let mut events = Vec::with_capacity(4);
+    parse_transaction_events_streaming(
+        instruction_data,
+        accounts,
+        logs,
+        signature,
+        slot,
+        tx_index,
+        block_time_us,
+        program_id,
+        |event| events.push(event),
+    );
+    events
+}
 
-    // 2. 解析日志事件
-    for log in logs {
-        if let Some(log_event) = crate::logs::parse_log_unified(log, signature, slot, block_time_us) {
-            log_events.push(log_event);
-        }
+/// 同 [`parse_transaction_events`]，slot 不在 `slot_filter` 区间内的交易不解析、返回空
+#[allow(clippy::too_many_arguments)]
+pub fn parse_transaction_events_with_slot_filter(
+    instruction_data: &[u8],
+    accounts: &[Pubkey],
+    logs: &[String],
+    signature: Signature,
+    slot: u64,
+    tx_index: u64,
+    block_time_us: Option<i64>,
+    program_id: &Pubkey,
+    slot_filter: Option<&SlotFilter>,
+) -> Vec<DexEvent> {
+    if slot_filter.is_some_and(|f| !f.contains(slot)) {
+        return Vec::new();
     }
-
-    // 3. 合并指令和日志事件
-    instruction_events.extend(log_events);
-    instruction_events
+    parse_transaction_events(instruction_data, accounts, logs, signature, slot, tx_index, block_time_us, program_id)
 }
 
 /// 简化版本 - 仅解析日志事件
@@ -108,22 +125,87 @@ pub fn parse_transaction_events_streaming<F>(
 ) where
     F: FnMut(DexEvent)
 {
-    // 1. 先解析指令事件（如果有） - 立即回调
-    // if let Some(instr_event) = crate::instr::parse_instruction_unified(
-    //     instruction_data, accounts, signature, slot, tx_index, block_time_us, program_id
-    // ) {
-    //     callback(instr_event);  // 立即回调指令事件
-    // }
-
-    // 2. 逐个解析日志事件 - 每个事件立即回调
+    let recv_us = crate::grpc::client::get_timestamp_us();
+    let has_create = crate::logs::optimized_matcher::detect_pumpfun_create(logs);
+
+    // 1. 先解析指令事件（不依赖日志），用于补齐 / 去重日志事件
+    let mut instruction_events = Vec::new();
+    crate::instr::parse_instruction_events(
+        instruction_data, accounts, signature, slot, tx_index, block_time_us, recv_us, None, program_id,
+        |event| instruction_events.push(event),
+    );
+    let mut merger = InstructionMerger::new(instruction_events);
+
+    // 2. 逐个解析日志事件 - 补齐指令字段后立即回调
     for log in logs {
-        if let Some(log_event) = crate::logs::parse_log_unified(log, signature, slot, block_time_us) {
+        if let Some(mut log_event) =
+            crate::logs::parse_log(log, signature, slot, tx_index, block_time_us, recv_us, None, has_create)
+        {
+            merger.merge_into(&mut log_event);
             callback(log_event);  // 立即回调日志事件，不等待其他日志
         }
     }
 
-    // 注意：这里完全不做事件合并和缓存，确保每个事件都是立即回调
-    // 回调顺序：先指令事件，然后按日志顺序回调日志事件
+    // 3. 再回调日志中没有的指令事件（如 PumpFun migrate CPI），与 gRPC 路径一致
+    merger.into_remaining().for_each(callback);
+
+    // 回调顺序与 gRPC 路径一致：先按日志顺序回调日志事件，然后回调指令事件
+}
+
+/// 日志与指令事件去重用的种类：PumpFun 日志统一产出 Trade，指令按方向产出 Buy / Sell
+#[inline]
+fn dedup_kind(event: &DexEvent) -> &'static str {
+    match event {
+        DexEvent::PumpFunTrade(_)
+        | DexEvent::PumpFunBuy(_)
+        | DexEvent::PumpFunSell(_)
+        | DexEvent::PumpFunBuyExactSolIn(_) => "PumpFunTrade",
+        _ => event.kind_name(),
+    }
+}
+
+/// 指令事件与日志事件合并（gRPC 与 parse_transaction_events 共用）
+///
+/// - Orca swap：按 whirlpool 依次匹配日志中的 Traded 事件，把只有指令才有的字段
+///   （hop / route_id / token program / memo / transfer hook）补进日志事件，不再单独输出；
+///   没有对应日志的指令事件照常输出
+/// - 其余事件：日志已产出同种事件时以日志为准（日志带实际成交数量，指令只有限价参数）
+pub(crate) struct InstructionMerger {
+    pending: Vec<Option<DexEvent>>,
+    log_kinds: Vec<&'static str>,
+}
+
+impl InstructionMerger {
+    /// instruction_events 按执行顺序排列
+    pub(crate) fn new(instruction_events: Vec<DexEvent>) -> Self {
+        Self { pending: instruction_events.into_iter().map(Some).collect(), log_kinds: Vec::new() }
+    }
+
+    /// 日志事件输出前调用
+    pub(crate) fn merge_into(&mut self, log_event: &mut DexEvent) {
+        self.log_kinds.push(dedup_kind(log_event));
+        let DexEvent::OrcaWhirlpoolSwap(log) = log_event else { return };
+        let matched = self.pending.iter_mut().find(
+            |e| matches!(e, Some(DexEvent::OrcaWhirlpoolSwap(ix)) if ix.whirlpool == log.whirlpool),
+        );
+        if let Some(Some(DexEvent::OrcaWhirlpoolSwap(ix))) = matched.map(Option::take) {
+            log.hop = ix.hop;
+            log.route_id = ix.route_id;
+            log.token_program_a = ix.token_program_a;
+            log.token_program_b = ix.token_program_b;
+            log.memo_program = ix.memo_program;
+            log.transfer_hook_accounts_a = ix.transfer_hook_accounts_a;
+            log.transfer_hook_accounts_b = ix.transfer_hook_accounts_b;
+        }
+    }
+
+    /// 日志之后输出的指令事件
+    pub(crate) fn into_remaining(self) -> impl Iterator<Item = DexEvent> {
+        let log_kinds = self.log_kinds;
+        self.pending.into_iter().flatten().filter(move |e| {
+            matches!(e, DexEvent::OrcaWhirlpoolSwap(_)) || !log_kinds.contains(&dedup_kind(e))
+        })
+    }
 }
 
 /// 流式解析日志事件 - 每解析出一个事件就立即回调
@@ -171,4 +253,218 @@ pub fn parse_transaction_with_streaming_listener<T: StreamingEventListener>(
         program_id,
         |event| listener.on_dex_event_streaming(event)
     );
-}
\ No newline at end of file
+}
+
+// ====== 完整交易解析（RPC / JSON 导出） ======
+
+/// 解析 getTransaction 返回（或浏览器导出）的完整交易
+///
+/// 与 gRPC 实时流使用同一条管线：ALT 地址解析、inner instruction 索引、account_filler 账户填充。
+/// 支持 base58/base64/json/jsonParsed 编码；交易无法解码或缺少 meta 时返回空。
+/// 交易在区块中的位置未知，tx_index 为 0。
+pub fn parse_confirmed_transaction(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<DexEvent> {
+    parse_confirmed_transaction_with_filter(tx, None)
+}
+
+/// 同 [`parse_confirmed_transaction`]，附带事件类型过滤
+pub fn parse_confirmed_transaction_with_filter(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    filter: Option<&EventTypeFilter>,
+) -> Vec<DexEvent> {
+    parse_confirmed_transaction_with_slot_filter(tx, filter, None)
+}
+
+/// 同 [`parse_confirmed_transaction_with_filter`]，slot 不在 `slot_filter` 区间内的交易不解析、返回空
+pub fn parse_confirmed_transaction_with_slot_filter(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    filter: Option<&EventTypeFilter>,
+    slot_filter: Option<&SlotFilter>,
+) -> Vec<DexEvent> {
+    if slot_filter.is_some_and(|f| !f.contains(tx.slot)) {
+        return Vec::new();
+    }
+    let Some(update) = crate::rpc::convert::encoded_to_update(&tx.transaction, tx.slot, 0) else {
+        return Vec::new();
+    };
+    let block_time_us = tx.block_time.map(|t| t * 1_000_000);
+    crate::grpc::client::parse_transaction_core(&update, crate::grpc::client::get_timestamp_us(), block_time_us, filter)
+}
+
+/// 解析已解码的交易 + RPC meta
+///
+/// 不含 slot / 区块时间信息（均为 0），需要时请使用 [`parse_confirmed_transaction`]
+pub fn parse_versioned_transaction(tx: &VersionedTransaction, meta: &UiTransactionStatusMeta) -> Vec<DexEvent> {
+    parse_versioned_transaction_with_filter(tx, meta, None)
+}
+
+/// 同 [`parse_versioned_transaction`]，附带事件类型过滤
+pub fn parse_versioned_transaction_with_filter(
+    tx: &VersionedTransaction,
+    meta: &UiTransactionStatusMeta,
+    filter: Option<&EventTypeFilter>,
+) -> Vec<DexEvent> {
+    let update = crate::rpc::convert::versioned_to_update(tx, meta, 0, 0);
+    crate::grpc::client::parse_transaction_core(&update, crate::grpc::client::get_timestamp_us(), None, filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instr::program_ids::PUMPFUN_PROGRAM_ID;
+    use crate::test_fixtures;
+
+    fn migrate_cpi_data(mint: &Pubkey, pool: &Pubkey) -> Vec<u8> {
+        test_fixtures::pumpfun_migrate_cpi_data(&Pubkey::new_unique(), mint, &Pubkey::new_unique(), pool)
+    }
+
+    #[test]
+    fn test_parse_transaction_events_merges_logs_and_instructions() {
+        let (mint, user, pool) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let sig = Signature::from([5u8; 64]);
+        let data = migrate_cpi_data(&mint, &pool);
+        let logs = test_fixtures::pumpfun_trade_logs(&mint, &user);
+
+        let events = parse_transaction_events(&data, &[], &logs, sig, 9, 4, Some(1), &PUMPFUN_PROGRAM_ID);
+        assert_eq!(events.len(), 2, "{:?}", events);
+        match &events[0] {
+            DexEvent::PumpFunTrade(e) => {
+                assert_eq!(e.mint, mint);
+                assert_eq!(e.metadata.tx_index, 4);
+            }
+            other => panic!("expected log-derived trade, got {:?}", other),
+        }
+        match &events[1] {
+            DexEvent::PumpFunMigrate(e) => {
+                assert_eq!(e.pool, pool);
+                assert_eq!(e.metadata.slot, 9);
+            }
+            other => panic!("expected instruction-derived migrate, got {:?}", other),
+        }
+
+        // 仅指令 / 仅日志
+        assert_eq!(parse_transaction_events(&data, &[], &[], sig, 9, 4, None, &PUMPFUN_PROGRAM_ID).len(), 1);
+        assert_eq!(parse_transaction_events(&[], &[], &logs, sig, 9, 4, None, &PUMPFUN_PROGRAM_ID).len(), 1);
+    }
+
+    #[test]
+    fn test_parse_transaction_events_with_slot_filter() {
+        let logs = test_fixtures::pumpfun_trade_logs(&Pubkey::new_unique(), &Pubkey::new_unique());
+        let parse = |slot: u64| {
+            parse_transaction_events_with_slot_filter(
+                &[], &[], &logs, Signature::default(), slot, 0, None, &PUMPFUN_PROGRAM_ID,
+                Some(&SlotFilter::range(100, 123)),
+            )
+        };
+        assert_eq!(parse(100).len(), 1);
+        assert!(parse(99).is_empty() && parse(124).is_empty());
+    }
+
+    #[test]
+    fn test_parse_transaction_events_skips_instruction_duplicates() {
+        let (mint, user, bonding_curve) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = test_fixtures::pumpfun_buy_instruction(mint, bonding_curve, user, 1_000_000, 50_000_000);
+        let accounts: Vec<Pubkey> = ix.accounts.iter().map(|m| m.pubkey).collect();
+        let logs = test_fixtures::pumpfun_trade_logs(&mint, &user);
+
+        // 带真实账户的 buy 指令 + 对应 TradeEvent 日志：只产出日志中的成交
+        let events = parse_transaction_events(
+            &ix.data, &accounts, &logs, Signature::default(), 1, 0, None, &PUMPFUN_PROGRAM_ID,
+        );
+        assert_eq!(events.len(), 1, "{:?}", events);
+        match &events[0] {
+            DexEvent::PumpFunTrade(e) => assert_eq!(e.sol_amount, test_fixtures::TRADE_SOL_AMOUNT),
+            other => panic!("expected log-derived trade, got {:?}", other),
+        }
+
+        // 没有日志时指令事件照常产出
+        let events = parse_transaction_events(
+            &ix.data, &accounts, &[], Signature::default(), 1, 0, None, &PUMPFUN_PROGRAM_ID,
+        );
+        assert!(matches!(events.as_slice(), [DexEvent::PumpFunBuy(_)]), "{:?}", events);
+    }
+
+    #[test]
+    fn test_parse_transaction_events_streaming_order() {
+        let (mint, user, pool) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let data = migrate_cpi_data(&mint, &pool);
+        let logs = test_fixtures::pumpfun_trade_logs(&mint, &user);
+
+        let mut kinds = Vec::new();
+        parse_transaction_events_streaming(
+            &data, &[], &logs, Signature::default(), 1, 0, None, &PUMPFUN_PROGRAM_ID,
+            |e| kinds.push(matches!(e, DexEvent::PumpFunMigrate(_))),
+        );
+        assert_eq!(kinds, vec![false, true]);
+    }
+
+    #[test]
+    fn test_parse_confirmed_transaction_json_with_alt() {
+        let (user, mint, bonding_curve, table) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let sig = Signature::from([3u8; 64]);
+        let trade_log = test_fixtures::program_data_log(&test_fixtures::pumpfun_trade_event_data(&mint, &user));
+
+        // 浏览器导出的 json 编码 v0 交易：bonding_curve 通过 ALT 加载（全局索引 2）
+        let dump = serde_json::json!({
+            "slot": 123,
+            "blockTime": 1_700_000_000,
+            "transaction": {
+                "signatures": [sig.to_string()],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 1,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 1
+                    },
+                    "accountKeys": [user.to_string(), PUMPFUN_PROGRAM_ID.to_string()],
+                    "recentBlockhash": "11111111111111111111111111111111",
+                    "instructions": [{
+                        "programIdIndex": 1,
+                        "accounts": [0, 0, 0, 2, 0, 0, 0],
+                        "data": bs58::encode(crate::instr::pump::discriminators::BUY).into_string(),
+                        "stackHeight": null
+                    }],
+                    "addressTableLookups": [{
+                        "accountKey": table.to_string(),
+                        "writableIndexes": [0],
+                        "readonlyIndexes": []
+                    }]
+                }
+            },
+            "meta": {
+                "err": null,
+                "status": {"Ok": null},
+                "fee": 5000,
+                "preBalances": [],
+                "postBalances": [],
+                "innerInstructions": [],
+                "logMessages": [
+                    format!("Program {} invoke [1]", PUMPFUN_PROGRAM_ID),
+                    trade_log,
+                    format!("Program {} success", PUMPFUN_PROGRAM_ID)
+                ],
+                "loadedAddresses": {"writable": [bonding_curve.to_string()], "readonly": []}
+            },
+            "version": 0
+        });
+        let tx: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_value(dump).unwrap();
+
+        let events = parse_confirmed_transaction(&tx);
+        assert_eq!(events.len(), 1, "{:?}", events);
+        match &events[0] {
+            DexEvent::PumpFunTrade(e) => {
+                assert_eq!(e.mint, mint);
+                assert_eq!(e.bonding_curve, bonding_curve);
+                assert_eq!(e.metadata.signature, sig);
+                assert_eq!(e.metadata.slot, 123);
+                assert_eq!(e.metadata.block_time_us, 1_700_000_000_000_000);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        let in_range = SlotFilter::range(100, 123);
+        assert_eq!(parse_confirmed_transaction_with_slot_filter(&tx, None, Some(&in_range)).len(), 1);
+        let past = SlotFilter::new().max_slot(122);
+        assert!(parse_confirmed_transaction_with_slot_filter(&tx, None, Some(&past)).is_empty());
+    }
+}
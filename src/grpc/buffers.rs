@@ -3,8 +3,11 @@
 //! 提供多种缓冲策略：
 //! - `SlotBuffer`: 按 slot 缓冲，支持 Ordered 和 StreamingOrdered 模式
 //! - `MicroBatchBuffer`: 微秒级时间窗口批次，用于 MicroBatch 模式
+//! - `ForkGuard`: 按 slot 状态暂扣 / 撤销 processed 事件，用于 `ForkPolicy`
 
+use super::types::ForkPolicy;
 use crate::DexEvent;
+use solana_sdk::signature::Signature;
 use std::collections::{BTreeMap, HashMap};
 use tokio::time::Instant;
 
@@ -142,6 +145,28 @@ impl SlotBuffer {
         result
     }
 
+    /// 取出指定 slot 的全部事件（按 tx_index 排序）
+    pub fn take_slot(&mut self, slot: u64) -> Vec<DexEvent> {
+        self.streaming_watermarks.remove(&slot);
+        let Some(mut events) = self.slots.remove(&slot) else { return Vec::new() };
+        events.sort_by_key(|(idx, _)| *idx);
+        events.into_iter().map(|(_, e)| e).collect()
+    }
+
+    /// 丢弃指定 slot 的缓冲事件，返回丢弃数量
+    pub fn discard_slot(&mut self, slot: u64) -> usize {
+        self.streaming_watermarks.remove(&slot);
+        self.slots.remove(&slot).map_or(0, |events| events.len())
+    }
+
+    /// 丢弃所有小于 slot 的缓冲事件，返回丢弃数量
+    pub fn discard_before(&mut self, slot: u64) -> usize {
+        let kept = self.slots.split_off(&slot);
+        let dropped = std::mem::replace(&mut self.slots, kept);
+        self.streaming_watermarks.retain(|s, _| *s >= slot);
+        dropped.values().map(Vec::len).sum()
+    }
+
     /// 流式模式超时释放
     pub fn flush_streaming_timeout(&mut self) -> Vec<DexEvent> {
         let mut result = Vec::new();
@@ -217,3 +242,79 @@ impl Default for MicroBatchBuffer {
         Self::new()
     }
 }
+
+// ==================== ForkGuard ====================
+
+/// 分叉保护：Withhold 暂扣未确认 slot 的事件，Retract 记录已输出事件的签名
+///
+/// 状态跨重连保留；finalized 之前仍未确认的 slot 视为已被放弃。
+pub struct ForkGuard {
+    policy: ForkPolicy,
+    /// Withhold：未确认 slot 的事件
+    withheld: SlotBuffer,
+    /// Retract：未确认 slot 已输出事件的签名
+    emitted: BTreeMap<u64, Vec<Signature>>,
+}
+
+impl ForkGuard {
+    pub fn new(policy: ForkPolicy) -> Self {
+        Self { policy, withheld: SlotBuffer::new(), emitted: BTreeMap::new() }
+    }
+
+    #[inline]
+    pub fn policy(&self) -> ForkPolicy {
+        self.policy
+    }
+
+    /// Withhold：暂扣事件直到 slot 确认
+    #[inline]
+    pub fn withhold(&mut self, slot: u64, tx_index: u64, event: DexEvent) {
+        self.withheld.push(slot, tx_index, event);
+    }
+
+    /// Retract：记录已输出事件所属交易
+    #[inline]
+    pub fn record(&mut self, slot: u64, signature: Signature) {
+        let signatures = self.emitted.entry(slot).or_default();
+        if signatures.last() != Some(&signature) {
+            signatures.push(signature);
+        }
+    }
+
+    /// slot confirmed：返回该 slot 暂扣的事件
+    pub fn on_confirmed(&mut self, slot: u64) -> Vec<DexEvent> {
+        self.emitted.remove(&slot);
+        self.withheld.take_slot(slot)
+    }
+
+    /// slot finalized：释放该 slot，并丢弃更早仍未确认的 slot
+    pub fn on_finalized(&mut self, slot: u64) -> Finalized {
+        let released = self.on_confirmed(slot);
+        let discarded = self.withheld.discard_before(slot);
+        let kept = self.emitted.split_off(&slot);
+        let orphaned = std::mem::replace(&mut self.emitted, kept).into_iter().collect();
+        Finalized { released, orphaned, discarded }
+    }
+
+    /// Withhold：是否还有 slot <= max_slot 的事件未确认
+    #[inline]
+    pub fn is_withholding_through(&self, max_slot: u64) -> bool {
+        self.withheld.slots.range(..=max_slot).next().is_some()
+    }
+
+    /// slot dead：丢弃暂扣事件，返回需撤销的签名与丢弃的暂扣事件数
+    pub fn on_dead(&mut self, slot: u64) -> (Vec<Signature>, usize) {
+        let discarded = self.withheld.discard_slot(slot);
+        (self.emitted.remove(&slot).unwrap_or_default(), discarded)
+    }
+}
+
+/// `ForkGuard::on_finalized` 的结果
+pub struct Finalized {
+    /// 该 slot 暂扣的事件
+    pub released: Vec<DexEvent>,
+    /// Retract：更早仍未确认（被放弃）的 slot 及其已输出事件的签名，按 slot 升序
+    pub orphaned: Vec<(u64, Vec<Signature>)>,
+    /// Withhold：更早仍未确认的 slot 被丢弃的暂扣事件数
+    pub discarded: usize,
+}
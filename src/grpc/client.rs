@@ -6,21 +6,25 @@
 //! - StreamingOrdered: 0.1-5ms 流式有序
 //! - Ordered: 1-50ms 完全有序
 
-use super::buffers::{MicroBatchBuffer, SlotBuffer};
+use super::buffers::{ForkGuard, MicroBatchBuffer, SlotBuffer};
+use super::error::{ConnectionEvent, GrpcError};
+use super::output::{EventSink, EventTarget};
+use super::resume::{is_replay_error, ResumeTracker};
 use super::types::*;
-use crate::core::EventMetadata;
+use crate::common::{MetricsEventType, MetricsManager, MetricsSnapshot, SubscriptionHandle};
+use crate::core::{timing, BlockMetaEvent, EventMetadata, SlotStatusEvent};
 use crate::instr::read_pubkey_fast;
 use crate::logs::timestamp_to_microseconds;
 use crate::DexEvent;
 use crossbeam_queue::ArrayQueue;
-use futures::{SinkExt, StreamExt};
-use log::error;
+use futures::{SinkExt, Stream, StreamExt};
+use log::{debug, error, info, warn};
 use memchr::memmem;
 use once_cell::sync::Lazy;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{broadcast, mpsc, watch, Mutex};
 use tokio::time::{Duration, Instant};
 use tonic::transport::ClientTlsConfig;
 use yellowstone_grpc_client::GeyserGrpcClient;
@@ -37,52 +41,306 @@ pub struct YellowstoneGrpc {
     token: Option<String>,
     config: ClientConfig,
     control_tx: Arc<Mutex<Option<mpsc::Sender<SubscribeRequest>>>>,
+    /// 活跃订阅的停止信号，`stop()` 时统一通知
+    subscriptions: Arc<std::sync::Mutex<Vec<Arc<watch::Sender<bool>>>>>,
+    metrics: Arc<MetricsManager>,
+    /// 连接状态事件
+    connection_tx: broadcast::Sender<ConnectionEvent>,
+    connection_callback: Arc<std::sync::RwLock<Option<ConnectionCallback>>>,
 }
 
+type ConnectionCallback = Arc<dyn Fn(&ConnectionEvent) + Send + Sync>;
+
 impl YellowstoneGrpc {
-    pub fn new(endpoint: String, token: Option<String>) -> Result<Self, Box<dyn std::error::Error>> {
-        crate::warmup::warmup_parser();
-        Ok(Self {
-            endpoint,
-            token,
-            config: ClientConfig::default(),
-            control_tx: Arc::new(Mutex::new(None)),
-        })
+    pub fn new(endpoint: String, token: Option<String>) -> Result<Self, GrpcError> {
+        Self::new_with_config(endpoint, token, ClientConfig::default())
     }
 
     pub fn new_with_config(
         endpoint: String,
         token: Option<String>,
         config: ClientConfig,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
+    ) -> Result<Self, GrpcError> {
+        config.validate()?;
         crate::warmup::warmup_parser();
-        Ok(Self { endpoint, token, config, control_tx: Arc::new(Mutex::new(None)) })
+        let metrics = Arc::new(MetricsManager::new(config.enable_metrics, "YellowstoneGrpc".to_string()));
+        Ok(Self::with_metrics(endpoint, token, config, metrics))
+    }
+
+    /// 测试用：不触发 warmup
+    #[cfg(test)]
+    pub(crate) fn new_unwarmed(endpoint: String, config: ClientConfig) -> Self {
+        Self::with_metrics(endpoint, None, config, Arc::new(MetricsManager::new(true, "test".to_string())))
+    }
+
+    fn with_metrics(endpoint: String, token: Option<String>, config: ClientConfig, metrics: Arc<MetricsManager>) -> Self {
+        Self {
+            endpoint,
+            token,
+            config,
+            control_tx: Arc::new(Mutex::new(None)),
+            subscriptions: Arc::new(std::sync::Mutex::new(Vec::new())),
+            metrics,
+            connection_tx: broadcast::channel(64).0,
+            connection_callback: Arc::new(std::sync::RwLock::new(None)),
+        }
+    }
+
+    #[inline]
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// 指标快照：收到的更新数、按类型 / 协议的输出事件数、解析延迟、输出积压和丢弃数
+    ///
+    /// 需要 `ClientConfig::enable_metrics`，否则各项均为 0。同一客户端上的全部订阅共用一份指标。
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// 在 addr 上以 Prometheus 文本格式导出该客户端的指标（`GET /metrics`，需 `prometheus` feature）
+    #[cfg(feature = "prometheus")]
+    pub async fn serve_prometheus(
+        &self,
+        addr: std::net::SocketAddr,
+    ) -> std::io::Result<(std::net::SocketAddr, tokio::task::JoinHandle<()>)> {
+        let metrics = Arc::clone(&self.metrics);
+        let stream = self.endpoint.clone();
+        crate::common::prometheus::serve(addr, move || {
+            crate::common::prometheus::render(&[(stream.as_str(), metrics.snapshot())])
+        })
+        .await
+    }
+
+    /// 订阅连接状态事件（Connecting / Connected / Disconnected / Reconnecting / Stopped）
+    ///
+    /// 可用 `tokio_stream::wrappers::BroadcastStream` 包装为 `Stream`。
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.connection_tx.subscribe()
     }
 
-    /// 订阅 DEX 事件（自动重连）
+    /// 设置连接状态回调（在订阅任务中同步调用，应尽快返回）
+    pub fn on_connection_event(&self, callback: impl Fn(&ConnectionEvent) + Send + Sync + 'static) {
+        *self.connection_callback.write().unwrap() = Some(Arc::new(callback));
+    }
+
+    fn notify(&self, event: ConnectionEvent) {
+        let callback = self.connection_callback.read().unwrap().clone();
+        if let Some(callback) = callback {
+            callback(&event);
+        }
+        let _ = self.connection_tx.send(event);
+    }
+
+    /// 订阅 DEX 事件（自动重连），输出到无锁队列
+    ///
+    /// 适合自旋轮询的极低延迟消费者；tokio 消费者可使用
+    /// `subscribe_dex_events_stream` / `_channel` / `_broadcast`。
+    ///
+    /// 返回事件队列和订阅句柄；`handle.stop_and_join()` 会输出有序模式下缓冲的事件后结束后台任务。
+    /// 丢弃句柄不会停止订阅，可通过 `stop()` 停止该客户端上的全部订阅。
     pub async fn subscribe_dex_events(
         &self,
         transaction_filters: Vec<TransactionFilter>,
         account_filters: Vec<AccountFilter>,
         event_type_filter: Option<EventTypeFilter>,
-    ) -> Result<Arc<ArrayQueue<DexEvent>>, Box<dyn std::error::Error>> {
-        let queue = Arc::new(ArrayQueue::new(100_000));
-        let queue_clone = Arc::clone(&queue);
+    ) -> Result<(Arc<ArrayQueue<DexEvent>>, SubscriptionHandle), GrpcError> {
+        self.subscribe_dex_events_with_slot_filter(transaction_filters, account_filters, event_type_filter, None).await
+    }
+
+    /// 同 [`subscribe_dex_events`](Self::subscribe_dex_events)，只输出 slot 区间内的事件
+    ///
+    /// 区间外的更新在解析前丢弃；设置了 max_slot 时，收到之后的更新即输出缓冲事件并结束订阅
+    /// （`handle.join()` 返回，连接事件以 `Stopped` 结束）。
+    pub async fn subscribe_dex_events_with_slot_filter(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        slot_filter: Option<SlotFilter>,
+    ) -> Result<(Arc<ArrayQueue<DexEvent>>, SubscriptionHandle), GrpcError> {
+        let queue = Arc::new(ArrayQueue::new(self.queue_capacity()));
+        let target = EventTarget::Queue(Arc::clone(&queue));
+        let handle = self.spawn_subscription(transaction_filters, account_filters, event_type_filter, slot_filter, target)?;
+        Ok((queue, handle))
+    }
+
+    /// 订阅 DEX 事件，输出为异步 `Stream`
+    pub async fn subscribe_dex_events_stream(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+    ) -> Result<(impl Stream<Item = DexEvent> + Send + Unpin + 'static, SubscriptionHandle), GrpcError> {
+        self.subscribe_dex_events_stream_with_slot_filter(transaction_filters, account_filters, event_type_filter, None)
+            .await
+    }
+
+    /// 同 [`subscribe_dex_events_stream`](Self::subscribe_dex_events_stream)，只输出 slot 区间内的事件
+    ///
+    /// 超过 max_slot 后 `Stream` 结束（返回 `None`）。
+    pub async fn subscribe_dex_events_stream_with_slot_filter(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        slot_filter: Option<SlotFilter>,
+    ) -> Result<(impl Stream<Item = DexEvent> + Send + Unpin + 'static, SubscriptionHandle), GrpcError> {
+        let (rx, handle) = self
+            .subscribe_dex_events_channel_with_slot_filter(transaction_filters, account_filters, event_type_filter, slot_filter)
+            .await?;
+        Ok((tokio_stream::wrappers::ReceiverStream::new(rx), handle))
+    }
+
+    /// 订阅 DEX 事件，输出到 `tokio::sync::mpsc` 通道
+    pub async fn subscribe_dex_events_channel(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+    ) -> Result<(mpsc::Receiver<DexEvent>, SubscriptionHandle), GrpcError> {
+        self.subscribe_dex_events_channel_with_slot_filter(transaction_filters, account_filters, event_type_filter, None)
+            .await
+    }
+
+    /// 同 [`subscribe_dex_events_channel`](Self::subscribe_dex_events_channel)，只输出 slot 区间内的事件
+    ///
+    /// 超过 max_slot 后通道关闭。
+    pub async fn subscribe_dex_events_channel_with_slot_filter(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        slot_filter: Option<SlotFilter>,
+    ) -> Result<(mpsc::Receiver<DexEvent>, SubscriptionHandle), GrpcError> {
+        self.config.validate_channel_target()?;
+        let (tx, rx) = mpsc::channel(self.queue_capacity());
+        let target = EventTarget::Channel(tx);
+        let handle = self.spawn_subscription(transaction_filters, account_filters, event_type_filter, slot_filter, target)?;
+        Ok((rx, handle))
+    }
+
+    /// 订阅 DEX 事件，通过 `tokio::sync::broadcast` 扇出
+    ///
+    /// 更多消费者通过 `receiver.resubscribe()` 获得；落后超过容量的消费者会收到 `Lagged`。
+    pub async fn subscribe_dex_events_broadcast(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+    ) -> Result<(broadcast::Receiver<DexEvent>, SubscriptionHandle), GrpcError> {
+        self.subscribe_dex_events_broadcast_with_slot_filter(transaction_filters, account_filters, event_type_filter, None)
+            .await
+    }
+
+    /// 同 [`subscribe_dex_events_broadcast`](Self::subscribe_dex_events_broadcast)，只输出 slot 区间内的事件
+    ///
+    /// 超过 max_slot 后发送端释放，接收端收到 `Closed`。
+    pub async fn subscribe_dex_events_broadcast_with_slot_filter(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        slot_filter: Option<SlotFilter>,
+    ) -> Result<(broadcast::Receiver<DexEvent>, SubscriptionHandle), GrpcError> {
+        let (tx, rx) = broadcast::channel(self.queue_capacity());
+        let target = EventTarget::Broadcast(tx);
+        let handle = self.spawn_subscription(transaction_filters, account_filters, event_type_filter, slot_filter, target)?;
+        Ok((rx, handle))
+    }
+
+    /// 启动后台订阅任务（自动重连，可通过句柄停止）
+    fn spawn_subscription(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        slot_filter: Option<SlotFilter>,
+        target: EventTarget,
+    ) -> Result<SubscriptionHandle, GrpcError> {
+        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+        let shutdown_tx = Arc::new(shutdown_tx);
+        {
+            let mut subs = self.subscriptions.lock().unwrap();
+            subs.retain(|tx| !tx.is_closed());
+            if subs.len() >= self.config.max_concurrent_streams as usize {
+                return Err(GrpcError::SubscriptionRejected(format!(
+                    "max_concurrent_streams ({}) reached",
+                    self.config.max_concurrent_streams
+                )));
+            }
+            subs.push(Arc::clone(&shutdown_tx));
+        }
+
         let self_clone = self.clone();
+        let mut sink = EventSink::new(target, self.config.overflow_policy, Arc::clone(&self.metrics));
+        let mut state = StreamState {
+            fork: ForkGuard::new(self.config.effective_fork_policy()),
+            resume: ResumeTracker::new(),
+            block_include_failed: transaction_filters.iter().any(|f| f.failed != Some(false)),
+            block_include_vote: transaction_filters.iter().any(|f| f.vote != Some(false)),
+            block_tx_filters: BlockTxFilter::from_filters(&transaction_filters),
+            established: false,
+            slot_filter,
+            past_max_slot: false,
+        };
 
-        tokio::spawn(async move {
-            let mut delay = 1u64;
+        let stream_handle = tokio::spawn(async move {
+            let endpoint = self_clone.endpoint.clone();
+            let retry_delay = Duration::from_millis(self_clone.config.retry_delay_ms);
+            let max_retries = self_clone.config.max_retries;
+            let mut delay = retry_delay;
+            let mut failures = 0u32;
             loop {
-                match self_clone.stream_events(&transaction_filters, &account_filters, &event_type_filter, &queue_clone).await {
-                    Ok(_) => delay = 1,
-                    Err(e) => println!("❌ gRPC error: {} - retry in {}s", e, delay),
+                self_clone.notify(ConnectionEvent::Connecting { endpoint: endpoint.clone(), attempt: failures + 1 });
+                let result = self_clone
+                    .stream_events(
+                        &transaction_filters, &account_filters, &event_type_filter,
+                        &mut sink, &mut state, &mut shutdown_rx,
+                    )
+                    .await;
+                // 订阅建立过即重新计算退避
+                if state.established {
+                    delay = retry_delay;
+                    failures = 0;
+                }
+                let error = result.err();
+                if let Some(ref e) = error {
+                    failures += 1;
+                    if e.is_auth() {
+                        error!("gRPC {} error: {} - retry in {:?}", endpoint, e, delay);
+                    } else {
+                        warn!("gRPC {} error: {} - retry in {:?}", endpoint, e, delay);
+                    }
+                }
+                self_clone.notify(ConnectionEvent::Disconnected { endpoint: endpoint.clone(), error });
+                if *shutdown_rx.borrow() || state.is_complete() {
+                    break;
+                }
+                if max_retries > 0 && failures > max_retries {
+                    error!("gRPC {} failed {} times in a row, giving up", endpoint, failures);
+                    break;
+                }
+                self_clone.metrics.record_reconnect();
+                self_clone.notify(ConnectionEvent::Reconnecting { endpoint: endpoint.clone(), delay });
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = wait_for_shutdown(&mut shutdown_rx) => break,
+                }
+                if failures > 0 {
+                    delay = (delay * 2).min(Duration::from_millis(MAX_RETRY_DELAY_MS));
                 }
-                tokio::time::sleep(Duration::from_secs(delay)).await;
-                delay = (delay * 2).min(60);
             }
+            sink.finish();
+            self_clone.notify(ConnectionEvent::Stopped { endpoint });
         });
 
-        Ok(queue)
+        Ok(SubscriptionHandle::new(stream_handle, None, None).with_shutdown(shutdown_tx))
+    }
+
+    #[inline]
+    fn queue_capacity(&self) -> usize {
+        self.config.queue_capacity.max(1)
     }
 
     /// 动态更新订阅过滤器
@@ -90,19 +348,24 @@ impl YellowstoneGrpc {
         &self,
         transaction_filters: Vec<TransactionFilter>,
         account_filters: Vec<AccountFilter>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), GrpcError> {
         let sender = self.control_tx.lock().await
             .as_ref()
-            .ok_or("No active subscription")?
+            .ok_or(GrpcError::NoActiveSubscription)?
             .clone();
         
-        let request = build_subscribe_request(&transaction_filters, &account_filters);
-        sender.send(request).await.map_err(|e| e.to_string())?;
+        let request = build_subscribe_request(&transaction_filters, &account_filters, &self.config);
+        sender.send(request).await.map_err(|_| GrpcError::NoActiveSubscription)?;
         Ok(())
     }
 
+    /// 停止该客户端上的全部订阅（各订阅会先输出缓冲事件再退出）
     pub async fn stop(&self) {
-        println!("🛑 Stopping gRPC subscription...");
+        info!("Stopping gRPC subscription");
+        for tx in self.subscriptions.lock().unwrap().drain(..) {
+            let _ = tx.send(true);
+        }
+        *self.control_tx.lock().await = None;
     }
 
     // ==================== 核心事件流处理 ====================
@@ -112,32 +375,81 @@ impl YellowstoneGrpc {
         tx_filters: &[TransactionFilter],
         acc_filters: &[AccountFilter],
         event_filter: &Option<EventTypeFilter>,
-        queue: &Arc<ArrayQueue<DexEvent>>,
-    ) -> Result<(), String> {
+        sink: &mut EventSink,
+        state: &mut StreamState,
+        shutdown: &mut watch::Receiver<bool>,
+    ) -> Result<(), GrpcError> {
         let _ = rustls::crypto::ring::default_provider().install_default();
         
         // 构建客户端
         let mut builder = GeyserGrpcClient::build_from_shared(self.endpoint.clone())
-            .map_err(|e| e.to_string())?
-            .x_token(self.token.clone())
-            .map_err(|e| e.to_string())?
-            .max_decoding_message_size(1024 * 1024 * 1024);
+            .map_err(|e| GrpcError::Config(e.to_string()))?
+            .x_token(self.token.clone())?
+            .max_decoding_message_size(self.config.max_decoding_message_size)
+            .buffer_size(self.config.buffer_size);
 
+        state.established = false;
         if self.config.connection_timeout_ms > 0 {
             builder = builder.connect_timeout(Duration::from_millis(self.config.connection_timeout_ms));
         }
+        if self.config.request_timeout_ms > 0 {
+            builder = builder.timeout(Duration::from_millis(self.config.request_timeout_ms));
+        }
+        if self.config.keep_alive_interval_ms > 0 {
+            builder = builder
+                .http2_keep_alive_interval(Duration::from_millis(self.config.keep_alive_interval_ms))
+                .keep_alive_timeout(Duration::from_millis(self.config.keep_alive_timeout_ms))
+                .keep_alive_while_idle(true);
+        }
         if self.config.enable_tls {
-            builder = builder.tls_config(ClientTlsConfig::new().with_native_roots()).map_err(|e| e.to_string())?;
+            builder = builder
+                .tls_config(ClientTlsConfig::new().with_native_roots())
+                .map_err(|e| GrpcError::Config(e.to_string()))?;
         }
 
-        let mut client = builder.connect().await.map_err(|e| e.to_string())?;
-        let request = build_subscribe_request(tx_filters, acc_filters);
-        
-        let (subscribe_tx, mut stream) = client
-            .subscribe_with_request(Some(request))
-            .await
-            .map_err(|e| e.to_string())?;
+        let mut request = build_subscribe_request(tx_filters, acc_filters, &self.config);
+        let resume_slot = if self.config.resume_from_slot { state.resume.resume_slot() } else { None };
+        let connect = async move {
+            let mut client = builder.connect().await?;
+            let mut truncated_at = None;
+            if let Some(from_slot) = resume_slot {
+                // 回放窗口不足时从最早可用的 slot 开始；不支持查询的服务端直接尝试 from_slot
+                let from_slot = match client.subscribe_replay_info().await {
+                    Ok(info) => match info.first_available {
+                        Some(first) if first > from_slot => {
+                            truncated_at = Some(first);
+                            first
+                        }
+                        _ => from_slot,
+                    },
+                    Err(_) => from_slot,
+                };
+                request.from_slot = Some(from_slot);
+            }
+            let (tx, stream) = client.subscribe_with_request(Some(request)).await?;
+            Ok::<_, GrpcError>((tx, stream, truncated_at))
+        };
+        let (subscribe_tx, mut stream, truncated_at) = tokio::select! {
+            res = connect => match res {
+                Ok(connected) => connected,
+                Err(e) => {
+                    if resume_slot.is_some() && is_replay_error(e.message()) {
+                        state.resume.replay_unavailable(e.message().to_string());
+                    }
+                    return Err(e);
+                }
+            },
+            _ = wait_for_shutdown(shutdown) => return Ok(()),
+        };
+        if resume_slot.is_some() {
+            state.resume.begin_replay();
+            if let Some(gap) = truncated_at.and_then(|first| state.resume.replay_truncated(first, get_timestamp_us())) {
+                sink.push(gap);
+            }
+        }
 
+        state.established = true;
+        self.notify(ConnectionEvent::Connected { endpoint: self.endpoint.clone() });
         self.print_mode_info();
 
         // 设置控制通道
@@ -155,11 +467,18 @@ impl YellowstoneGrpc {
         let batch_us = self.config.micro_batch_us;
         let check_interval = Duration::from_millis(timeout_ms / 2);
         let mut next_check = Instant::now() + check_interval;
+        // Spill 模式：消费者空闲时定期补发溢出事件
+        let mut pending_tick = tokio::time::interval(Duration::from_millis(1));
+        pending_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // 流空闲时发送 ping，防止负载均衡器断开空闲连接；ping 无响应视为连接已断
+        let mut keep_alive = KeepAlive::new(self.config.keep_alive_interval_ms, self.config.keep_alive_timeout_ms);
+        let mut keep_alive_tick = tokio::time::interval_at(Instant::now() + keep_alive.period(), keep_alive.period());
+        keep_alive_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
         loop {
             // Periodic timeout check for ordered modes and MicroBatch
             self.check_timeout(
-                order_mode, &mut slot_buffer, &mut micro_batch, queue,
+                order_mode, &mut slot_buffer, &mut micro_batch, sink,
                 timeout_ms, batch_us, &mut next_check, check_interval
             );
 
@@ -167,25 +486,74 @@ impl YellowstoneGrpc {
                 msg = stream.next() => {
                     match msg {
                         Some(Ok(update)) => {
+                            keep_alive.on_message();
+                            // 服务端心跳：回复 ping
+                            if matches!(update.update_oneof, Some(subscribe_update::UpdateOneof::Ping(_))) {
+                                if let Err(e) = subscribe_tx.lock().await.send(keep_alive.ping_request()).await {
+                                    self.flush_on_disconnect(order_mode, &mut slot_buffer, &mut micro_batch, sink);
+                                    return Err(GrpcError::StreamReset(e.to_string()));
+                                }
+                                continue;
+                            }
                             self.handle_update(
-                                update, order_mode, event_filter, queue,
+                                update, order_mode, event_filter, sink, state,
                                 &mut slot_buffer, &mut micro_batch, &mut last_slot, batch_us
                             );
+                            // 已超过 max_slot：输出缓冲事件后结束
+                            if state.is_complete() {
+                                self.flush_on_disconnect(order_mode, &mut slot_buffer, &mut micro_batch, sink);
+                                return Ok(());
+                            }
+                            // Block 模式：写不下时暂停读取，反压到服务端
+                            if sink.should_block() {
+                                let stopped = tokio::select! {
+                                    _ = sink.wait_pending() => false,
+                                    _ = wait_for_shutdown(shutdown) => true,
+                                };
+                                if stopped {
+                                    self.flush_on_disconnect(order_mode, &mut slot_buffer, &mut micro_batch, sink);
+                                    return Ok(());
+                                }
+                            }
                         }
                         Some(Err(e)) => {
-                            error!("Stream error: {:?}", e);
-                            self.flush_on_disconnect(order_mode, &mut slot_buffer, queue);
-                            return Err(e.to_string());
+                            if resume_slot.is_some() && is_replay_error(e.message()) {
+                                state.resume.replay_unavailable(e.message().to_string());
+                            }
+                            self.flush_on_disconnect(order_mode, &mut slot_buffer, &mut micro_batch, sink);
+                            return Err(GrpcError::from_status(&e));
                         }
                         None => {
-                            self.flush_on_disconnect(order_mode, &mut slot_buffer, queue);
+                            self.flush_on_disconnect(order_mode, &mut slot_buffer, &mut micro_batch, sink);
                             return Ok(());
                         }
                     }
                 }
+                _ = wait_for_shutdown(shutdown) => {
+                    self.flush_on_disconnect(order_mode, &mut slot_buffer, &mut micro_batch, sink);
+                    return Ok(());
+                }
                 Some(req) = control_rx.recv() => {
                     if let Err(e) = subscribe_tx.lock().await.send(req).await {
-                        return Err(e.to_string());
+                        return Err(GrpcError::StreamReset(e.to_string()));
+                    }
+                }
+                _ = pending_tick.tick(), if sink.has_pending() => {
+                    sink.drain_pending();
+                }
+                _ = keep_alive_tick.tick(), if keep_alive.enabled() => {
+                    let ping = match keep_alive.check() {
+                        Ok(ping) => ping,
+                        Err(e) => {
+                            self.flush_on_disconnect(order_mode, &mut slot_buffer, &mut micro_batch, sink);
+                            return Err(e);
+                        }
+                    };
+                    if ping {
+                        if let Err(e) = subscribe_tx.lock().await.send(keep_alive.ping_request()).await {
+                            self.flush_on_disconnect(order_mode, &mut slot_buffer, &mut micro_batch, sink);
+                            return Err(GrpcError::StreamReset(e.to_string()));
+                        }
                     }
                 }
             }
@@ -194,10 +562,10 @@ impl YellowstoneGrpc {
 
     fn print_mode_info(&self) {
         match self.config.order_mode {
-            OrderMode::Unordered => println!("✅ Unordered Mode (10-20μs)"),
-            OrderMode::Ordered => println!("✅ Ordered Mode (timeout={}ms)", self.config.order_timeout_ms),
-            OrderMode::StreamingOrdered => println!("✅ StreamingOrdered Mode (timeout={}ms)", self.config.order_timeout_ms),
-            OrderMode::MicroBatch => println!("✅ MicroBatch Mode (window={}μs)", self.config.micro_batch_us),
+            OrderMode::Unordered => debug!("Unordered Mode (10-20μs)"),
+            OrderMode::Ordered => debug!("Ordered Mode (timeout={}ms)", self.config.order_timeout_ms),
+            OrderMode::StreamingOrdered => debug!("StreamingOrdered Mode (timeout={}ms)", self.config.order_timeout_ms),
+            OrderMode::MicroBatch => debug!("MicroBatch Mode (window={}μs)", self.config.micro_batch_us),
         }
     }
 
@@ -207,7 +575,7 @@ impl YellowstoneGrpc {
         mode: OrderMode,
         slot_buf: &mut SlotBuffer,
         micro_buf: &mut MicroBatchBuffer,
-        queue: &Arc<ArrayQueue<DexEvent>>,
+        sink: &mut EventSink,
         timeout_ms: u64,
         batch_us: u64,
         next_check: &mut Instant,
@@ -221,42 +589,51 @@ impl YellowstoneGrpc {
         match mode {
             OrderMode::Ordered => {
                 if slot_buf.should_timeout(timeout_ms) {
-                    for e in slot_buf.flush_all() { let _ = queue.push(e); }
+                    for e in slot_buf.flush_all() { sink.push(e); }
                 }
             }
             OrderMode::StreamingOrdered => {
                 if slot_buf.should_timeout(timeout_ms) {
-                    for e in slot_buf.flush_streaming_timeout() { let _ = queue.push(e); }
+                    for e in slot_buf.flush_streaming_timeout() { sink.push(e); }
                 }
             }
             OrderMode::MicroBatch => {
                 // Periodic flush for MicroBatch mode
                 let now_us = get_timestamp_us();
                 if micro_buf.should_flush(now_us, batch_us) {
-                    for e in micro_buf.flush() { let _ = queue.push(e); }
+                    for e in micro_buf.flush() { sink.push(e); }
                 }
             }
             OrderMode::Unordered => {}
         }
     }
 
-    fn flush_on_disconnect(&self, mode: OrderMode, buffer: &mut SlotBuffer, queue: &Arc<ArrayQueue<DexEvent>>) {
-        if matches!(mode, OrderMode::Ordered | OrderMode::StreamingOrdered) {
-            let events = match mode {
-                OrderMode::StreamingOrdered => buffer.flush_streaming_timeout(),
-                _ => buffer.flush_all(),
-            };
-            for e in events { let _ = queue.push(e); }
-        }
+    /// 断线 / 停止时输出所有缓冲事件
+    fn flush_on_disconnect(
+        &self,
+        mode: OrderMode,
+        buffer: &mut SlotBuffer,
+        micro_buf: &mut MicroBatchBuffer,
+        sink: &mut EventSink,
+    ) {
+        let events = match mode {
+            OrderMode::Ordered => buffer.flush_all(),
+            OrderMode::StreamingOrdered => buffer.flush_streaming_timeout(),
+            OrderMode::MicroBatch => micro_buf.flush(),
+            OrderMode::Unordered => return,
+        };
+        for e in events { sink.push(e); }
     }
 
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     fn handle_update(
         &self,
         update_msg: SubscribeUpdate,
         mode: OrderMode,
         filter: &Option<EventTypeFilter>,
-        queue: &Arc<ArrayQueue<DexEvent>>,
+        sink: &mut EventSink,
+        state: &mut StreamState,
         slot_buf: &mut SlotBuffer,
         micro_buf: &mut MicroBatchBuffer,
         last_slot: &mut u64,
@@ -267,24 +644,98 @@ impl YellowstoneGrpc {
 
         let Some(update) = update_msg.update_oneof else { return };
 
+        // from_slot 被拒绝后的第一条更新：报告缺口
+        let update_slot = match update {
+            subscribe_update::UpdateOneof::Transaction(ref tx) => tx.slot,
+            subscribe_update::UpdateOneof::Account(ref acc) => acc.slot,
+            subscribe_update::UpdateOneof::Slot(ref slot) => slot.slot,
+            subscribe_update::UpdateOneof::Block(ref block) => block.slot,
+            subscribe_update::UpdateOneof::BlockMeta(ref meta) => meta.slot,
+            _ => 0,
+        };
+        if update_slot > 0 {
+            if let Some(gap) = state.resume.take_gap(update_slot, grpc_recv_us) {
+                sink.push(gap);
+            }
+        }
+
+        // slot 区间外的更新在解析前丢弃；下一个 slot 的状态更新可能早于当前 slot 的交易到达，不作为结束标志
+        if let Some(slots) = state.slot_filter {
+            if update_slot > 0 && !slots.contains(update_slot) {
+                if slots.is_past(update_slot) && !matches!(update, subscribe_update::UpdateOneof::Slot(_)) {
+                    state.past_max_slot = true;
+                }
+                return;
+            }
+        }
+
         match update {
             subscribe_update::UpdateOneof::Transaction(tx) => {
-                self.handle_transaction(tx, mode, filter, queue, slot_buf, micro_buf, last_slot, batch_us, grpc_recv_us, block_time_us);
+                self.handle_transaction(tx, mode, filter, sink, state, slot_buf, micro_buf, last_slot, batch_us, grpc_recv_us, block_time_us);
             }
             subscribe_update::UpdateOneof::Account(acc) => {
-                Self::handle_account(acc, filter, queue, grpc_recv_us, block_time_us);
+                self.handle_account(acc, filter, sink, grpc_recv_us, block_time_us);
+            }
+            subscribe_update::UpdateOneof::Slot(slot) => {
+                self.handle_slot(slot, sink, &mut state.fork, slot_buf, grpc_recv_us, block_time_us);
+            }
+            subscribe_update::UpdateOneof::Block(block) => {
+                self.metrics.record_process(MetricsEventType::BlockMeta);
+                let meta = block_meta_event(SubscribeUpdateBlockMeta {
+                    slot: block.slot,
+                    blockhash: block.blockhash,
+                    rewards: None,
+                    block_time: block.block_time,
+                    block_height: block.block_height,
+                    parent_slot: block.parent_slot,
+                    parent_blockhash: block.parent_blockhash,
+                    executed_transaction_count: block.executed_transaction_count,
+                    entries_count: block.entries_count,
+                }, block_time_us, grpc_recv_us);
+                // 区块内交易使用链上出块时间
+                let block_us = meta.metadata.block_time_us;
+                for info in block.transactions {
+                    // 区块过滤不支持 vote / failed / account_exclude / account_required，在本地过滤
+                    let failed = info.meta.as_ref().is_some_and(|m| m.err.is_some());
+                    if (info.is_vote && !state.block_include_vote)
+                        || (failed && !state.block_include_failed)
+                        || !BlockTxFilter::matches_any(&state.block_tx_filters, &info)
+                    {
+                        continue;
+                    }
+                    let tx = SubscribeUpdateTransaction { transaction: Some(info), slot: block.slot };
+                    self.handle_transaction(tx, mode, filter, sink, state, slot_buf, micro_buf, last_slot, batch_us, grpc_recv_us, block_us);
+                }
+                self.emit_block_meta(meta, filter, sink);
+            }
+            subscribe_update::UpdateOneof::BlockMeta(block) => {
+                self.metrics.record_process(MetricsEventType::BlockMeta);
+                self.emit_block_meta(block_meta_event(block, block_time_us, grpc_recv_us), filter, sink);
             }
             _ => {}
         }
     }
 
     #[inline]
+    fn emit_block_meta(&self, meta: BlockMetaEvent, filter: &Option<EventTypeFilter>, sink: &mut EventSink) {
+        if !self.config.block_meta {
+            return;
+        }
+        if filter.as_ref().is_some_and(|f| !f.should_include(EventType::BlockMeta)) {
+            return;
+        }
+        sink.push(DexEvent::BlockMeta(meta));
+    }
+
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
     fn handle_transaction(
         &self,
         tx: SubscribeUpdateTransaction,
         mode: OrderMode,
         filter: &Option<EventTypeFilter>,
-        queue: &Arc<ArrayQueue<DexEvent>>,
+        sink: &mut EventSink,
+        state: &mut StreamState,
         slot_buf: &mut SlotBuffer,
         micro_buf: &mut MicroBatchBuffer,
         last_slot: &mut u64,
@@ -293,33 +744,64 @@ impl YellowstoneGrpc {
         block_us: i64,
     ) {
         let slot = tx.slot;
-        
+        let Some(info) = tx.transaction.as_ref() else { return };
+        let idx = info.index;
+        self.metrics.record_process(MetricsEventType::Transaction);
+
+        // 续传回放中已输出过的交易
+        if let Ok(signature) = solana_sdk::signature::Signature::try_from(info.signature.as_slice()) {
+            if state.resume.is_duplicate(slot, &signature) {
+                return;
+            }
+        }
+
+        let parse_start = self.metrics.is_enabled().then(get_timestamp_us);
+        let stage_start = timing::now_us();
+        let mut events = parse_transaction_core(&tx, grpc_us, Some(block_us), filter.as_ref());
+        timing::mark_parsed(&mut events, stage_start);
+        if let Some(start) = parse_start {
+            let elapsed = (get_timestamp_us() - start) as f64;
+            self.metrics.update_metrics(MetricsEventType::Transaction, events.len() as u64, elapsed);
+        }
+        if let Some(signature) = events.first().and_then(|e| e.metadata()).map(|m| m.signature) {
+            state.resume.record(slot, signature);
+            match state.fork.policy() {
+                // 暂扣到 slot confirmed 后按 tx_index 顺序输出
+                ForkPolicy::Withhold => {
+                    for e in events { state.fork.withhold(slot, idx, e); }
+                    return;
+                }
+                ForkPolicy::Retract => state.fork.record(slot, signature),
+                ForkPolicy::Emit => {}
+            }
+        }
+
         match mode {
             OrderMode::Unordered => {
-                for e in parse_transaction_core(&tx, grpc_us, Some(block_us), filter.as_ref()) {
-                    let _ = queue.push(e);
+                for e in events {
+                    sink.push(e);
                 }
             }
             OrderMode::Ordered => {
                 if slot > *last_slot && *last_slot > 0 {
-                    for e in slot_buf.flush_before(slot) { let _ = queue.push(e); }
+                    for e in slot_buf.flush_before(slot) { sink.push(e); }
                 }
                 *last_slot = slot;
-                for (idx, e) in parse_transaction_to_vec(&tx, grpc_us, Some(block_us), filter.as_ref()) {
+                for e in events {
                     slot_buf.push(slot, idx, e);
                 }
             }
             OrderMode::StreamingOrdered => {
-                for (idx, e) in parse_transaction_to_vec(&tx, grpc_us, Some(block_us), filter.as_ref()) {
+                for e in events {
                     for evt in slot_buf.push_streaming(slot, idx, e) {
-                        let _ = queue.push(evt);
+                        sink.push(evt);
                     }
                 }
             }
             OrderMode::MicroBatch => {
-                for (idx, e) in parse_transaction_to_vec(&tx, grpc_us, Some(block_us), filter.as_ref()) {
+                for e in events {
                     if micro_buf.push(slot, idx, e, grpc_us, batch_us) {
-                        for evt in micro_buf.flush() { let _ = queue.push(evt); }
+                        for evt in micro_buf.flush() { sink.push(evt); }
                     }
                 }
             }
@@ -328,9 +810,10 @@ impl YellowstoneGrpc {
 
     #[inline]
     fn handle_account(
+        &self,
         acc: SubscribeUpdateAccount,
         filter: &Option<EventTypeFilter>,
-        queue: &Arc<ArrayQueue<DexEvent>>,
+        sink: &mut EventSink,
         grpc_us: i64,
         block_us: i64,
     ) {
@@ -349,17 +832,249 @@ impl YellowstoneGrpc {
             tx_index: 0,
             block_time_us: block_us,
             grpc_recv_us: grpc_us,
+            is_failed: false,
+            timing: Default::default(),
         };
-        if let Some(e) = crate::accounts::parse_account_unified(&data, meta, filter.as_ref()) {
-            let _ = queue.push(e);
+        self.metrics.record_process(MetricsEventType::Account);
+        let parse_start = self.metrics.is_enabled().then(get_timestamp_us);
+        let stage_start = timing::now_us();
+        let mut event = crate::accounts::parse_account_unified(&data, meta, filter.as_ref());
+        timing::mark_parsed(event.as_mut_slice(), stage_start);
+        if let Some(start) = parse_start {
+            let elapsed = (get_timestamp_us() - start) as f64;
+            self.metrics.update_metrics(MetricsEventType::Account, event.is_some() as u64, elapsed);
+        }
+        if let Some(e) = event {
+            sink.push(e);
         }
     }
+
+    /// slot 状态：释放 / 丢弃分叉事件，并按配置输出 Slot* 事件
+    fn handle_slot(
+        &self,
+        update: SubscribeUpdateSlot,
+        sink: &mut EventSink,
+        fork: &mut ForkGuard,
+        slot_buf: &mut SlotBuffer,
+        grpc_us: i64,
+        block_us: i64,
+    ) {
+        let slot = update.slot;
+        let mut retracted_signatures = Vec::new();
+        let status = SlotStatus::try_from(update.status);
+        let metadata = EventMetadata { slot, block_time_us: block_us, grpc_recv_us: grpc_us, ..Default::default() };
+        match status {
+            Ok(SlotStatus::SlotConfirmed) => {
+                for e in fork.on_confirmed(slot) { sink.push(e); }
+            }
+            Ok(SlotStatus::SlotFinalized) => {
+                let finalized = fork.on_finalized(slot);
+                self.metrics.record_fork_discarded(finalized.discarded as u64);
+                // 更早仍未确认的 slot 已被放弃：与 dead slot 一样撤销其已输出事件
+                for (orphan, signatures) in finalized.orphaned {
+                    sink.push(DexEvent::SlotDead(SlotStatusEvent {
+                        metadata: EventMetadata { slot: orphan, ..metadata.clone() },
+                        parent: None,
+                        dead_error: None,
+                        retracted_signatures: signatures,
+                    }));
+                }
+                for e in finalized.released { sink.push(e); }
+            }
+            Ok(SlotStatus::SlotDead) => {
+                let mut discarded = 0;
+                if fork.policy() != ForkPolicy::Emit {
+                    // 有序缓冲中尚未输出的事件直接丢弃
+                    discarded += slot_buf.discard_slot(slot);
+                }
+                let (signatures, withheld) = fork.on_dead(slot);
+                self.metrics.record_fork_discarded((discarded + withheld) as u64);
+                retracted_signatures = signatures;
+            }
+            _ => return,
+        }
+
+        let retract = status == Ok(SlotStatus::SlotDead) && fork.policy() == ForkPolicy::Retract;
+        if !self.config.slot_status && !retract {
+            return;
+        }
+        let event = SlotStatusEvent {
+            metadata,
+            parent: update.parent,
+            dead_error: update.dead_error,
+            retracted_signatures,
+        };
+        sink.push(match status {
+            Ok(SlotStatus::SlotConfirmed) => DexEvent::SlotConfirmed(event),
+            Ok(SlotStatus::SlotFinalized) => DexEvent::SlotFinalized(event),
+            _ => DexEvent::SlotDead(event),
+        });
+    }
 }
 
 // ==================== 辅助函数 ====================
 
+/// 跨重连保留的订阅状态
+struct StreamState {
+    fork: ForkGuard,
+    resume: ResumeTracker,
+    /// 区块订阅下是否保留失败交易
+    block_include_failed: bool,
+    /// 区块订阅下是否保留投票交易
+    block_include_vote: bool,
+    /// 区块订阅下在本地应用的 account_exclude / account_required，为空时不过滤
+    block_tx_filters: Vec<BlockTxFilter>,
+    /// 本次连接是否已建立订阅（用于重置重试退避）
+    established: bool,
+    slot_filter: Option<SlotFilter>,
+    /// 已收到 max_slot 之后的更新
+    past_max_slot: bool,
+}
+
+impl StreamState {
+    /// 有界订阅已完成：超过 max_slot，且 Withhold 下区间内的事件均已确认或丢弃
+    #[inline]
+    fn is_complete(&self) -> bool {
+        let Some(max_slot) = self.slot_filter.and_then(|f| f.max_slot) else { return false };
+        self.past_max_slot && !self.fork.is_withholding_through(max_slot)
+    }
+}
+
+/// 区块过滤只支持 account_include，exclude / required 在本地按过滤器逐个匹配
+struct BlockTxFilter {
+    include: Vec<Pubkey>,
+    exclude: Vec<Pubkey>,
+    required: Vec<Pubkey>,
+}
+
+impl BlockTxFilter {
+    /// 所有过滤器都只有 account_include 时服务端已完成过滤，返回空列表
+    fn from_filters(filters: &[TransactionFilter]) -> Vec<Self> {
+        if filters.iter().all(|f| f.account_exclude.is_empty() && f.account_required.is_empty()) {
+            return Vec::new();
+        }
+        let parse = |v: &[String]| v.iter().filter_map(|s| s.parse::<Pubkey>().ok()).collect();
+        filters
+            .iter()
+            .map(|f| Self {
+                include: parse(&f.account_include),
+                exclude: parse(&f.account_exclude),
+                required: parse(&f.account_required),
+            })
+            .collect()
+    }
+
+    /// 任一过滤器匹配交易的全部账户（含 ALT 加载的账户）即保留
+    fn matches_any(filters: &[Self], info: &SubscribeUpdateTransactionInfo) -> bool {
+        if filters.is_empty() {
+            return true;
+        }
+        let message_keys = info.transaction.as_ref().and_then(|t| t.message.as_ref()).map(|m| &m.account_keys[..]);
+        let keys: Vec<Pubkey> = message_keys
+            .unwrap_or_default()
+            .iter()
+            .chain(info.meta.iter().flat_map(|m| m.loaded_writable_addresses.iter().chain(&m.loaded_readonly_addresses)))
+            .map(|k| read_pubkey_fast(k))
+            .collect();
+        filters.iter().any(|f| {
+            (f.include.is_empty() || f.include.iter().any(|k| keys.contains(k)))
+                && !f.exclude.iter().any(|k| keys.contains(k))
+                && f.required.iter().all(|k| keys.contains(k))
+        })
+    }
+}
+
+/// 应用层心跳：流空闲超过 interval 时发送 Yellowstone ping，timeout 内无任何消息则断开
+struct KeepAlive {
+    interval: Duration,
+    timeout: Duration,
+    last_message: Instant,
+    ping_sent: Option<Instant>,
+    next_id: i32,
+}
+
+impl KeepAlive {
+    fn new(interval_ms: u64, timeout_ms: u64) -> Self {
+        Self {
+            interval: Duration::from_millis(interval_ms),
+            timeout: Duration::from_millis(timeout_ms),
+            last_message: Instant::now(),
+            ping_sent: None,
+            next_id: 1,
+        }
+    }
+
+    #[inline]
+    fn enabled(&self) -> bool {
+        !self.interval.is_zero()
+    }
+
+    /// 检查周期（关闭时给一个不会触发的值）
+    fn period(&self) -> Duration {
+        if self.enabled() {
+            self.interval.min(self.timeout).max(Duration::from_millis(1))
+        } else {
+            Duration::from_secs(3600)
+        }
+    }
+
+    #[inline]
+    fn on_message(&mut self) {
+        self.last_message = Instant::now();
+        self.ping_sent = None;
+    }
+
+    /// Ok(true) 表示需要发送 ping；ping 超时返回 StreamReset
+    fn check(&mut self) -> Result<bool, GrpcError> {
+        let now = Instant::now();
+        if let Some(sent) = self.ping_sent {
+            if now.duration_since(sent) >= self.timeout {
+                return Err(GrpcError::StreamReset(format!("no response to ping within {:?}", self.timeout)));
+            }
+            return Ok(false);
+        }
+        if now.duration_since(self.last_message) >= self.interval {
+            self.ping_sent = Some(now);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn ping_request(&mut self) -> SubscribeRequest {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        SubscribeRequest { ping: Some(SubscribeRequestPing { id }), ..Default::default() }
+    }
+}
+
+/// 区块元数据事件；没有链上出块时间时使用 created_at
+fn block_meta_event(block: SubscribeUpdateBlockMeta, created_us: i64, grpc_us: i64) -> BlockMetaEvent {
+    let block_time = block.block_time.map(|t| t.timestamp);
+    BlockMetaEvent {
+        metadata: EventMetadata {
+            slot: block.slot,
+            block_time_us: block_time.map_or(created_us, |t| t * 1_000_000),
+            grpc_recv_us: grpc_us,
+            ..Default::default()
+        },
+        blockhash: block.blockhash,
+        parent_slot: block.parent_slot,
+        parent_blockhash: block.parent_blockhash,
+        block_height: block.block_height.map(|h| h.block_height),
+        block_time,
+        executed_transaction_count: block.executed_transaction_count,
+    }
+}
+
+/// 等待停止信号；发送端全部释放时视为永不停止
+pub(crate) async fn wait_for_shutdown(rx: &mut watch::Receiver<bool>) {
+    if rx.wait_for(|stop| *stop).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
 #[inline(always)]
-fn get_timestamp_us() -> i64 {
+pub(crate) fn get_timestamp_us() -> i64 {
     unsafe {
         let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
         libc::clock_gettime(libc::CLOCK_REALTIME, &mut ts);
@@ -367,11 +1082,16 @@ fn get_timestamp_us() -> i64 {
     }
 }
 
-fn build_subscribe_request(tx_filters: &[TransactionFilter], acc_filters: &[AccountFilter]) -> SubscribeRequest {
-    let transactions = tx_filters.iter().enumerate().map(|(i, f)| {
+fn build_subscribe_request(
+    tx_filters: &[TransactionFilter],
+    acc_filters: &[AccountFilter],
+    config: &ClientConfig,
+) -> SubscribeRequest {
+    // 区块订阅替代交易订阅，避免同一交易输出两次
+    let transactions = tx_filters.iter().filter(|_| !config.blocks).enumerate().map(|(i, f)| {
         (format!("tx_{}", i), SubscribeRequestFilterTransactions {
-            vote: Some(false),
-            failed: Some(false),
+            vote: f.vote,
+            failed: f.failed,
             signature: None,
             account_include: f.account_include.clone(),
             account_exclude: f.account_exclude.clone(),
@@ -388,15 +1108,45 @@ fn build_subscribe_request(tx_filters: &[TransactionFilter], acc_filters: &[Acco
         })
     }).collect();
 
+    // dead 属于 interslot 状态，需要 interslot_updates 才会推送
+    let mut slots = HashMap::new();
+    if config.subscribes_slots() {
+        slots.insert("slots".to_string(), SubscribeRequestFilterSlots {
+            filter_by_commitment: Some(false),
+            interslot_updates: Some(true),
+        });
+    }
+
+    let mut blocks = HashMap::new();
+    let mut blocks_meta = HashMap::new();
+    if config.blocks {
+        // 任一过滤器不限 account_include 时服务端不能按账户收窄
+        let mut account_include: Vec<String> = if tx_filters.iter().any(|f| f.account_include.is_empty()) {
+            Vec::new()
+        } else {
+            tx_filters.iter().flat_map(|f| f.account_include.clone()).collect()
+        };
+        account_include.sort_unstable();
+        account_include.dedup();
+        blocks.insert("blocks".to_string(), SubscribeRequestFilterBlocks {
+            account_include,
+            include_transactions: Some(true),
+            include_accounts: Some(false),
+            include_entries: Some(false),
+        });
+    } else if config.block_meta {
+        blocks_meta.insert("blocks_meta".to_string(), SubscribeRequestFilterBlocksMeta {});
+    }
+
     SubscribeRequest {
-        slots: HashMap::new(),
+        slots,
         accounts,
         transactions,
         transactions_status: HashMap::new(),
-        blocks: HashMap::new(),
-        blocks_meta: HashMap::new(),
+        blocks,
+        blocks_meta,
         entry: HashMap::new(),
-        commitment: Some(CommitmentLevel::Processed as i32),
+        commitment: Some(CommitmentLevel::from(config.commitment) as i32),
         accounts_data_slice: Vec::new(),
         ping: None,
         from_slot: None,
@@ -406,21 +1156,7 @@ fn build_subscribe_request(tx_filters: &[TransactionFilter], acc_filters: &[Acco
 // ==================== 交易解析 ====================
 
 #[inline]
-fn parse_transaction_to_vec(
-    tx: &SubscribeUpdateTransaction,
-    grpc_us: i64,
-    block_us: Option<i64>,
-    filter: Option<&EventTypeFilter>,
-) -> Vec<(u64, DexEvent)> {
-    let idx = tx.transaction.as_ref().map(|t| t.index).unwrap_or(0);
-    parse_transaction_core(tx, grpc_us, block_us, filter)
-        .into_iter()
-        .map(|e| (idx, e))
-        .collect()
-}
-
-#[inline]
-fn parse_transaction_core(
+pub(crate) fn parse_transaction_core(
     tx: &SubscribeUpdateTransaction,
     grpc_us: i64,
     block_us: Option<i64>,
@@ -439,9 +1175,22 @@ fn parse_transaction_core(
         || parse_instructions(meta, &info.transaction, sig, slot, idx, block_us, grpc_us, filter),
     );
 
-    let mut result = Vec::with_capacity(log_events.len() + instr_events.len());
-    result.extend(log_events);
-    result.extend(instr_events);
+    // 指令字段补进对应日志事件；其余指令事件只保留日志中没有的种类（如 migrate CPI）
+    let mut merger = crate::core::unified_parser::InstructionMerger::new(instr_events);
+    let mut result = Vec::with_capacity(log_events.len() + 2);
+    for mut e in log_events {
+        merger.merge_into(&mut e);
+        result.push(e);
+    }
+    result.extend(merger.into_remaining());
+    // 订阅包含失败交易时标记执行失败的事件
+    if meta.err.is_some() {
+        for e in result.iter_mut() {
+            if let Some(m) = e.metadata_mut() {
+                m.is_failed = true;
+            }
+        }
+    }
     result
 }
 
@@ -511,7 +1260,6 @@ fn parse_instructions(
         else { meta.loaded_readonly_addresses.get(i - keys_len - writable_len) }
     };
 
-    static EMPTY: &[Pubkey] = &[];
     let mut invokes: HashMap<Pubkey, Vec<(i32, i32)>> = HashMap::with_capacity(8);
     let mut result = Vec::with_capacity(4);
 
@@ -520,16 +1268,808 @@ fn parse_instructions(
         invokes.entry(pid).or_default().push((i as i32, -1));
     }
 
-    for inner in &meta.inner_instructions {
-        for (j, ix) in inner.instructions.iter().enumerate() {
-            let pid = get_key(ix.program_id_index as usize).map_or(Pubkey::default(), |k| read_pubkey_fast(k));
-            if let Some(mut e) = crate::instr::parse_instruction_unified(&ix.data, EMPTY, sig, slot, tx_idx, block_us, grpc_us, filter, &pid) {
+    // 只为已注册协议解析指令账户，其余程序不解析
+    let resolve = |pid: &Pubkey, indexes: &[u8]| -> Vec<Pubkey> {
+        if super::program_ids::protocol_of(pid).is_none() {
+            return Vec::new();
+        }
+        indexes.iter().map(|&a| get_key(a as usize).map_or(Pubkey::default(), |k| read_pubkey_fast(k))).collect()
+    };
+
+    // 按执行顺序：外层指令 i，随后是它的内层指令
+    for (i, ix) in msg.instructions.iter().enumerate() {
+        let pid = get_key(ix.program_id_index as usize).map_or(Pubkey::default(), |k| read_pubkey_fast(k));
+        if decodes_outer_instruction(&pid, &ix.data) {
+            let accounts = resolve(&pid, &ix.accounts);
+            crate::instr::parse_instruction_events(&ix.data, &accounts, sig, slot, tx_idx, block_us, grpc_us, filter, &pid, |mut e| {
                 crate::core::account_filler::fill_accounts_with_owned_keys(&mut e, meta, transaction, &invokes);
                 result.push(e);
-            } else {
-                invokes.entry(pid).or_default().push((inner.index as i32, j as i32));
+            });
+        }
+
+        for inner in meta.inner_instructions.iter().filter(|inner| inner.index as usize == i) {
+            for (j, ix) in inner.instructions.iter().enumerate() {
+                let pid = get_key(ix.program_id_index as usize).map_or(Pubkey::default(), |k| read_pubkey_fast(k));
+                let accounts = resolve(&pid, &ix.accounts);
+                let before = result.len();
+                crate::instr::parse_instruction_events(&ix.data, &accounts, sig, slot, tx_idx, block_us, grpc_us, filter, &pid, |mut e| {
+                    crate::core::account_filler::fill_accounts_with_owned_keys(&mut e, meta, transaction, &invokes);
+                    result.push(e);
+                });
+                if result.len() == before {
+                    invokes.entry(pid).or_default().push((inner.index as i32, j as i32));
+                }
             }
         }
     }
     result
 }
+
+/// 外层指令的事件大多已由日志或内层 CPI 事件给出，只解码补充信息的指令：
+/// Orca swap 的路由与 Token-2022 字段只在指令中；LaunchLab 迁移没有事件
+#[inline]
+fn decodes_outer_instruction(program_id: &Pubkey, data: &[u8]) -> bool {
+    match super::program_ids::protocol_of(program_id) {
+        Some(Protocol::OrcaWhirlpool) => crate::instr::orca_whirlpool::is_swap(data),
+        Some(Protocol::Bonk) => crate::instr::raydium_launchpad::is_migrate(data),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::mock_server::{block_update, pumpfun_trade_update, slot_update, MockGeyser};
+
+    fn test_client(endpoint: String, config: ClientConfig) -> YellowstoneGrpc {
+        YellowstoneGrpc::new_unwarmed(endpoint, config)
+    }
+
+    fn ordered_config() -> ClientConfig {
+        ClientConfig {
+            enable_tls: false,
+            order_mode: OrderMode::Ordered,
+            order_timeout_ms: 60_000,
+            ..ClientConfig::default()
+        }
+    }
+
+    async fn subscribe(grpc: &YellowstoneGrpc) -> (Arc<ArrayQueue<DexEvent>>, SubscriptionHandle) {
+        grpc.subscribe_dex_events(vec![TransactionFilter::default()], vec![], None).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_stop_drains_ordered_buffer_and_joins() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, ordered_config());
+        let (queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+
+        // 同一 slot 的事件在 Ordered 模式下会一直缓冲，直到下一个 slot 或超时
+        mock.send(pumpfun_trade_update(10, 3, 1));
+        mock.send(pumpfun_trade_update(10, 1, 2));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(queue.is_empty());
+
+        tokio::time::timeout(Duration::from_secs(5), handle.stop_and_join()).await.unwrap().unwrap();
+
+        let indices: Vec<u64> = std::iter::from_fn(|| queue.pop())
+            .map(|e| match e {
+                DexEvent::PumpFunTrade(t) => t.metadata.tx_index,
+                other => panic!("unexpected event: {:?}", other),
+            })
+            .collect();
+        assert_eq!(indices, vec![1, 3]);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while mock.subscriber_count() > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    fn unordered_config() -> ClientConfig {
+        ClientConfig { enable_tls: false, order_mode: OrderMode::Unordered, ..ClientConfig::default() }
+    }
+
+    #[tokio::test]
+    async fn test_stream_output() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, unordered_config());
+        let (mut stream, handle) = grpc
+            .subscribe_dex_events_stream(vec![TransactionFilter::default()], vec![], None)
+            .await
+            .unwrap();
+        mock.wait_subscribers(1).await;
+
+        mock.send(pumpfun_trade_update(5, 2, 1));
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next()).await.unwrap();
+        assert!(matches!(event, Some(DexEvent::PumpFunTrade(ref t)) if t.metadata.slot == 5));
+
+        handle.stop_and_join().await.unwrap();
+        // 任务结束后发送端释放，流随之结束
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_fan_out() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, unordered_config());
+        let (mut rx1, handle) = grpc
+            .subscribe_dex_events_broadcast(vec![TransactionFilter::default()], vec![], None)
+            .await
+            .unwrap();
+        let mut rx2 = rx1.resubscribe();
+        mock.wait_subscribers(1).await;
+
+        mock.send(pumpfun_trade_update(6, 0, 1));
+        for rx in [&mut rx1, &mut rx2] {
+            let event = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+            assert!(matches!(event, DexEvent::PumpFunTrade(ref t) if t.metadata.slot == 6));
+        }
+        handle.stop_and_join().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_slot_filter_bounds_broadcast() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, unordered_config());
+        let (mut rx, handle) = grpc
+            .subscribe_dex_events_broadcast_with_slot_filter(
+                vec![TransactionFilter::default()], vec![], None, Some(SlotFilter::range(40, 41)),
+            )
+            .await
+            .unwrap();
+        mock.wait_subscribers(1).await;
+
+        mock.send(pumpfun_trade_update(39, 0, 1));
+        mock.send(pumpfun_trade_update(40, 0, 2));
+        mock.send(pumpfun_trade_update(42, 0, 3));
+
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(slot_and_index(&event), (40, 0));
+        let closed = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap();
+        assert!(matches!(closed, Err(broadcast::error::RecvError::Closed)));
+        tokio::time::timeout(Duration::from_secs(5), handle.join()).await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_client_stop_ends_all_subscriptions() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, unordered_config());
+        let (_q1, h1) = subscribe(&grpc).await;
+        let (_q2, h2) = subscribe(&grpc).await;
+        mock.wait_subscribers(2).await;
+
+        grpc.stop().await;
+        tokio::time::timeout(Duration::from_secs(5), async {
+            h1.join().await.unwrap();
+            h2.join().await.unwrap();
+        })
+        .await
+        .unwrap();
+        assert!(grpc.subscriptions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_commitment_and_failed_transactions() {
+        let mock = MockGeyser::new();
+        let config = ClientConfig { commitment: Commitment::Confirmed, ..unordered_config() };
+        let grpc = test_client(mock.serve().await, config);
+        let filter = TransactionFilter::default().include_failed(true);
+        let (queue, handle) = grpc.subscribe_dex_events(vec![filter], vec![], None).await.unwrap();
+        mock.wait_subscribers(1).await;
+
+        let request = mock.requests().into_iter().next().unwrap();
+        assert_eq!(request.commitment, Some(CommitmentLevel::Confirmed as i32));
+        let tx_filter = &request.transactions["tx_0"];
+        assert_eq!((tx_filter.vote, tx_filter.failed), (Some(false), None));
+
+        let mut update = pumpfun_trade_update(7, 0, 1);
+        if let Some(subscribe_update::UpdateOneof::Transaction(ref mut tx)) = update.update_oneof {
+            tx.transaction.as_mut().unwrap().meta.as_mut().unwrap().err =
+                Some(yellowstone_grpc_proto::prelude::TransactionError { err: vec![1] });
+        }
+        mock.send(update);
+        mock.send(pumpfun_trade_update(7, 1, 2));
+
+        let mut failed = Vec::new();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while failed.len() < 2 {
+                match queue.pop() {
+                    Some(e) => failed.push(e.metadata().unwrap().is_failed),
+                    None => tokio::time::sleep(Duration::from_millis(5)).await,
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(failed, vec![true, false]);
+        handle.stop_and_join().await.unwrap();
+    }
+
+    async fn next_event(queue: &ArrayQueue<DexEvent>) -> DexEvent {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match queue.pop() {
+                    Some(e) => return e,
+                    None => tokio::time::sleep(Duration::from_millis(5)).await,
+                }
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_withhold_until_confirmed() {
+        let mock = MockGeyser::new();
+        let config = ClientConfig { fork_policy: ForkPolicy::Withhold, slot_status: true, ..unordered_config() };
+        let grpc = test_client(mock.serve().await, config);
+        let (queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+        assert!(mock.requests()[0].slots["slots"].interslot_updates.unwrap());
+
+        mock.send(pumpfun_trade_update(20, 2, 1));
+        mock.send(pumpfun_trade_update(20, 1, 2));
+        mock.send(pumpfun_trade_update(21, 0, 3));
+        mock.send(slot_update(21, SlotStatus::SlotDead));
+        mock.send(slot_update(20, SlotStatus::SlotConfirmed));
+
+        let dead = next_event(&queue).await;
+        assert!(matches!(dead, DexEvent::SlotDead(ref s) if s.metadata.slot == 21 && s.retracted_signatures.is_empty()));
+        let indexes: Vec<_> = [next_event(&queue).await, next_event(&queue).await]
+            .iter()
+            .map(|e| (e.metadata().unwrap().slot, e.metadata().unwrap().tx_index))
+            .collect();
+        assert_eq!(indexes, vec![(20, 1), (20, 2)]);
+        assert!(matches!(next_event(&queue).await, DexEvent::SlotConfirmed(ref s) if s.metadata.slot == 20));
+        handle.stop_and_join().await.unwrap();
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retract_dead_slot() {
+        let mock = MockGeyser::new();
+        let config = ClientConfig { fork_policy: ForkPolicy::Retract, ..unordered_config() };
+        let grpc = test_client(mock.serve().await, config);
+        let (queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+
+        mock.send(pumpfun_trade_update(30, 0, 1));
+        let emitted = next_event(&queue).await.metadata().unwrap().signature;
+        // slot_status 未开启：confirmed 不输出，dead 仍输出撤销通知
+        mock.send(slot_update(29, SlotStatus::SlotConfirmed));
+        mock.send(slot_update(30, SlotStatus::SlotDead));
+        match next_event(&queue).await {
+            DexEvent::SlotDead(s) => {
+                assert_eq!(s.metadata.slot, 30);
+                assert_eq!(s.retracted_signatures, vec![emitted]);
+                assert_eq!(s.dead_error.as_deref(), Some("mock dead"));
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        handle.stop_and_join().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_finalized_slot_retracts_skipped_slot() {
+        let mock = MockGeyser::new();
+        let config = ClientConfig { fork_policy: ForkPolicy::Retract, ..unordered_config() };
+        let grpc = test_client(mock.serve().await, config);
+        let (queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+
+        // slot 32 被跳过：从未 confirmed，也没有 dead 通知
+        mock.send(pumpfun_trade_update(32, 0, 1));
+        let skipped = next_event(&queue).await.metadata().unwrap().signature;
+        mock.send(pumpfun_trade_update(33, 0, 2));
+        next_event(&queue).await;
+        mock.send(slot_update(33, SlotStatus::SlotConfirmed));
+        mock.send(slot_update(33, SlotStatus::SlotFinalized));
+        match next_event(&queue).await {
+            DexEvent::SlotDead(s) => {
+                assert_eq!(s.metadata.slot, 32);
+                assert_eq!(s.retracted_signatures, vec![skipped]);
+                assert!(s.dead_error.is_none());
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        handle.stop_and_join().await.unwrap();
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_finalized_slot_discards_withheld_skipped_slot() {
+        let mock = MockGeyser::new();
+        let config = ClientConfig { fork_policy: ForkPolicy::Withhold, ..unordered_config() };
+        let grpc = test_client(mock.serve().await, config);
+        let (queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+
+        mock.send(pumpfun_trade_update(34, 0, 1));
+        mock.send(pumpfun_trade_update(34, 1, 2));
+        mock.send(pumpfun_trade_update(35, 0, 3));
+        mock.send(slot_update(35, SlotStatus::SlotFinalized));
+        assert_eq!(next_event(&queue).await.metadata().unwrap().slot, 35);
+        assert_eq!(grpc.metrics().fork_discarded_events, 2);
+        handle.stop_and_join().await.unwrap();
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_block_subscription_uses_block_time() {
+        let mock = MockGeyser::new();
+        let config = ClientConfig { blocks: true, block_meta: true, ..unordered_config() };
+        let grpc = test_client(mock.serve().await, config);
+        let pumpfun = crate::instr::program_ids::PUMPFUN_PROGRAM_ID.to_string();
+        let filter = TransactionFilter::from_program_ids(vec![pumpfun.clone()]);
+        let (queue, handle) = grpc.subscribe_dex_events(vec![filter], vec![], None).await.unwrap();
+        mock.wait_subscribers(1).await;
+
+        let request = mock.requests().into_iter().next().unwrap();
+        assert!(request.transactions.is_empty() && request.blocks_meta.is_empty());
+        assert_eq!(request.blocks["blocks"].account_include, vec![pumpfun]);
+
+        let mut failed = pumpfun_trade_update(60, 1, 2);
+        if let Some(subscribe_update::UpdateOneof::Transaction(ref mut tx)) = failed.update_oneof {
+            tx.transaction.as_mut().unwrap().meta.as_mut().unwrap().err =
+                Some(yellowstone_grpc_proto::prelude::TransactionError { err: vec![1] });
+        }
+        mock.send(block_update(60, 1_700_000_000, vec![pumpfun_trade_update(60, 0, 1), failed]));
+
+        let trade = next_event(&queue).await;
+        let m = trade.metadata().unwrap();
+        assert_eq!((m.slot, m.tx_index, m.block_time_us), (60, 0, 1_700_000_000_000_000));
+        match next_event(&queue).await {
+            DexEvent::BlockMeta(b) => {
+                assert_eq!(b.metadata.block_time_us, 1_700_000_000_000_000);
+                assert_eq!((b.parent_slot, b.block_height, b.block_time), (59, Some(50), Some(1_700_000_000)));
+                assert_eq!((b.blockhash.as_str(), b.executed_transaction_count), ("mock-blockhash", 2));
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        handle.stop_and_join().await.unwrap();
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_block_subscription_applies_exclude_and_required() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, ClientConfig { blocks: true, ..unordered_config() });
+        let pumpfun = crate::instr::program_ids::PUMPFUN_PROGRAM_ID;
+        let (excluded, required) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let filter = TransactionFilter::from_program_ids(vec![pumpfun.to_string()])
+            .exclude_account(excluded.to_string())
+            .require_account(required.to_string());
+        let (queue, handle) = grpc.subscribe_dex_events(vec![filter], vec![], None).await.unwrap();
+        mock.wait_subscribers(1).await;
+
+        let with_keys = |index: u64, keys: &[Pubkey]| {
+            let mut update = pumpfun_trade_update(60, index, index as u8 + 1);
+            if let Some(subscribe_update::UpdateOneof::Transaction(ref mut tx)) = update.update_oneof {
+                let message = Message { account_keys: keys.iter().map(|k| k.to_bytes().to_vec()).collect(), ..Default::default() };
+                tx.transaction.as_mut().unwrap().transaction = Some(Transaction { message: Some(message), ..Default::default() });
+            }
+            update
+        };
+        mock.send(block_update(60, 1_700_000_000, vec![
+            with_keys(0, &[pumpfun, excluded, required]),
+            with_keys(1, &[pumpfun]),
+            with_keys(2, &[pumpfun, required]),
+        ]));
+
+        assert_eq!(slot_and_index(&next_event(&queue).await), (60, 2));
+        handle.stop_and_join().await.unwrap();
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_block_subscription_vote_filter() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, ClientConfig { blocks: true, ..unordered_config() });
+        let pumpfun = crate::instr::program_ids::PUMPFUN_PROGRAM_ID.to_string();
+        let default_filter = TransactionFilter::from_program_ids(vec![pumpfun.clone()]);
+        let (skip_votes, h1) = grpc.subscribe_dex_events(vec![default_filter.clone()], vec![], None).await.unwrap();
+        let (with_votes, h2) =
+            grpc.subscribe_dex_events(vec![default_filter.include_votes(true)], vec![], None).await.unwrap();
+        mock.wait_subscribers(2).await;
+
+        let mut vote = pumpfun_trade_update(61, 0, 1);
+        if let Some(subscribe_update::UpdateOneof::Transaction(ref mut tx)) = vote.update_oneof {
+            tx.transaction.as_mut().unwrap().is_vote = true;
+        }
+        mock.send(block_update(61, 1_700_000_000, vec![vote, pumpfun_trade_update(61, 1, 2)]));
+
+        assert_eq!(slot_and_index(&next_event(&skip_votes).await), (61, 1));
+        assert_eq!(slot_and_index(&next_event(&with_votes).await), (61, 0));
+        assert_eq!(slot_and_index(&next_event(&with_votes).await), (61, 1));
+        h1.stop_and_join().await.unwrap();
+        h2.stop_and_join().await.unwrap();
+        assert!(skip_votes.is_empty() && with_votes.is_empty());
+    }
+
+    /// 断开并等待客户端重新订阅成功，返回至此收到的全部请求（共 n 个）
+    async fn reconnect(mock: &MockGeyser, n: usize) -> Vec<SubscribeRequest> {
+        mock.disconnect_all();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while mock.requests().len() < n || mock.subscriber_count() == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        mock.requests()
+    }
+
+    fn slot_and_index(e: &DexEvent) -> (u64, u64) {
+        let m = e.metadata().unwrap();
+        (m.slot, m.tx_index)
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_slot_dedupes_replay() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, unordered_config());
+        let (queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+        assert_eq!(mock.requests()[0].from_slot, None);
+
+        mock.send(pumpfun_trade_update(1000, 0, 1));
+        assert_eq!(slot_and_index(&next_event(&queue).await), (1000, 0));
+
+        // 从最大 slot 往前 64 个 slot 回放
+        assert_eq!(reconnect(&mock, 2).await[1].from_slot, Some(936));
+        // 断线时尚未到达的较早 slot 交易被补回，已输出的交易被去重
+        mock.send(pumpfun_trade_update(999, 0, 4));
+        mock.send(pumpfun_trade_update(1000, 0, 1));
+        mock.send(pumpfun_trade_update(1000, 1, 2));
+        mock.send(pumpfun_trade_update(1001, 0, 3));
+        assert_eq!(slot_and_index(&next_event(&queue).await), (999, 0));
+        assert_eq!(slot_and_index(&next_event(&queue).await), (1000, 1));
+        assert_eq!(slot_and_index(&next_event(&queue).await), (1001, 0));
+        handle.stop_and_join().await.unwrap();
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resume_gap_reported() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, unordered_config());
+        let (queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+        mock.send(pumpfun_trade_update(1000, 0, 1));
+        next_event(&queue).await;
+
+        // 回放窗口从 1005 开始：立即报告 [936, 1005)
+        mock.set_first_available(Some(1005));
+        assert_eq!(reconnect(&mock, 2).await[1].from_slot, Some(1005));
+        match next_event(&queue).await {
+            DexEvent::SlotGap(g) => assert_eq!((g.from_slot, g.to_slot), (936, 1005)),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        mock.send(pumpfun_trade_update(1006, 0, 2));
+        next_event(&queue).await;
+
+        // 拒绝 from_slot：从最新位置重连，第一条更新时报告缺口
+        mock.set_first_available(None);
+        mock.set_reject_from_slot(true);
+        let requests = reconnect(&mock, 4).await;
+        assert_eq!((requests[2].from_slot, requests[3].from_slot), (Some(942), None));
+        mock.send(pumpfun_trade_update(1010, 0, 3));
+        match next_event(&queue).await {
+            DexEvent::SlotGap(g) => assert_eq!((g.from_slot, g.to_slot), (942, 1010)),
+            other => panic!("unexpected event: {:?}", other),
+        }
+        assert_eq!(slot_and_index(&next_event(&queue).await), (1010, 0));
+        handle.stop_and_join().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_connection_events_classify_errors() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, unordered_config());
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen_clone = Arc::clone(&seen);
+        grpc.on_connection_event(move |e| seen_clone.lock().unwrap().push(e.clone()));
+        let mut events = grpc.connection_events();
+        let (queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+        mock.send(pumpfun_trade_update(60, 0, 1));
+        next_event(&queue).await;
+
+        // 服务端拒绝 from_slot：Disconnected 携带 SubscriptionRejected
+        mock.set_reject_from_slot(true);
+        mock.disconnect_all();
+        let error = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let ConnectionEvent::Disconnected { error: Some(e), .. } = events.recv().await.unwrap() {
+                    return e;
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert!(matches!(error, GrpcError::SubscriptionRejected(ref m) if m.contains("from_slot")));
+        handle.stop_and_join().await.unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert!(matches!(seen[0], ConnectionEvent::Connecting { attempt: 1, .. }));
+        assert!(matches!(seen[1], ConnectionEvent::Connected { .. }));
+        assert!(matches!(seen.last(), Some(ConnectionEvent::Stopped { .. })));
+        assert!(GrpcError::from(tonic::Status::unauthenticated("bad token")).is_auth());
+        assert!(matches!(GrpcError::from(tonic::Status::unavailable("reset")), GrpcError::StreamReset(_)));
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(ClientConfig::default().validate().is_ok());
+        assert!(ClientConfig::low_latency().validate().is_ok());
+        assert!(ClientConfig::high_throughput().validate().is_ok());
+        let bad = [
+            ClientConfig { retry_delay_ms: 0, ..ClientConfig::default() },
+            ClientConfig { max_concurrent_streams: 0, ..ClientConfig::default() },
+            ClientConfig { keep_alive_interval_ms: 1000, keep_alive_timeout_ms: 1000, ..ClientConfig::default() },
+            ClientConfig { order_mode: OrderMode::Ordered, order_timeout_ms: 0, ..ClientConfig::default() },
+        ];
+        for config in bad {
+            assert!(matches!(config.validate(), Err(GrpcError::Config(_))));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_rejected_for_channel_output() {
+        let config = ClientConfig { overflow_policy: OverflowPolicy::DropOldest, ..unordered_config() };
+        assert!(config.validate().is_ok());
+        let grpc = test_client("http://127.0.0.1:1".to_string(), config);
+        let result = grpc.subscribe_dex_events_channel(vec![], vec![], None).await;
+        assert!(matches!(result, Err(GrpcError::Config(_))));
+        let result = grpc.subscribe_dex_events_stream(vec![], vec![], None).await;
+        assert!(matches!(result, Err(GrpcError::Config(_))));
+        // 队列输出支持挤出
+        let (_queue, handle) = subscribe(&grpc).await;
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_ping_reply_and_keep_alive_timeout() {
+        let mock = MockGeyser::new();
+        let config = ClientConfig { keep_alive_interval_ms: 300, keep_alive_timeout_ms: 100, ..unordered_config() };
+        let grpc = test_client(mock.serve().await, config);
+        let mut events = grpc.connection_events();
+        let (_queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+
+        // 服务端 ping：客户端立即回复
+        mock.send(SubscribeUpdate {
+            filters: vec![],
+            created_at: None,
+            update_oneof: Some(subscribe_update::UpdateOneof::Ping(SubscribeUpdatePing {})),
+        });
+        // 空闲超过 interval 再发一次 ping；mock 不回复 pong，timeout 后断开
+        let error = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let ConnectionEvent::Disconnected { error: Some(e), .. } = events.recv().await.unwrap() {
+                    return e;
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert!(matches!(error, GrpcError::StreamReset(ref m) if m.contains("ping")));
+        let pings: Vec<i32> = mock.requests().iter().filter_map(|r| r.ping.as_ref().map(|p| p.id)).collect();
+        assert_eq!(pings, vec![1, 2]);
+        handle.stop_and_join().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_slot_filter_bounds_stream() {
+        let mock = MockGeyser::new();
+        let config = ClientConfig { slot_status: true, ..ordered_config() };
+        let grpc = test_client(mock.serve().await, config);
+        let mut connection = grpc.connection_events();
+        let (stream, handle) = grpc
+            .subscribe_dex_events_stream_with_slot_filter(
+                vec![TransactionFilter::default()], vec![], None, Some(SlotFilter::range(40, 41)),
+            )
+            .await
+            .unwrap();
+        mock.wait_subscribers(1).await;
+
+        mock.send(pumpfun_trade_update(39, 0, 1));
+        mock.send(pumpfun_trade_update(40, 1, 2));
+        mock.send(pumpfun_trade_update(41, 0, 3));
+        // 下一个 slot 的状态更新不结束订阅
+        mock.send(slot_update(42, SlotStatus::SlotConfirmed));
+        mock.send(pumpfun_trade_update(41, 2, 4));
+        mock.send(pumpfun_trade_update(42, 0, 5));
+
+        let events: Vec<_> = tokio::time::timeout(Duration::from_secs(5), stream.collect::<Vec<_>>()).await.unwrap();
+        let slots: Vec<_> = events.iter().map(slot_and_index).collect();
+        assert_eq!(slots, vec![(40, 1), (41, 0), (41, 2)]);
+        tokio::time::timeout(Duration::from_secs(5), handle.join()).await.unwrap().unwrap();
+        let mut last = None;
+        while let Ok(e) = connection.try_recv() {
+            last = Some(e);
+        }
+        assert!(matches!(last, Some(ConnectionEvent::Stopped { .. })));
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_max_retries_and_concurrent_streams() {
+        // 没有服务监听的端口：连接立即失败
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let config = ClientConfig { max_retries: 2, retry_delay_ms: 10, max_concurrent_streams: 1, ..unordered_config() };
+        let grpc = test_client(endpoint, config);
+        let mut events = grpc.connection_events();
+        let (_queue, handle) = subscribe(&grpc).await;
+        assert!(matches!(
+            grpc.subscribe_dex_events(vec![], vec![], None).await,
+            Err(GrpcError::SubscriptionRejected(_))
+        ));
+
+        tokio::time::timeout(Duration::from_secs(5), handle.join()).await.unwrap().unwrap();
+        let mut attempts = Vec::new();
+        while let Ok(e) = events.try_recv() {
+            match e {
+                ConnectionEvent::Connecting { attempt, .. } => attempts.push(attempt),
+                ConnectionEvent::Disconnected { error, .. } => assert!(matches!(error, Some(GrpcError::Connect(_)))),
+                _ => {}
+            }
+        }
+        assert_eq!(attempts, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_snapshot() {
+        let mock = MockGeyser::new();
+        let grpc = test_client(mock.serve().await, ClientConfig { enable_metrics: true, ..unordered_config() });
+        let (queue, handle) = subscribe(&grpc).await;
+        mock.wait_subscribers(1).await;
+        mock.send(pumpfun_trade_update(70, 0, 1));
+        mock.send(pumpfun_trade_update(70, 1, 2));
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while queue.len() < 2 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let m = grpc.metrics();
+        assert_eq!(m.transactions.process_count, 2);
+        assert_eq!(m.transactions.events_processed, 2);
+        assert_eq!(m.events_by_protocol, vec![("PumpFun", 2)]);
+        assert_eq!(m.events_by_kind.iter().map(|(_, n)| n).sum::<u64>(), 2);
+        assert!(m.events_by_kind.iter().all(|(kind, _)| kind.starts_with("PumpFun")));
+        assert_eq!((m.queue_depth, m.max_queue_depth), (2, 2));
+        assert!(m.parse_latency.max_us >= m.parse_latency.min_us);
+        assert_eq!(m.dropped_events, 0);
+        handle.stop_and_join().await.unwrap();
+    }
+
+    /// 外层调用 router，router 以 CPI 调用 `program` 的内层指令交易
+    fn cpi_update(program: Pubkey, ix_accounts: &[Pubkey], data: Vec<u8>, logs: Vec<String>) -> SubscribeUpdateTransaction {
+        let mut keys = vec![Pubkey::new_unique(), Pubkey::new_unique(), program];
+        keys.extend_from_slice(ix_accounts);
+        let message = Message {
+            account_keys: keys.iter().map(|k| k.to_bytes().to_vec()).collect(),
+            instructions: vec![CompiledInstruction { program_id_index: 1, ..Default::default() }],
+            ..Default::default()
+        };
+        let inner = InnerInstruction {
+            program_id_index: 2,
+            accounts: (3..keys.len() as u8).collect(),
+            data,
+            stack_height: Some(2),
+        };
+        SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                signature: vec![9u8; 64],
+                transaction: Some(Transaction { message: Some(message), ..Default::default() }),
+                meta: Some(TransactionStatusMeta {
+                    log_messages: logs,
+                    inner_instructions: vec![InnerInstructions { index: 0, instructions: vec![inner] }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            slot: 1,
+        }
+    }
+
+    #[test]
+    fn test_cpi_orca_two_hop_resolves_inner_accounts() {
+        let accounts: Vec<Pubkey> = (0..27).map(|_| Pubkey::new_unique()).collect();
+        let update = cpi_update(
+            crate::instr::program_ids::ORCA_WHIRLPOOL_PROGRAM_ID,
+            &accounts,
+            crate::test_fixtures::orca_two_hop_swap_v2_data(),
+            vec![],
+        );
+        let legs: Vec<_> = parse_transaction_core(&update, 0, None, None)
+            .into_iter()
+            .map(|e| match e {
+                DexEvent::OrcaWhirlpoolSwap(e) => e,
+                other => panic!("unexpected event: {:?}", other),
+            })
+            .collect();
+        let [first, second] = legs.as_slice() else { panic!("expected two legs, got {}", legs.len()) };
+        assert_eq!((first.hop, second.hop), (1, 2));
+        assert_eq!((first.whirlpool, second.whirlpool), (accounts[0], accounts[1]));
+        assert_eq!(second.memo_program, accounts[23]);
+    }
+
+    #[test]
+    fn test_orca_two_hop_instruction_fields_merge_into_traded_logs() {
+        use crate::instr::program_ids::ORCA_WHIRLPOOL_PROGRAM_ID;
+        use crate::test_fixtures::orca_traded_log;
+        let accounts: Vec<Pubkey> = (0..27).map(|_| Pubkey::new_unique()).collect();
+        let logs = vec![
+            format!("Program {} invoke [2]", ORCA_WHIRLPOOL_PROGRAM_ID),
+            orca_traded_log(&accounts[0], true, 1_000, 950),
+            orca_traded_log(&accounts[1], false, 950, 920),
+            format!("Program {} success", ORCA_WHIRLPOOL_PROGRAM_ID),
+        ];
+        let update =
+            cpi_update(ORCA_WHIRLPOOL_PROGRAM_ID, &accounts, crate::test_fixtures::orca_two_hop_swap_v2_data(), logs);
+        let legs: Vec<_> = parse_transaction_core(&update, 0, None, None)
+            .into_iter()
+            .map(|e| match e {
+                DexEvent::OrcaWhirlpoolSwap(e) => e,
+                other => panic!("unexpected event: {:?}", other),
+            })
+            .collect();
+        let [first, second] = legs.as_slice() else { panic!("expected two legs, got {}", legs.len()) };
+        // 数量来自日志，路由字段来自指令
+        assert_eq!((first.input_amount, first.output_amount, first.lp_fee), (1_000, 950, 3));
+        assert_eq!((second.input_amount, second.output_amount), (950, 920));
+        assert_eq!((first.hop, second.hop), (1, 2));
+        assert_ne!(first.route_id, 0);
+        assert_eq!(first.route_id, second.route_id);
+        assert_eq!((first.memo_program, second.memo_program), (accounts[23], accounts[23]));
+        assert_eq!(first.transfer_hook_accounts_a, vec![accounts[24]]);
+    }
+
+    #[test]
+    fn test_outer_orca_swap_v2_merges_into_traded_log() {
+        use crate::instr::program_ids::ORCA_WHIRLPOOL_PROGRAM_ID;
+        let accounts: Vec<Pubkey> = (0..16).map(|_| Pubkey::new_unique()).collect();
+        let logs = vec![
+            format!("Program {} invoke [1]", ORCA_WHIRLPOOL_PROGRAM_ID),
+            crate::test_fixtures::orca_traded_log(&accounts[4], true, 1_000, 990),
+            format!("Program {} success", ORCA_WHIRLPOOL_PROGRAM_ID),
+        ];
+        let mut update =
+            cpi_update(ORCA_WHIRLPOOL_PROGRAM_ID, &accounts, crate::test_fixtures::orca_swap_v2_data(), logs);
+        // 把内层指令改为外层指令
+        let info = update.transaction.as_mut().unwrap();
+        let meta = info.meta.as_mut().unwrap();
+        let inner = meta.inner_instructions.remove(0).instructions.remove(0);
+        let msg = info.transaction.as_mut().unwrap().message.as_mut().unwrap();
+        msg.instructions =
+            vec![CompiledInstruction { program_id_index: inner.program_id_index, accounts: inner.accounts, data: inner.data }];
+
+        let events = parse_transaction_core(&update, 0, None, None);
+        let [DexEvent::OrcaWhirlpoolSwap(swap)] = events.as_slice() else { panic!("{:?}", events) };
+        assert_eq!((swap.whirlpool, swap.output_amount), (accounts[4], 990));
+        assert_eq!((swap.token_program_a, swap.token_program_b, swap.memo_program), (accounts[0], accounts[1], accounts[2]));
+        assert_eq!(swap.transfer_hook_accounts_a, vec![accounts[15]]);
+    }
+
+    #[test]
+    fn test_cpi_instruction_duplicating_log_event_is_dropped() {
+        let (mint, user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let ix = crate::test_fixtures::pumpfun_buy_instruction(mint, Pubkey::new_unique(), user, 1_000_000, 50_000_000);
+        let accounts: Vec<Pubkey> = ix.accounts.iter().map(|m| m.pubkey).collect();
+        let logs = crate::test_fixtures::pumpfun_trade_logs(&mint, &user);
+        let update = cpi_update(ix.program_id, &accounts, ix.data, logs);
+
+        let events = parse_transaction_core(&update, 0, None, None);
+        assert!(matches!(events.as_slice(), [DexEvent::PumpFunTrade(_)]), "{:?}", events);
+    }
+}
@@ -1,4 +1,228 @@
+//! 订阅配置文件 - 从 TOML / JSON 加载端点、`ClientConfig` 和过滤器
+//!
+//! 运维修改协议覆盖范围或连接参数时无需重新编译；token 可通过环境变量覆盖，不必写入文件。
+
 pub use crate::grpc::types::{ClientConfig, SlotFilter};
 
 // Re-export for backwards compatibility
-pub use crate::grpc::types::ClientConfig as StreamingConfig;
\ No newline at end of file
+pub use crate::grpc::types::ClientConfig as StreamingConfig;
+
+use super::client::YellowstoneGrpc;
+use super::error::GrpcError;
+use super::program_ids::{self, Cluster};
+use super::types::{AccountFilter, EventTypeFilter, Protocol, TransactionFilter};
+use crate::common::SubscriptionHandle;
+use crate::DexEvent;
+use crossbeam_queue::ArrayQueue;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// 默认的 token 环境变量
+pub const DEFAULT_TOKEN_ENV: &str = "SOL_PARSER_GRPC_TOKEN";
+
+/// 完整的订阅配置
+///
+/// ```toml
+/// endpoint = "https://solana-yellowstone-grpc.publicnode.com:443"
+/// protocols = ["PumpFun", "PumpSwap"]
+///
+/// [client]
+/// order_mode = "StreamingOrdered"
+/// commitment = "Confirmed"
+///
+/// [event_filter]
+/// include_only = ["PumpFunTrade", "PumpSwapBuy", "PumpSwapSell"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubscriptionConfig {
+    pub endpoint: String,
+    /// x-token；环境变量 `token_env`（默认 `SOL_PARSER_GRPC_TOKEN`）存在时优先使用
+    pub token: Option<String>,
+    /// 覆盖 token 的环境变量名
+    pub token_env: Option<String>,
+    pub client: ClientConfig,
+    /// transaction_filters / account_filters 为空时按协议生成
+    pub protocols: Vec<Protocol>,
+    pub transaction_filters: Vec<TransactionFilter>,
+    pub account_filters: Vec<AccountFilter>,
+    pub event_filter: Option<EventTypeFilter>,
+    pub slot_filter: Option<SlotFilter>,
+    /// 程序 ID 集群配置，设置后在 connect / subscribe 时应用到全局注册表（见 [`apply_program_ids`](Self::apply_program_ids)）
+    pub cluster: Option<Cluster>,
+    /// 按协议覆盖程序 ID（base58），在 cluster 之后应用
+    pub program_ids: HashMap<Protocol, Vec<String>>,
+}
+
+impl SubscriptionConfig {
+    /// 从文件加载：`.json` 按 JSON 解析，其余按 TOML 解析；之后应用环境变量覆盖并校验
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, GrpcError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| GrpcError::Config(format!("{}: {}", path.display(), e)))?;
+        let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let config = if is_json { Self::from_json_str(&text) } else { Self::from_toml_str(&text) };
+        config.map_err(|e| GrpcError::Config(format!("{}: {}", path.display(), e.message())))
+    }
+
+    pub fn from_toml_str(text: &str) -> Result<Self, GrpcError> {
+        let config: Self = toml::from_str(text).map_err(|e| GrpcError::Config(e.to_string()))?;
+        config.finish()
+    }
+
+    pub fn from_json_str(text: &str) -> Result<Self, GrpcError> {
+        let config: Self = serde_json::from_str(text).map_err(|e| GrpcError::Config(e.to_string()))?;
+        config.finish()
+    }
+
+    fn finish(mut self) -> Result<Self, GrpcError> {
+        let env = self.token_env.as_deref().unwrap_or(DEFAULT_TOKEN_ENV);
+        if let Some(token) = std::env::var(env).ok().filter(|t| !t.is_empty()) {
+            self.token = Some(token);
+        }
+        if self.endpoint.is_empty() {
+            return Err(GrpcError::Config("endpoint is required".to_string()));
+        }
+        self.client.validate()?;
+        self.program_id_overrides()?;
+        Ok(self)
+    }
+
+    fn program_id_overrides(&self) -> Result<Vec<(Protocol, Vec<Pubkey>)>, GrpcError> {
+        self.program_ids
+            .iter()
+            .map(|(protocol, ids)| {
+                let ids = ids
+                    .iter()
+                    .map(|id| id.parse().map_err(|_| GrpcError::Config(format!("invalid program id for {:?}: {}", protocol, id))))
+                    .collect::<Result<_, _>>()?;
+                Ok((*protocol, ids))
+            })
+            .collect()
+    }
+
+    /// 将 cluster / program_ids 应用到全局程序 ID 注册表（未配置时不做改动）
+    ///
+    /// 注册表是进程级的，不按客户端区分：同一进程中多个配置各自 connect 时，最后应用的配置生效，
+    /// 并影响所有客户端及离线解析接口。需要不同集群时请分进程运行。
+    pub fn apply_program_ids(&self) -> Result<(), GrpcError> {
+        let overrides = self.program_id_overrides()?;
+        if let Some(cluster) = self.cluster {
+            program_ids::use_cluster(cluster);
+        }
+        for (protocol, ids) in overrides {
+            program_ids::set_program_ids(protocol, ids);
+        }
+        Ok(())
+    }
+
+    /// 实际使用的交易过滤器（未配置时按 protocols 生成）
+    pub fn transaction_filters(&self) -> Vec<TransactionFilter> {
+        if self.transaction_filters.is_empty() && !self.protocols.is_empty() {
+            return vec![TransactionFilter::for_protocols(&self.protocols)];
+        }
+        self.transaction_filters.clone()
+    }
+
+    /// 实际使用的账户过滤器（未配置时按 protocols 生成）
+    pub fn account_filters(&self) -> Vec<AccountFilter> {
+        if self.account_filters.is_empty() && !self.protocols.is_empty() {
+            return vec![AccountFilter::for_protocols(&self.protocols)];
+        }
+        self.account_filters.clone()
+    }
+
+    /// 按配置创建客户端（同时把程序 ID 配置应用到进程级注册表）
+    pub fn connect(&self) -> Result<YellowstoneGrpc, GrpcError> {
+        self.apply_program_ids()?;
+        YellowstoneGrpc::new_with_config(self.endpoint.clone(), self.token.clone(), self.client.clone())
+    }
+
+    /// 创建客户端并按配置订阅，输出到无锁队列
+    pub async fn subscribe(
+        &self,
+    ) -> Result<(YellowstoneGrpc, Arc<ArrayQueue<DexEvent>>, SubscriptionHandle), GrpcError> {
+        let grpc = self.connect()?;
+        // 过滤器在注册表更新之后生成
+        let (queue, handle) = grpc
+            .subscribe_dex_events_with_slot_filter(
+                self.transaction_filters(),
+                self.account_filters(),
+                self.event_filter.clone(),
+                self.slot_filter,
+            )
+            .await?;
+        Ok((grpc, queue, handle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::types::{AccountFilterData, Commitment, EventType, OrderMode};
+    use crate::grpc::program_ids::PUMPFUN_PROGRAM_ID;
+
+    #[test]
+    fn test_load_toml_and_json() {
+        let toml = r#"
+            endpoint = "http://127.0.0.1:10000"
+            token = "from-file"
+            token_env = "SOL_PARSER_TEST_TOKEN_TOML"
+            protocols = ["PumpFun"]
+
+            [client]
+            enable_tls = false
+            order_mode = "StreamingOrdered"
+            commitment = "Confirmed"
+
+            [event_filter]
+            include_only = ["PumpFunTrade"]
+
+            [slot_filter]
+            max_slot = 500
+
+            [[account_filters]]
+            owner = ["TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"]
+            filters = [{ datasize = 165 }, { memcmp = { offset = 32, bytes = "3yZe7d" } }]
+        "#;
+        let config = SubscriptionConfig::from_toml_str(toml).unwrap();
+        assert_eq!(config.token.as_deref(), Some("from-file"));
+        assert_eq!((config.client.order_mode, config.client.commitment), (OrderMode::StreamingOrdered, Commitment::Confirmed));
+        // 未配置的字段取默认值
+        assert_eq!(config.client.queue_capacity, ClientConfig::default().queue_capacity);
+        assert!(config.event_filter.as_ref().unwrap().should_include(EventType::PumpFunTrade));
+        assert_eq!(config.slot_filter, Some(SlotFilter::new().max_slot(500)));
+        assert_eq!(config.transaction_filters()[0].account_include, vec![PUMPFUN_PROGRAM_ID.to_string()]);
+        let filters = &config.account_filters()[0].filters;
+        assert_eq!(filters.len(), 2);
+        let memcmp = AccountFilterData::from_filter(&filters[1]).unwrap().memcmp.unwrap();
+        assert_eq!((memcmp.offset, memcmp.bytes), (32, bs58::decode("3yZe7d").into_vec().unwrap()));
+
+        std::env::set_var("SOL_PARSER_TEST_TOKEN_TOML", "from-env");
+        let config = SubscriptionConfig::from_toml_str(toml).unwrap();
+        std::env::remove_var("SOL_PARSER_TEST_TOKEN_TOML");
+        assert_eq!(config.token.as_deref(), Some("from-env"));
+
+        let json = serde_json::to_string(&config).unwrap();
+        let reloaded = SubscriptionConfig::from_json_str(&json).unwrap();
+        assert_eq!(reloaded.account_filters[0].filters, config.account_filters[0].filters);
+
+        assert!(matches!(SubscriptionConfig::from_toml_str("protocols = []"), Err(GrpcError::Config(_))));
+        let invalid = "endpoint = \"http://x\"\n[client]\nretry_delay_ms = 0";
+        assert!(matches!(SubscriptionConfig::from_toml_str(invalid), Err(GrpcError::Config(_))));
+        assert!(SubscriptionConfig::from_toml_str("endpoint = \"http://x\"\nprotocols = [\"Unknown\"]").is_err());
+    }
+
+    #[test]
+    fn test_load_program_id_overrides() {
+        let with_ids = "endpoint = \"http://x\"\ncluster = \"Devnet\"\n[program_ids]\nPumpFun = [\"6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P\"]";
+        let config = SubscriptionConfig::from_toml_str(with_ids).unwrap();
+        assert_eq!(config.cluster, Some(Cluster::Devnet));
+        assert_eq!(config.program_ids[&Protocol::PumpFun], vec![PUMPFUN_PROGRAM_ID.to_string()]);
+        let bad_id = "endpoint = \"http://x\"\n[program_ids]\nPumpFun = [\"not-a-key\"]";
+        assert!(matches!(SubscriptionConfig::from_toml_str(bad_id), Err(GrpcError::Config(_))));
+    }
+}
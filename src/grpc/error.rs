@@ -0,0 +1,108 @@
+//! gRPC 客户端错误类型与连接状态事件
+
+use std::time::Duration;
+use tonic::Code;
+use yellowstone_grpc_client::{GeyserGrpcBuilderError, GeyserGrpcClientError};
+
+/// gRPC 客户端错误
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum GrpcError {
+    /// 配置或 endpoint 无效
+    #[error("invalid configuration: {0}")]
+    Config(String),
+    /// 无法建立连接（网络、TLS、超时）
+    #[error("connect failed: {0}")]
+    Connect(String),
+    /// token 无效或无权限
+    #[error("authentication failed: {0}")]
+    Auth(String),
+    /// 已建立的订阅流中断
+    #[error("stream reset: {0}")]
+    StreamReset(String),
+    /// 消息解码失败（含超出最大消息长度）
+    #[error("decode error: {0}")]
+    Decode(String),
+    /// 服务端拒绝订阅请求（过滤器无效、超出配额、from_slot 不可用等）
+    #[error("subscription rejected: {0}")]
+    SubscriptionRejected(String),
+    /// 当前没有活跃订阅
+    #[error("no active subscription")]
+    NoActiveSubscription,
+}
+
+impl GrpcError {
+    /// 按 gRPC 状态码分类
+    pub fn from_status(status: &tonic::Status) -> Self {
+        let message = status.message().to_string();
+        match status.code() {
+            Code::Unauthenticated | Code::PermissionDenied => GrpcError::Auth(message),
+            Code::InvalidArgument
+            | Code::FailedPrecondition
+            | Code::Unimplemented
+            | Code::ResourceExhausted
+            | Code::NotFound
+            | Code::AlreadyExists => GrpcError::SubscriptionRejected(message),
+            Code::DataLoss | Code::OutOfRange => GrpcError::Decode(message),
+            Code::Internal if message.contains("decode") => GrpcError::Decode(message),
+            _ => GrpcError::StreamReset(format!("{:?}: {}", status.code(), message)),
+        }
+    }
+
+    /// 认证类错误（重连通常无法恢复）
+    #[inline]
+    pub fn is_auth(&self) -> bool {
+        matches!(self, GrpcError::Auth(_))
+    }
+
+    /// 错误详情（不含分类前缀）
+    pub fn message(&self) -> &str {
+        match self {
+            GrpcError::Config(m)
+            | GrpcError::Connect(m)
+            | GrpcError::Auth(m)
+            | GrpcError::StreamReset(m)
+            | GrpcError::Decode(m)
+            | GrpcError::SubscriptionRejected(m) => m,
+            GrpcError::NoActiveSubscription => "no active subscription",
+        }
+    }
+}
+
+impl From<tonic::Status> for GrpcError {
+    fn from(status: tonic::Status) -> Self {
+        GrpcError::from_status(&status)
+    }
+}
+
+impl From<GeyserGrpcBuilderError> for GrpcError {
+    fn from(err: GeyserGrpcBuilderError) -> Self {
+        match err {
+            GeyserGrpcBuilderError::MetadataValueError(e) => GrpcError::Auth(format!("invalid x-token: {}", e)),
+            GeyserGrpcBuilderError::TonicError(e) => GrpcError::Connect(e.to_string()),
+        }
+    }
+}
+
+impl From<GeyserGrpcClientError> for GrpcError {
+    fn from(err: GeyserGrpcClientError) -> Self {
+        match err {
+            GeyserGrpcClientError::TonicStatus(status) => GrpcError::from_status(&status),
+            GeyserGrpcClientError::SubscribeSendError(e) => GrpcError::StreamReset(e.to_string()),
+        }
+    }
+}
+
+/// 连接状态事件，通过 `connection_events()` / `on_connection_event()` 获取
+#[derive(Debug, Clone)]
+pub enum ConnectionEvent {
+    /// 开始第 attempt 次连接（连续失败时递增，订阅建立后从 1 重新计数）
+    Connecting { endpoint: String, attempt: u32 },
+    /// 订阅已建立
+    Connected { endpoint: String },
+    /// 连接断开；正常结束时 error 为 None
+    Disconnected { endpoint: String, error: Option<GrpcError> },
+    /// 等待 delay 后重连
+    Reconnecting { endpoint: String, delay: Duration },
+    /// 订阅已停止，不再重连
+    Stopped { endpoint: String },
+}
@@ -9,11 +9,7 @@ use crate::grpc::program_ids::get_program_ids_for_protocols;
 impl TransactionFilter {
     pub fn for_protocols(protocols: &[Protocol]) -> Self {
         let program_ids = get_program_ids_for_protocols(protocols);
-        Self {
-            account_include: program_ids,
-            account_exclude: Vec::new(),
-            account_required: Vec::new(),
-        }
+        Self::from_program_ids(program_ids)
     }
 }
 
@@ -0,0 +1,250 @@
+//! 测试用 Yellowstone Geyser mock 服务
+//!
+//! yellowstone-grpc-proto 默认不生成服务端代码，这里手写最小的 `geyser.Geyser/Subscribe`
+//! 双向流实现：记录客户端发来的 SubscribeRequest，并把测试推送的 SubscribeUpdate 广播给所有订阅者。
+//! 另外支持 `SubscribeReplayInfo`、断开全部连接和拒绝 from_slot，用于续传测试。
+
+use futures::StreamExt;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, watch};
+use tonic::codegen::*;
+use yellowstone_grpc_proto::prelude::*;
+
+#[derive(Clone)]
+pub(crate) struct MockGeyser {
+    updates: broadcast::Sender<SubscribeUpdate>,
+    requests: Arc<Mutex<Vec<SubscribeRequest>>>,
+    /// 递增即断开当前全部订阅流
+    epoch: Arc<watch::Sender<u64>>,
+    /// SubscribeReplayInfo 返回的 first_available；None 时该接口返回 unimplemented
+    first_available: Arc<Mutex<Option<u64>>>,
+    /// 以 invalid_argument 拒绝带 from_slot 的订阅
+    reject_from_slot: Arc<Mutex<bool>>,
+}
+
+impl MockGeyser {
+    pub(crate) fn new() -> Self {
+        let (updates, _) = broadcast::channel(1024);
+        Self {
+            updates,
+            requests: Arc::new(Mutex::new(Vec::new())),
+            epoch: Arc::new(watch::channel(0).0),
+            first_available: Arc::new(Mutex::new(None)),
+            reject_from_slot: Arc::new(Mutex::new(false)),
+        }
+    }
+
+    /// 在随机端口启动服务，返回 endpoint
+    pub(crate) async fn serve(&self) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let svc = self.clone();
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(svc)
+                .serve_with_incoming(tonic::transport::server::TcpIncoming::from(listener))
+                .await
+                .unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    /// 推送一条更新给所有当前订阅者
+    pub(crate) fn send(&self, update: SubscribeUpdate) {
+        let _ = self.updates.send(update);
+    }
+
+    /// 等待至少 n 个订阅者连上
+    pub(crate) async fn wait_subscribers(&self, n: usize) {
+        while self.updates.receiver_count() < n {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    pub(crate) fn subscriber_count(&self) -> usize {
+        self.updates.receiver_count()
+    }
+
+    /// 客户端发来的全部 SubscribeRequest
+    pub(crate) fn requests(&self) -> Vec<SubscribeRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// 正常结束当前全部订阅流（客户端会重连）
+    pub(crate) fn disconnect_all(&self) {
+        self.epoch.send_modify(|e| *e += 1);
+    }
+
+    pub(crate) fn set_first_available(&self, slot: Option<u64>) {
+        *self.first_available.lock().unwrap() = slot;
+    }
+
+    pub(crate) fn set_reject_from_slot(&self, reject: bool) {
+        *self.reject_from_slot.lock().unwrap() = reject;
+    }
+}
+
+impl<B> Service<http::Request<B>> for MockGeyser
+where
+    B: Body + std::marker::Send + 'static,
+    B::Error: Into<StdError> + std::marker::Send + 'static,
+{
+    type Response = http::Response<tonic::body::Body>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        let mock = self.clone();
+        Box::pin(async move {
+            match req.uri().path() {
+                "/geyser.Geyser/Subscribe" => {
+                    Ok(tonic::server::Grpc::new(tonic_prost::ProstCodec::default()).streaming(SubscribeSvc(mock), req).await)
+                }
+                "/geyser.Geyser/SubscribeReplayInfo" => {
+                    Ok(tonic::server::Grpc::new(tonic_prost::ProstCodec::default()).unary(ReplayInfoSvc(mock), req).await)
+                }
+                _ => Ok(tonic::Status::unimplemented("mock").into_http()),
+            }
+        })
+    }
+}
+
+impl tonic::server::NamedService for MockGeyser {
+    const NAME: &'static str = "geyser.Geyser";
+}
+
+struct SubscribeSvc(MockGeyser);
+
+impl tonic::server::StreamingService<SubscribeRequest> for SubscribeSvc {
+    type Response = SubscribeUpdate;
+    type ResponseStream = futures::stream::BoxStream<'static, Result<SubscribeUpdate, tonic::Status>>;
+    type Future = BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+
+    fn call(&mut self, request: tonic::Request<tonic::Streaming<SubscribeRequest>>) -> Self::Future {
+        let mock = self.0.clone();
+        Box::pin(async move {
+            let mut incoming = request.into_inner();
+            // 客户端在建立流之前就会发出第一条请求
+            let first = match incoming.next().await {
+                Some(Ok(req)) => req,
+                _ => return Err(tonic::Status::cancelled("no request")),
+            };
+            let rejected = first.from_slot.is_some() && *mock.reject_from_slot.lock().unwrap();
+            let requests = Arc::clone(&mock.requests);
+            requests.lock().unwrap().push(first);
+            if rejected {
+                let status = tonic::Status::invalid_argument("broadcast from_slot is not available");
+                return Ok(tonic::Response::new(futures::stream::iter([Err(status)]).boxed()));
+            }
+            tokio::spawn(async move {
+                while let Some(Ok(req)) = incoming.next().await {
+                    requests.lock().unwrap().push(req);
+                }
+            });
+
+            let rx = mock.updates.subscribe();
+            let mut epoch = mock.epoch.subscribe();
+            epoch.borrow_and_update();
+            let stream = futures::stream::unfold((rx, epoch), |(mut rx, mut epoch)| async move {
+                loop {
+                    tokio::select! {
+                        msg = rx.recv() => match msg {
+                            Ok(update) => return Some((Ok(update), (rx, epoch))),
+                            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(broadcast::error::RecvError::Closed) => return None,
+                        },
+                        _ = epoch.changed() => return None,
+                    }
+                }
+            });
+            Ok(tonic::Response::new(stream.boxed()))
+        })
+    }
+}
+
+struct ReplayInfoSvc(MockGeyser);
+
+impl tonic::server::UnaryService<SubscribeReplayInfoRequest> for ReplayInfoSvc {
+    type Response = SubscribeReplayInfoResponse;
+    type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+
+    fn call(&mut self, _request: tonic::Request<SubscribeReplayInfoRequest>) -> Self::Future {
+        let first_available = *self.0.first_available.lock().unwrap();
+        Box::pin(async move {
+            match first_available {
+                Some(slot) => Ok(tonic::Response::new(SubscribeReplayInfoResponse { first_available: Some(slot) })),
+                None => Err(tonic::Status::unimplemented("mock")),
+            }
+        })
+    }
+}
+
+// ==================== 测试数据 ====================
+
+/// 构造一条只含 PumpFun TradeEvent 日志的交易更新（签名首字节为 sig_byte）
+pub(crate) fn pumpfun_trade_update(slot: u64, index: u64, sig_byte: u8) -> SubscribeUpdate {
+    let logs = crate::test_fixtures::pumpfun_trade_logs(&Pubkey::new_unique(), &Pubkey::new_unique());
+
+    let mut signature = vec![0u8; 64];
+    signature[0] = sig_byte;
+    signature[1..9].copy_from_slice(&slot.to_le_bytes());
+    SubscribeUpdate {
+        filters: vec!["dex_transactions".to_string()],
+        update_oneof: Some(subscribe_update::UpdateOneof::Transaction(SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                signature,
+                is_vote: false,
+                transaction: None,
+                meta: Some(TransactionStatusMeta { log_messages: logs, ..Default::default() }),
+                index,
+            }),
+            slot,
+        })),
+        created_at: None,
+    }
+}
+
+/// 构造一条 slot 状态更新
+pub(crate) fn slot_update(slot: u64, status: SlotStatus) -> SubscribeUpdate {
+    SubscribeUpdate {
+        filters: vec!["slots".to_string()],
+        update_oneof: Some(subscribe_update::UpdateOneof::Slot(SubscribeUpdateSlot {
+            slot,
+            parent: slot.checked_sub(1),
+            status: status as i32,
+            dead_error: (status == SlotStatus::SlotDead).then(|| "mock dead".to_string()),
+        })),
+        created_at: None,
+    }
+}
+
+/// 把若干交易更新打包成一个区块更新
+pub(crate) fn block_update(slot: u64, block_time: i64, txs: Vec<SubscribeUpdate>) -> SubscribeUpdate {
+    let transactions: Vec<_> = txs
+        .into_iter()
+        .filter_map(|u| match u.update_oneof {
+            Some(subscribe_update::UpdateOneof::Transaction(tx)) => tx.transaction,
+            _ => None,
+        })
+        .collect();
+    SubscribeUpdate {
+        filters: vec!["blocks".to_string()],
+        update_oneof: Some(subscribe_update::UpdateOneof::Block(SubscribeUpdateBlock {
+            slot,
+            blockhash: "mock-blockhash".to_string(),
+            block_time: Some(UnixTimestamp { timestamp: block_time }),
+            block_height: Some(BlockHeight { block_height: slot - 10 }),
+            parent_slot: slot - 1,
+            parent_blockhash: "mock-parent".to_string(),
+            executed_transaction_count: transactions.len() as u64,
+            transactions,
+            ..Default::default()
+        })),
+        created_at: None,
+    }
+}
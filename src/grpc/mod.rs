@@ -9,16 +9,27 @@
 pub mod buffers;
 pub mod client;
 pub mod config;
+pub mod error;
 pub mod event_parser;
 pub mod filter;
+pub mod multi_source;
+pub mod output;
 pub mod program_ids;
+pub mod resume;
 pub mod types;
 
+#[cfg(test)]
+pub(crate) mod mock_server;
+
 // 重新导出主要API
 pub use client::YellowstoneGrpc;
+pub use config::SubscriptionConfig;
+pub use error::{ConnectionEvent, GrpcError};
+pub use multi_source::{MultiSourceGrpc, SourceMetrics};
+pub use program_ids::{Cluster, ProgramIdRegistry};
 pub use types::{
-    AccountFilter, ClientConfig, EventType as StreamingEventType, EventTypeFilter, OrderMode,
-    Protocol, SlotFilter, TransactionFilter,
+    AccountFilter, ClientConfig, Commitment, EventType as StreamingEventType, EventTypeFilter, ForkPolicy,
+    OrderMode, OverflowPolicy, Protocol, SlotFilter, TransactionFilter,
 };
 
 // 事件解析器重新导出
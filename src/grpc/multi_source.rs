@@ -0,0 +1,570 @@
+//! 多源冗余订阅 - 同一组过滤器订阅多个 Yellowstone 端点，先到先得
+//!
+//! 各端点独立解析，事件按 (签名, 事件类型, 交易内同类事件序号) 去重，只转发最先到达的副本。
+//! 同一交易解析出的事件序列在各端点上一致，序号即对应外层 / 内层指令的位置。
+//! 每个端点的抢先率和延迟通过 `source_metrics()` 查看。
+//! 单个端点的 `SlotGap` 只有在其他端点已完整覆盖该区间时才丢弃，否则照常转发。
+
+use super::client::YellowstoneGrpc;
+use super::error::GrpcError;
+use super::output::{EventSink, EventTarget};
+use super::types::{AccountFilter, ClientConfig, EventTypeFilter, SlotFilter, TransactionFilter};
+use crate::common::{MetricsManager, MetricsSnapshot, SubscriptionHandle};
+use crate::DexEvent;
+use crossbeam_queue::ArrayQueue;
+use futures::StreamExt;
+use solana_sdk::signature::Signature;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::mem::Discriminant;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch};
+use tokio_stream::wrappers::ReceiverStream;
+
+/// 去重记录保留的 slot 数量
+const DEDUP_WINDOW_SLOTS: u64 = 64;
+
+// ==================== 端点统计 ====================
+
+#[derive(Default)]
+struct SourceStats {
+    received: AtomicU64,
+    wins: AtomicU64,
+    /// 非首个副本落后首个副本的累计时间
+    lag_us: AtomicU64,
+    lag_samples: AtomicU64,
+    /// 服务端 created_at 到本地接收的累计延迟
+    latency_us: AtomicU64,
+    latency_samples: AtomicU64,
+}
+
+/// 单个端点的统计快照
+#[derive(Debug, Clone)]
+pub struct SourceMetrics {
+    pub endpoint: String,
+    /// 收到的事件数（含被去重的副本）
+    pub events_received: u64,
+    /// 最先到达而被转发的事件数
+    pub wins: u64,
+    /// wins / 去重后转发的事件总数
+    pub win_rate: f64,
+    /// 未抢先时落后首个副本的平均时间（微秒）
+    pub avg_lag_us: f64,
+    /// 服务端 created_at 到本地接收的平均延迟（微秒），服务端未提供时间戳时为 0
+    pub avg_latency_us: f64,
+}
+
+#[inline]
+fn average(total: &AtomicU64, samples: &AtomicU64) -> f64 {
+    match samples.load(Ordering::Relaxed) {
+        0 => 0.0,
+        n => total.load(Ordering::Relaxed) as f64 / n as f64,
+    }
+}
+
+// ==================== 去重 ====================
+
+#[derive(Hash, PartialEq, Eq)]
+enum DedupKey {
+    /// 交易事件：签名 + 事件类型 + 交易内同类事件序号
+    Transaction(Signature, Discriminant<DexEvent>, u32),
+    /// 账户 / slot 事件：事件类型 + slot + 内容哈希
+    Other(Discriminant<DexEvent>, u64, u64),
+}
+
+struct Seen {
+    slot: u64,
+    recv_us: i64,
+}
+
+/// 单个端点收到事件的 slot 范围与自身报告的缺口
+#[derive(Default)]
+struct Coverage {
+    first_slot: Option<u64>,
+    last_slot: u64,
+    gaps: Vec<(u64, u64)>,
+}
+
+impl Coverage {
+    /// [from, to) 前后都收到过事件，且期间没有报告缺口
+    fn covers(&self, from: u64, to: u64) -> bool {
+        self.first_slot.is_some_and(|first| first <= from)
+            && self.last_slot + 1 >= to
+            && !self.gaps.iter().any(|&(f, t)| f < to && from < t)
+    }
+}
+
+struct Deduper {
+    seen: HashMap<DedupKey, Seen>,
+    max_slot: u64,
+    /// 每个端点当前交易内各事件类型已出现的次数
+    cursors: Vec<(Signature, HashMap<Discriminant<DexEvent>, u32>)>,
+    coverage: Vec<Coverage>,
+}
+
+impl Deduper {
+    fn new(sources: usize) -> Self {
+        Self {
+            seen: HashMap::new(),
+            max_slot: 0,
+            cursors: (0..sources).map(|_| (Signature::default(), HashMap::new())).collect(),
+            coverage: (0..sources).map(|_| Coverage::default()).collect(),
+        }
+    }
+
+    /// 记录端点收到事件的 slot
+    #[inline]
+    fn observe_slot(&mut self, source: usize, slot: u64) {
+        if slot == 0 {
+            return;
+        }
+        let c = &mut self.coverage[source];
+        c.first_slot.get_or_insert(slot);
+        c.last_slot = c.last_slot.max(slot);
+    }
+
+    /// 记录端点的缺口，返回该缺口是否已由其他端点覆盖
+    fn gap_covered(&mut self, source: usize, from: u64, to: u64) -> bool {
+        let covered = self.coverage.iter().enumerate().any(|(i, c)| i != source && c.covers(from, to));
+        let keep_from = self.max_slot.saturating_sub(DEDUP_WINDOW_SLOTS);
+        let gaps = &mut self.coverage[source].gaps;
+        gaps.retain(|&(_, t)| t >= keep_from);
+        gaps.push((from, to));
+        covered
+    }
+
+    fn key(&mut self, source: usize, event: &DexEvent) -> DedupKey {
+        let kind = std::mem::discriminant(event);
+        let (signature, slot) = event.metadata().map(|m| (m.signature, m.slot)).unwrap_or_default();
+        if signature != Signature::default() {
+            let (current, counts) = &mut self.cursors[source];
+            if *current != signature {
+                *current = signature;
+                counts.clear();
+            }
+            let ordinal = counts.entry(kind).or_insert(0);
+            *ordinal += 1;
+            return DedupKey::Transaction(signature, kind, *ordinal - 1);
+        }
+        DedupKey::Other(kind, slot, content_hash(event))
+    }
+
+    /// 首次出现返回 None；重复副本返回首个副本的接收时间
+    fn observe(&mut self, source: usize, event: &DexEvent) -> Option<i64> {
+        let key = self.key(source, event);
+        let (slot, recv_us) = event.metadata().map(|m| (m.slot, m.grpc_recv_us)).unwrap_or_default();
+        if let Some(first) = self.seen.get(&key) {
+            return Some(first.recv_us);
+        }
+        self.seen.insert(key, Seen { slot, recv_us });
+        if slot > self.max_slot {
+            self.max_slot = slot;
+            let keep_from = slot.saturating_sub(DEDUP_WINDOW_SLOTS);
+            self.seen.retain(|_, s| s.slot >= keep_from);
+        }
+        None
+    }
+}
+
+/// 不含接收时间的事件内容哈希
+fn content_hash(event: &DexEvent) -> u64 {
+    let mut event = event.clone();
+    if let Some(m) = event.metadata_mut() {
+        m.grpc_recv_us = 0;
+        m.block_time_us = 0;
+        m.timing = Default::default();
+    }
+    let mut hasher = DefaultHasher::new();
+    bincode::serialize(&event).unwrap_or_default().hash(&mut hasher);
+    hasher.finish()
+}
+
+// ==================== MultiSourceGrpc ====================
+
+/// 多端点冗余订阅客户端
+pub struct MultiSourceGrpc {
+    sources: Vec<YellowstoneGrpc>,
+    endpoints: Vec<String>,
+    config: ClientConfig,
+    stats: Arc<Vec<SourceStats>>,
+    metrics: Arc<MetricsManager>,
+}
+
+impl MultiSourceGrpc {
+    /// endpoints: (endpoint, x-token)，所有端点共用同一份配置
+    pub fn new(
+        endpoints: Vec<(String, Option<String>)>,
+        config: ClientConfig,
+    ) -> Result<Self, GrpcError> {
+        let sources = endpoints
+            .into_iter()
+            .map(|(endpoint, token)| YellowstoneGrpc::new_with_config(endpoint, token, config.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_sources(sources, config)
+    }
+
+    /// 使用已创建的客户端
+    pub fn from_sources(
+        sources: Vec<YellowstoneGrpc>,
+        config: ClientConfig,
+    ) -> Result<Self, GrpcError> {
+        if sources.is_empty() {
+            return Err(GrpcError::Config("MultiSourceGrpc requires at least one endpoint".to_string()));
+        }
+        config.validate()?;
+        let endpoints = sources.iter().map(|s| s.endpoint().to_string()).collect();
+        let stats = Arc::new(sources.iter().map(|_| SourceStats::default()).collect());
+        let metrics = Arc::new(MetricsManager::new(config.enable_metrics, "MultiSourceGrpc".to_string()));
+        Ok(Self { sources, endpoints, config, stats, metrics })
+    }
+
+    /// 订阅所有端点，去重后输出到无锁队列
+    pub async fn subscribe_dex_events(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+    ) -> Result<(Arc<ArrayQueue<DexEvent>>, SubscriptionHandle), GrpcError> {
+        self.subscribe_dex_events_with_slot_filter(transaction_filters, account_filters, event_type_filter, None).await
+    }
+
+    /// 同 [`subscribe_dex_events`](Self::subscribe_dex_events)，各端点只输出 slot 区间内的事件
+    ///
+    /// 所有端点都超过 max_slot 后订阅结束（`handle.join()` 返回）。
+    pub async fn subscribe_dex_events_with_slot_filter(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        slot_filter: Option<SlotFilter>,
+    ) -> Result<(Arc<ArrayQueue<DexEvent>>, SubscriptionHandle), GrpcError> {
+        let queue = Arc::new(ArrayQueue::new(self.config.queue_capacity.max(1)));
+        let target = EventTarget::Queue(Arc::clone(&queue));
+        let handle = self.spawn_merge(transaction_filters, account_filters, event_type_filter, slot_filter, target).await?;
+        Ok((queue, handle))
+    }
+
+    /// 订阅所有端点，去重后输出到 `tokio::sync::mpsc` 通道
+    pub async fn subscribe_dex_events_channel(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+    ) -> Result<(mpsc::Receiver<DexEvent>, SubscriptionHandle), GrpcError> {
+        self.subscribe_dex_events_channel_with_slot_filter(transaction_filters, account_filters, event_type_filter, None)
+            .await
+    }
+
+    /// 同 [`subscribe_dex_events_channel`](Self::subscribe_dex_events_channel)，各端点只输出 slot 区间内的事件
+    ///
+    /// 所有端点都超过 max_slot 后通道关闭。
+    pub async fn subscribe_dex_events_channel_with_slot_filter(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        slot_filter: Option<SlotFilter>,
+    ) -> Result<(mpsc::Receiver<DexEvent>, SubscriptionHandle), GrpcError> {
+        self.config.validate_channel_target()?;
+        let (tx, rx) = mpsc::channel(self.config.queue_capacity.max(1));
+        let target = EventTarget::Channel(tx);
+        let handle = self.spawn_merge(transaction_filters, account_filters, event_type_filter, slot_filter, target).await?;
+        Ok((rx, handle))
+    }
+
+    /// 各端点的抢先率与延迟
+    pub fn source_metrics(&self) -> Vec<SourceMetrics> {
+        let total_wins: u64 = self.stats.iter().map(|s| s.wins.load(Ordering::Relaxed)).sum();
+        self.endpoints
+            .iter()
+            .zip(self.stats.iter())
+            .map(|(endpoint, s)| {
+                let wins = s.wins.load(Ordering::Relaxed);
+                SourceMetrics {
+                    endpoint: endpoint.clone(),
+                    events_received: s.received.load(Ordering::Relaxed),
+                    wins,
+                    win_rate: if total_wins == 0 { 0.0 } else { wins as f64 / total_wins as f64 },
+                    avg_lag_us: average(&s.lag_us, &s.lag_samples),
+                    avg_latency_us: average(&s.latency_us, &s.latency_samples),
+                }
+            })
+            .collect()
+    }
+
+    /// 去重后输出的指标快照（各端点自身的指标见 `YellowstoneGrpc::metrics()`）
+    pub fn metrics(&self) -> MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// 停止全部端点上的订阅
+    pub async fn stop(&self) {
+        for source in &self.sources {
+            source.stop().await;
+        }
+    }
+
+    async fn spawn_merge(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        account_filters: Vec<AccountFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+        slot_filter: Option<SlotFilter>,
+        target: EventTarget,
+    ) -> Result<SubscriptionHandle, GrpcError> {
+        let mut receivers = Vec::with_capacity(self.sources.len());
+        let mut handles = SourceHandles(Vec::with_capacity(self.sources.len()));
+        for source in &self.sources {
+            let (rx, handle) = source
+                .subscribe_dex_events_channel_with_slot_filter(
+                    transaction_filters.clone(),
+                    account_filters.clone(),
+                    event_type_filter.clone(),
+                    slot_filter,
+                )
+                .await?;
+            receivers.push(rx);
+            handles.0.push(handle);
+        }
+
+        let mut merged = futures::stream::select_all(
+            receivers.into_iter().enumerate().map(|(i, rx)| ReceiverStream::new(rx).map(move |e| (i, e))),
+        );
+        let mut sink = EventSink::new(target, self.config.overflow_policy, Arc::clone(&self.metrics));
+        let mut dedup = Deduper::new(self.sources.len());
+        let stats = Arc::clone(&self.stats);
+        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+
+        let merge_handle = tokio::spawn(async move {
+            let mut stopping = false;
+            loop {
+                let next = tokio::select! {
+                    next = merged.next() => next,
+                    // 停止各端点订阅（它们会先输出缓冲事件），随后继续转发直到全部结束
+                    _ = shutdown_rx.wait_for(|stop| *stop), if !stopping => {
+                        stopping = true;
+                        handles.0.iter().for_each(SubscriptionHandle::stop);
+                        continue;
+                    }
+                };
+                let Some((source, event)) = next else { break };
+                forward(source, event, &mut dedup, &stats, &mut sink);
+                if sink.should_block() {
+                    sink.wait_pending().await;
+                }
+            }
+            sink.finish();
+            handles.join().await;
+        });
+
+        Ok(SubscriptionHandle::new(merge_handle, None, None).with_shutdown(Arc::new(shutdown_tx)))
+    }
+}
+
+/// 合并任务持有的端点订阅；合并任务被中止或 drop 时一并中止，避免端点任务泄漏
+struct SourceHandles(Vec<SubscriptionHandle>);
+
+impl SourceHandles {
+    /// 正常结束：等待各端点任务退出
+    async fn join(mut self) {
+        for handle in std::mem::take(&mut self.0) {
+            if let Err(e) = handle.join().await {
+                log::warn!("multi-source endpoint task failed: {}", e);
+            }
+        }
+    }
+}
+
+impl Drop for SourceHandles {
+    fn drop(&mut self) {
+        self.0.iter().for_each(SubscriptionHandle::abort);
+    }
+}
+
+#[inline]
+fn forward(source: usize, event: DexEvent, dedup: &mut Deduper, stats: &[SourceStats], sink: &mut EventSink) {
+    // 单个端点的续传缺口：其他端点已覆盖该区间时丢弃，否则转发
+    if let DexEvent::SlotGap(gap) = &event {
+        if !dedup.gap_covered(source, gap.from_slot, gap.to_slot) {
+            sink.push(event);
+        }
+        return;
+    }
+    let s = &stats[source];
+    s.received.fetch_add(1, Ordering::Relaxed);
+    let (recv_us, block_us) = event.metadata().map(|m| (m.grpc_recv_us, m.block_time_us)).unwrap_or_default();
+    if block_us > 0 && recv_us >= block_us {
+        s.latency_us.fetch_add((recv_us - block_us) as u64, Ordering::Relaxed);
+        s.latency_samples.fetch_add(1, Ordering::Relaxed);
+    }
+
+    dedup.observe_slot(source, event.metadata().map_or(0, |m| m.slot));
+    match dedup.observe(source, &event) {
+        None => {
+            s.wins.fetch_add(1, Ordering::Relaxed);
+            sink.push(event);
+        }
+        Some(first_recv_us) => {
+            s.lag_us.fetch_add(recv_us.saturating_sub(first_recv_us).max(0) as u64, Ordering::Relaxed);
+            s.lag_samples.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::mock_server::{pumpfun_trade_update, MockGeyser};
+    use crate::grpc::types::OrderMode;
+    use tokio::time::Duration;
+
+    #[tokio::test]
+    async fn test_first_wins_dedupe_across_endpoints() {
+        let config = ClientConfig { enable_tls: false, order_mode: OrderMode::Unordered, ..ClientConfig::default() };
+        let (mock_a, mock_b) = (MockGeyser::new(), MockGeyser::new());
+        let sources = vec![
+            YellowstoneGrpc::new_unwarmed(mock_a.serve().await, config.clone()),
+            YellowstoneGrpc::new_unwarmed(mock_b.serve().await, config.clone()),
+        ];
+        let multi = MultiSourceGrpc::from_sources(sources, config).unwrap();
+        let (mut rx, handle) = multi
+            .subscribe_dex_events_channel(vec![TransactionFilter::default()], vec![], None)
+            .await
+            .unwrap();
+        mock_a.wait_subscribers(1).await;
+        mock_b.wait_subscribers(1).await;
+
+        // A 先到 tx1，B 先到 tx2；两端都推送两笔
+        mock_a.send(pumpfun_trade_update(10, 0, 1));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        mock_b.send(pumpfun_trade_update(10, 0, 1));
+        mock_b.send(pumpfun_trade_update(10, 1, 2));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        mock_a.send(pumpfun_trade_update(10, 1, 2));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        handle.stop_and_join().await.unwrap();
+        let mut forwarded = Vec::new();
+        while let Ok(e) = rx.try_recv() {
+            forwarded.push(e.metadata().unwrap().tx_index);
+        }
+        assert_eq!(forwarded, vec![0, 1]);
+
+        let metrics = multi.source_metrics();
+        assert_eq!(metrics.iter().map(|m| (m.events_received, m.wins)).collect::<Vec<_>>(), vec![(2, 1), (2, 1)]);
+        assert!(metrics.iter().all(|m| m.win_rate == 0.5 && m.avg_lag_us > 0.0));
+    }
+
+    #[tokio::test]
+    async fn test_slot_filter_applies_to_all_sources() {
+        let config = ClientConfig { enable_tls: false, order_mode: OrderMode::Unordered, ..ClientConfig::default() };
+        let (mock_a, mock_b) = (MockGeyser::new(), MockGeyser::new());
+        let sources = vec![
+            YellowstoneGrpc::new_unwarmed(mock_a.serve().await, config.clone()),
+            YellowstoneGrpc::new_unwarmed(mock_b.serve().await, config.clone()),
+        ];
+        let multi = MultiSourceGrpc::from_sources(sources, config).unwrap();
+        let (mut rx, handle) = multi
+            .subscribe_dex_events_channel_with_slot_filter(
+                vec![TransactionFilter::default()], vec![], None, Some(SlotFilter::range(40, 41)),
+            )
+            .await
+            .unwrap();
+        mock_a.wait_subscribers(1).await;
+        mock_b.wait_subscribers(1).await;
+
+        for mock in [&mock_a, &mock_b] {
+            mock.send(pumpfun_trade_update(39, 0, 1));
+            mock.send(pumpfun_trade_update(40, 0, 2));
+            mock.send(pumpfun_trade_update(42, 0, 3));
+        }
+
+        // 两个端点都超过 max_slot 后合并任务结束、通道关闭
+        tokio::time::timeout(Duration::from_secs(5), handle.join()).await.unwrap().unwrap();
+        let mut slots = Vec::new();
+        while let Ok(e) = rx.try_recv() {
+            slots.push(e.metadata().unwrap().slot);
+        }
+        assert_eq!(slots, vec![40]);
+    }
+
+    #[tokio::test]
+    async fn test_abort_stops_source_subscriptions() {
+        let config = ClientConfig { enable_tls: false, order_mode: OrderMode::Unordered, ..ClientConfig::default() };
+        let (mock_a, mock_b) = (MockGeyser::new(), MockGeyser::new());
+        let sources = vec![
+            YellowstoneGrpc::new_unwarmed(mock_a.serve().await, config.clone()),
+            YellowstoneGrpc::new_unwarmed(mock_b.serve().await, config.clone()),
+        ];
+        let multi = MultiSourceGrpc::from_sources(sources, config).unwrap();
+        let (_rx, handle) = multi
+            .subscribe_dex_events_channel(vec![TransactionFilter::default()], vec![], None)
+            .await
+            .unwrap();
+        mock_a.wait_subscribers(1).await;
+        mock_b.wait_subscribers(1).await;
+
+        // 中止合并任务：端点订阅随之中止，连接断开
+        handle.abort();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while mock_a.subscriber_count() > 0 || mock_b.subscriber_count() > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    /// 断开端点并等待其以 from_slot 重连
+    async fn reconnect(mock: &MockGeyser) {
+        let n = mock.requests().len() + 1;
+        mock.disconnect_all();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while mock.requests().len() < n || mock.subscriber_count() == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    #[tokio::test]
+    async fn test_slot_gap_forwarded_unless_covered() {
+        let config = ClientConfig { enable_tls: false, order_mode: OrderMode::Unordered, ..ClientConfig::default() };
+        let (mock_a, mock_b) = (MockGeyser::new(), MockGeyser::new());
+        let sources = vec![
+            YellowstoneGrpc::new_unwarmed(mock_a.serve().await, config.clone()),
+            YellowstoneGrpc::new_unwarmed(mock_b.serve().await, config.clone()),
+        ];
+        let multi = MultiSourceGrpc::from_sources(sources, config).unwrap();
+        let (mut rx, handle) = multi
+            .subscribe_dex_events_channel(vec![TransactionFilter::default()], vec![], None)
+            .await
+            .unwrap();
+        mock_a.wait_subscribers(1).await;
+        mock_b.wait_subscribers(1).await;
+        for (slot, sig) in [(900, 1), (1010, 2)] {
+            mock_a.send(pumpfun_trade_update(slot, 0, sig));
+            mock_b.send(pumpfun_trade_update(slot, 0, sig));
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // A 的缺口 [946, 1005) 在 B 的连续接收范围内：丢弃
+        mock_a.set_first_available(Some(1005));
+        reconnect(&mock_a).await;
+        // B 的同一缺口与 A 的缺口重叠，没有端点覆盖：转发
+        mock_b.set_first_available(Some(1005));
+        reconnect(&mock_b).await;
+
+        handle.stop_and_join().await.unwrap();
+        let mut gaps = Vec::new();
+        while let Ok(e) = rx.try_recv() {
+            if let DexEvent::SlotGap(g) = e {
+                gaps.push((g.from_slot, g.to_slot));
+            }
+        }
+        assert_eq!(gaps, vec![(946, 1005)]);
+    }
+}
@@ -0,0 +1,316 @@
+//! 事件输出模块 - 订阅任务把解析出的事件写入哪里
+//!
+//! - `Queue`: 无锁 `ArrayQueue`，适合自旋轮询的极低延迟消费者
+//! - `Channel`: `tokio::sync::mpsc`，可 `.await` 接收，也可包装为 `Stream`
+//! - `Broadcast`: `tokio::sync::broadcast`，多个消费者各自收到全部事件
+//!
+//! 输出满时按 `OverflowPolicy` 处理，所有丢弃都会计入 `MetricsManager::increment_dropped_events`。
+
+use super::client::get_timestamp_us;
+use super::types::OverflowPolicy;
+use crate::common::MetricsManager;
+use crate::core::timing;
+use crate::DexEvent;
+use crossbeam_queue::ArrayQueue;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::Duration;
+
+/// 输出目标
+pub(crate) enum EventTarget {
+    Queue(Arc<ArrayQueue<DexEvent>>),
+    Channel(mpsc::Sender<DexEvent>),
+    Broadcast(broadcast::Sender<DexEvent>),
+}
+
+#[allow(clippy::large_enum_variant)]
+enum Delivery {
+    Delivered,
+    Full(DexEvent),
+    /// 消费者已全部释放
+    Closed,
+}
+
+/// 写出前记下的指标信息（事件写出后已被移走）
+#[derive(Clone, Copy)]
+struct OutputTiming {
+    kind: usize,
+    recv_us: i64,
+    block_us: i64,
+}
+
+impl OutputTiming {
+    #[inline]
+    fn of(event: &DexEvent) -> Self {
+        let (recv_us, block_us) = event.metadata().map_or((0, 0), |m| (m.grpc_recv_us, m.block_time_us));
+        Self { kind: event.kind_index(), recv_us, block_us }
+    }
+}
+
+/// 订阅任务的事件出口
+pub(crate) struct EventSink {
+    target: EventTarget,
+    policy: OverflowPolicy,
+    /// Block / Spill 模式下暂存的事件（保持顺序）
+    pending: VecDeque<DexEvent>,
+    metrics: Arc<MetricsManager>,
+}
+
+impl EventSink {
+    pub(crate) fn new(target: EventTarget, policy: OverflowPolicy, metrics: Arc<MetricsManager>) -> Self {
+        Self { target, policy, pending: VecDeque::new(), metrics }
+    }
+
+    /// 写入事件（不阻塞）
+    ///
+    /// Block 模式下写不下的事件先暂存，由订阅循环调用 `wait_pending` 等待写出。
+    #[inline]
+    pub(crate) fn push(&mut self, mut event: DexEvent) {
+        timing::mark_buffer_release(&mut event);
+        if !self.pending.is_empty() {
+            self.drain_pending();
+            if !self.pending.is_empty() {
+                self.pending.push_back(event);
+                return;
+            }
+        }
+
+        match self.deliver(event) {
+            Delivery::Delivered => {}
+            Delivery::Closed => self.drop_event(),
+            Delivery::Full(event) => match self.policy {
+                OverflowPolicy::DropNewest => self.drop_event(),
+                OverflowPolicy::DropOldest => match self.target {
+                    EventTarget::Queue(ref queue) => {
+                        if queue.force_push(event).is_some() {
+                            self.drop_event();
+                        }
+                    }
+                    // mpsc 通道不支持 DropOldest（订阅时已拒绝），broadcast 不会写满
+                    _ => self.drop_event(),
+                },
+                OverflowPolicy::Block | OverflowPolicy::Spill => self.pending.push_back(event),
+            },
+        }
+    }
+
+    /// 是否有暂存事件
+    #[inline]
+    pub(crate) fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// 订阅循环是否需要在读取下一条消息前等待暂存事件写出
+    #[inline]
+    pub(crate) fn should_block(&self) -> bool {
+        self.policy == OverflowPolicy::Block && self.has_pending()
+    }
+
+    /// 尽量写出暂存事件（不阻塞）
+    pub(crate) fn drain_pending(&mut self) {
+        while let Some(event) = self.pending.pop_front() {
+            match self.deliver(event) {
+                Delivery::Delivered => {}
+                Delivery::Closed => self.drop_event(),
+                Delivery::Full(event) => {
+                    self.pending.push_front(event);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// 等待暂存事件全部写出（Block 模式的反压点）
+    pub(crate) async fn wait_pending(&mut self) {
+        while !self.pending.is_empty() {
+            if let EventTarget::Channel(ref tx) = self.target {
+                let tx = tx.clone();
+                while let Some(mut event) = self.pending.pop_front() {
+                    timing::mark_queue_push(&mut event);
+                    let output = OutputTiming::of(&event);
+                    if tx.send(event).await.is_err() {
+                        self.drop_event();
+                    } else {
+                        self.record_output(output);
+                    }
+                }
+                return;
+            }
+            self.drain_pending();
+            if !self.pending.is_empty() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }
+    }
+
+    /// 订阅结束：最后尝试写出暂存事件，仍写不下的计为丢弃
+    pub(crate) fn finish(&mut self) {
+        self.drain_pending();
+        let remaining = self.pending.len() as u64;
+        self.pending.clear();
+        self.metrics.increment_dropped_events_by(remaining);
+    }
+
+    /// 写出并记录输出指标
+    #[inline]
+    fn deliver(&self, mut event: DexEvent) -> Delivery {
+        timing::mark_queue_push(&mut event);
+        if !self.metrics.is_enabled() {
+            return self.try_deliver(event);
+        }
+        let output = OutputTiming::of(&event);
+        let delivery = self.try_deliver(event);
+        if let Delivery::Delivered = delivery {
+            self.record_output(output);
+        }
+        delivery
+    }
+
+    #[inline]
+    fn record_output(&self, output: OutputTiming) {
+        let OutputTiming { kind, recv_us, block_us } = output;
+        let latency_us = (recv_us > 0).then(|| (get_timestamp_us() - recv_us).max(0) as f64);
+        let end_to_end_us = (recv_us > 0 && block_us > 0).then(|| (recv_us - block_us).max(0) as f64);
+        self.metrics.record_output(kind, latency_us, end_to_end_us, self.depth());
+    }
+
+    /// 输出积压：目标中未被消费的事件 + 暂存事件
+    fn depth(&self) -> usize {
+        let queued = match self.target {
+            EventTarget::Queue(ref queue) => queue.len(),
+            EventTarget::Channel(ref tx) => tx.max_capacity() - tx.capacity(),
+            EventTarget::Broadcast(ref tx) => tx.len(),
+        };
+        queued + self.pending.len()
+    }
+
+    #[inline]
+    fn try_deliver(&self, event: DexEvent) -> Delivery {
+        match self.target {
+            EventTarget::Queue(ref queue) => {
+                // 只剩订阅任务持有队列，说明消费者已释放
+                if Arc::strong_count(queue) == 1 {
+                    return Delivery::Closed;
+                }
+                match queue.push(event) {
+                    Ok(()) => Delivery::Delivered,
+                    Err(event) => Delivery::Full(event),
+                }
+            }
+            EventTarget::Channel(ref tx) => match tx.try_send(event) {
+                Ok(()) => Delivery::Delivered,
+                Err(mpsc::error::TrySendError::Full(event)) => Delivery::Full(event),
+                Err(mpsc::error::TrySendError::Closed(_)) => Delivery::Closed,
+            },
+            // broadcast 不会写满：落后的接收端自行收到 Lagged
+            EventTarget::Broadcast(ref tx) => match tx.send(event) {
+                Ok(_) => Delivery::Delivered,
+                Err(_) => Delivery::Closed,
+            },
+        }
+    }
+
+    #[inline]
+    fn drop_event(&self) {
+        self.metrics.increment_dropped_events();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::PumpFunTradeEvent;
+
+    fn event(tx_index: u64) -> DexEvent {
+        let mut e = PumpFunTradeEvent::default();
+        e.metadata.tx_index = tx_index;
+        DexEvent::PumpFunTrade(e)
+    }
+
+    fn index_of(e: DexEvent) -> u64 {
+        match e {
+            DexEvent::PumpFunTrade(t) => t.metadata.tx_index,
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    fn queue_sink(capacity: usize, policy: OverflowPolicy) -> (Arc<ArrayQueue<DexEvent>>, EventSink, Arc<MetricsManager>) {
+        let queue = Arc::new(ArrayQueue::new(capacity));
+        let metrics = Arc::new(MetricsManager::new(true, "test".to_string()));
+        let sink = EventSink::new(EventTarget::Queue(Arc::clone(&queue)), policy, Arc::clone(&metrics));
+        (queue, sink, metrics)
+    }
+
+    fn drain(queue: &ArrayQueue<DexEvent>) -> Vec<u64> {
+        std::iter::from_fn(|| queue.pop()).map(index_of).collect()
+    }
+
+    #[tokio::test]
+    async fn test_drop_newest_and_drop_oldest() {
+        let (queue, mut sink, metrics) = queue_sink(2, OverflowPolicy::DropNewest);
+        (0..3).for_each(|i| sink.push(event(i)));
+        assert_eq!(drain(&queue), vec![0, 1]);
+        assert_eq!(metrics.get_dropped_events_count(), 1);
+
+        let (queue, mut sink, metrics) = queue_sink(2, OverflowPolicy::DropOldest);
+        (0..3).for_each(|i| sink.push(event(i)));
+        assert_eq!(drain(&queue), vec![1, 2]);
+        assert_eq!(metrics.get_dropped_events_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_spill_preserves_order() {
+        let (queue, mut sink, metrics) = queue_sink(2, OverflowPolicy::Spill);
+        (0..4).for_each(|i| sink.push(event(i)));
+        assert!(sink.has_pending());
+        assert!(!sink.should_block());
+
+        assert_eq!(index_of(queue.pop().unwrap()), 0);
+        sink.drain_pending();
+        sink.push(event(4));
+        assert_eq!(drain(&queue), vec![1, 2]);
+        sink.drain_pending();
+        assert_eq!(drain(&queue), vec![3, 4]);
+        assert!(!sink.has_pending());
+        assert_eq!(metrics.get_dropped_events_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_block_waits_for_consumer() {
+        let metrics = Arc::new(MetricsManager::new(true, "test".to_string()));
+        let (tx, mut rx) = mpsc::channel(1);
+        let mut sink = EventSink::new(EventTarget::Channel(tx), OverflowPolicy::Block, Arc::clone(&metrics));
+        (0..3).for_each(|i| sink.push(event(i)));
+        assert!(sink.should_block());
+
+        let consumer = tokio::spawn(async move {
+            let mut got = Vec::new();
+            while let Some(e) = rx.recv().await {
+                got.push(index_of(e));
+            }
+            got
+        });
+        tokio::time::timeout(Duration::from_secs(5), sink.wait_pending()).await.unwrap();
+        assert!(!sink.has_pending());
+        drop(sink);
+        assert_eq!(consumer.await.unwrap(), vec![0, 1, 2]);
+        assert_eq!(metrics.get_dropped_events_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_closed_consumer_counts_drops() {
+        let (queue, mut sink, metrics) = queue_sink(4, OverflowPolicy::Block);
+        drop(queue);
+        sink.push(event(0));
+        sink.push(event(1));
+        assert!(!sink.has_pending());
+        assert_eq!(metrics.get_dropped_events_count(), 2);
+
+        let (queue, mut sink, metrics) = queue_sink(1, OverflowPolicy::Spill);
+        (0..3).for_each(|i| sink.push(event(i)));
+        sink.finish();
+        assert_eq!(drain(&queue), vec![0]);
+        assert_eq!(metrics.get_dropped_events_count(), 2);
+    }
+}
@@ -1,24 +1,58 @@
+//! 程序 ID 注册表 - 所有解析器、过滤器和 `TransactionFilter::for_protocols` 的唯一来源
+//!
+//! - 下面的常量为 mainnet 地址，`instr` / `accounts` / `logs` 中的同名常量均从这里导出
+//! - 运行时使用的地址由当前注册表决定：`use_cluster` 切换 mainnet / devnet，
+//!   `set_program_ids` / `add_program_id` 覆盖或追加地址（devnet 分叉、本地验证节点）
+//! - 热路径通过线程本地缓存读取注册表，只有注册表变化后的第一次读取需要加锁
+
+use crate::grpc::types::Protocol;
+use memchr::memmem;
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+// ====== mainnet 程序 ID ======
 
-// Program IDs for supported DEX protocols
 pub const PUMPFUN_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
 pub const PUMPSWAP_PROGRAM_ID: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
 pub const PUMPSWAP_FEES_PROGRAM_ID: &str = "pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ";
-pub const BONK_PROGRAM_ID: &str = "BSwp6bEBihVLdqJRKS58NaebUBSDNjN7MdpFwNaR6gn3";
+/// Raydium LaunchLab（Bonk）
+pub const BONK_PROGRAM_ID: &str = "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj";
 pub const RAYDIUM_CPMM_PROGRAM_ID: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
-pub const RAYDIUM_CLMM_PROGRAM_ID: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUQtcaMpgYqJPXBDvfE";
+pub const RAYDIUM_CLMM_PROGRAM_ID: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
 pub const RAYDIUM_AMM_V4_PROGRAM_ID: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+pub const ORCA_WHIRLPOOL_PROGRAM_ID: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+pub const METEORA_POOLS_PROGRAM_ID: &str = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB";
 pub const METEORA_DAMM_V2_PROGRAM_ID: &str = "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG";
+pub const METEORA_DLMM_PROGRAM_ID: &str = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
 
 pub const PUMPFUN_PROGRAM: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
 pub const PUMPSWAP_PROGRAM: Pubkey = pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+pub const PUMPSWAP_FEES_PROGRAM: Pubkey = pubkey!("pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ");
+pub const BONK_PROGRAM: Pubkey = pubkey!("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj");
+pub const RAYDIUM_CPMM_PROGRAM: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+pub const RAYDIUM_CLMM_PROGRAM: Pubkey = pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+pub const RAYDIUM_AMM_V4_PROGRAM: Pubkey = pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+pub const ORCA_WHIRLPOOL_PROGRAM: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+pub const METEORA_POOLS_PROGRAM: Pubkey = pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
 pub const METEORA_DAMM_V2_PROGRAM: Pubkey = pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+pub const METEORA_DLMM_PROGRAM: Pubkey = pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
 
-use crate::grpc::types::Protocol;
-use solana_sdk::pubkey::Pubkey;
-use std::collections::HashMap;
+// ====== devnet 程序 ID（与 mainnet 不同的部分） ======
+
+pub const DEVNET_BONK_PROGRAM_ID: &str = "DRay6fNdQ5J82H7xV6uq2aV3mNrUZ1J4PgSKsWgptcm6";
+pub const DEVNET_RAYDIUM_CPMM_PROGRAM_ID: &str = "CPMDWBwJDtYax9qKcQP3CtKz7tHjJsN3H8hGrYVD9mZD";
+pub const DEVNET_RAYDIUM_CLMM_PROGRAM_ID: &str = "devi51mZmdwUJGU9hjN27vEz64Gps7uUefqxg27EAtH";
+pub const DEVNET_RAYDIUM_AMM_V4_PROGRAM_ID: &str = "HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8";
 
 lazy_static::lazy_static! {
+    /// mainnet 各协议的程序 ID
     pub static ref PROTOCOL_PROGRAM_IDS: HashMap<Protocol, Vec<&'static str>> = {
         let mut map = HashMap::new();
         map.insert(Protocol::PumpFun, vec![PUMPFUN_PROGRAM_ID]);
@@ -28,21 +62,264 @@ lazy_static::lazy_static! {
         map.insert(Protocol::RaydiumClmm, vec![RAYDIUM_CLMM_PROGRAM_ID]);
         map.insert(Protocol::RaydiumAmmV4, vec![RAYDIUM_AMM_V4_PROGRAM_ID]);
         map.insert(Protocol::MeteoraDammV2, vec![METEORA_DAMM_V2_PROGRAM_ID]);
-        // 移除不存在的协议，只保留有实际常量的协议
+        map.insert(Protocol::OrcaWhirlpool, vec![ORCA_WHIRLPOOL_PROGRAM_ID]);
+        map.insert(Protocol::MeteoraPools, vec![METEORA_POOLS_PROGRAM_ID]);
+        map.insert(Protocol::MeteoraDlmm, vec![METEORA_DLMM_PROGRAM_ID]);
+        map
+    };
+
+    /// devnet 各协议的程序 ID（Pump、Orca、Meteora 与 mainnet 相同）
+    pub static ref DEVNET_PROTOCOL_PROGRAM_IDS: HashMap<Protocol, Vec<&'static str>> = {
+        let mut map = PROTOCOL_PROGRAM_IDS.clone();
+        map.insert(Protocol::Bonk, vec![DEVNET_BONK_PROGRAM_ID]);
+        map.insert(Protocol::RaydiumCpmm, vec![DEVNET_RAYDIUM_CPMM_PROGRAM_ID]);
+        map.insert(Protocol::RaydiumClmm, vec![DEVNET_RAYDIUM_CLMM_PROGRAM_ID]);
+        map.insert(Protocol::RaydiumAmmV4, vec![DEVNET_RAYDIUM_AMM_V4_PROGRAM_ID]);
         map
     };
 }
 
-pub fn get_program_ids_for_protocols(protocols: &[Protocol]) -> Vec<String> {
-    let mut program_ids = Vec::new();
-    for protocol in protocols {
-        if let Some(ids) = PROTOCOL_PROGRAM_IDS.get(protocol) {
-            for id in ids {
-                program_ids.push(id.to_string());
-            }
+/// 集群配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Cluster {
+    #[default]
+    Mainnet,
+    Devnet,
+}
+
+// ====== 注册表 ======
+
+/// 协议 → 程序 ID 映射，一个协议可以对应多个地址
+#[derive(Debug, Clone)]
+pub struct ProgramIdRegistry {
+    cluster: Cluster,
+    ids: HashMap<Protocol, Vec<Pubkey>>,
+    /// 反向索引，按程序 ID 查协议（线性扫描，条目很少）
+    lookup: Vec<(Pubkey, Protocol)>,
+    /// base58 形式，用于 invoke 位置查找
+    strings: Vec<(String, Protocol)>,
+    /// 预编译的 SIMD 查找器，用于日志匹配
+    finders: Vec<(memmem::Finder<'static>, Protocol)>,
+}
+
+impl ProgramIdRegistry {
+    /// 指定集群的默认地址
+    pub fn for_cluster(cluster: Cluster) -> Self {
+        let table = match cluster {
+            Cluster::Mainnet => &*PROTOCOL_PROGRAM_IDS,
+            Cluster::Devnet => &*DEVNET_PROTOCOL_PROGRAM_IDS,
+        };
+        let ids = table
+            .iter()
+            .map(|(protocol, ids)| (*protocol, ids.iter().map(|id| id.parse().expect("valid program id")).collect()))
+            .collect();
+        let mut registry =
+            Self { cluster, ids, lookup: Vec::new(), strings: Vec::new(), finders: Vec::new() };
+        registry.reindex();
+        registry
+    }
+
+    #[inline]
+    pub fn cluster(&self) -> Cluster {
+        self.cluster
+    }
+
+    /// 替换协议的程序 ID
+    pub fn set(&mut self, protocol: Protocol, ids: Vec<Pubkey>) {
+        self.ids.insert(protocol, ids);
+        self.reindex();
+    }
+
+    /// 为协议追加一个程序 ID（例如本地部署的同一程序）
+    pub fn add(&mut self, protocol: Protocol, id: Pubkey) {
+        let ids = self.ids.entry(protocol).or_default();
+        if !ids.contains(&id) {
+            ids.push(id);
+            self.reindex();
         }
     }
+
+    /// 协议的程序 ID
+    #[inline]
+    pub fn program_ids(&self, protocol: Protocol) -> &[Pubkey] {
+        self.ids.get(&protocol).map_or(&[], Vec::as_slice)
+    }
+
+    /// 程序 ID 所属的协议
+    #[inline]
+    pub fn protocol_of(&self, program_id: &Pubkey) -> Option<Protocol> {
+        self.lookup.iter().find(|(id, _)| id == program_id).map(|(_, protocol)| *protocol)
+    }
+
+    /// base58 程序 ID 所属的协议
+    #[inline]
+    pub fn protocol_of_str(&self, program_id: &str) -> Option<Protocol> {
+        self.strings.iter().find(|(id, _)| id == program_id).map(|(_, protocol)| *protocol)
+    }
+
+    /// 协议的 base58 程序 ID
+    pub fn program_id_strs(&self, protocol: Protocol) -> impl Iterator<Item = &str> {
+        self.strings.iter().filter(move |(_, p)| *p == protocol).map(|(id, _)| id.as_str())
+    }
+
+    /// 日志中出现的第一个已注册程序 ID 所属的协议
+    pub fn find_in_log(&self, log: &[u8]) -> Option<Protocol> {
+        self.finders.iter().find(|(finder, _)| finder.find(log).is_some()).map(|(_, protocol)| *protocol)
+    }
+
+    fn reindex(&mut self) {
+        let mut protocols: Vec<_> = self.ids.keys().copied().collect();
+        protocols.sort_by_key(|p| *p as u8);
+        self.lookup = protocols
+            .iter()
+            .flat_map(|p| self.ids[p].iter().map(move |id| (*id, *p)))
+            .collect();
+        self.strings = self.lookup.iter().map(|(id, p)| (id.to_string(), *p)).collect();
+        self.finders = self
+            .strings
+            .iter()
+            .map(|(id, p)| (memmem::Finder::new(id.as_bytes()).into_owned(), *p))
+            .collect();
+    }
+}
+
+impl Default for ProgramIdRegistry {
+    fn default() -> Self {
+        Self::for_cluster(Cluster::Mainnet)
+    }
+}
+
+static CURRENT: Lazy<RwLock<Arc<ProgramIdRegistry>>> = Lazy::new(|| RwLock::new(Arc::new(ProgramIdRegistry::default())));
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static CACHED: RefCell<Option<(u64, Arc<ProgramIdRegistry>)>> = const { RefCell::new(None) };
+}
+
+/// 读取当前注册表
+#[inline]
+pub fn with_registry<R>(f: impl FnOnce(&ProgramIdRegistry) -> R) -> R {
+    let generation = GENERATION.load(Ordering::Acquire);
+    CACHED.with(|cached| {
+        let stale = !matches!(&*cached.borrow(), Some((g, _)) if *g == generation);
+        if stale {
+            // 嵌套调用中无法刷新时沿用旧值，下次读取再刷新
+            if let Ok(mut slot) = cached.try_borrow_mut() {
+                *slot = Some((generation, Arc::clone(&CURRENT.read())));
+            }
+        }
+        match &*cached.borrow() {
+            Some((_, registry)) => f(registry),
+            None => f(&CURRENT.read()),
+        }
+    })
+}
+
+fn update(f: impl FnOnce(&mut ProgramIdRegistry)) {
+    let mut current = CURRENT.write();
+    let mut next = ProgramIdRegistry::clone(&current);
+    f(&mut next);
+    *current = Arc::new(next);
+    GENERATION.fetch_add(1, Ordering::Release);
+}
+
+/// 切换到指定集群的默认地址（清除此前的覆盖）
+pub fn use_cluster(cluster: Cluster) {
+    update(|registry| *registry = ProgramIdRegistry::for_cluster(cluster));
+}
+
+/// 覆盖协议的程序 ID
+pub fn set_program_ids(protocol: Protocol, ids: Vec<Pubkey>) {
+    update(|registry| registry.set(protocol, ids));
+}
+
+/// 为协议追加程序 ID
+pub fn add_program_id(protocol: Protocol, id: Pubkey) {
+    update(|registry| registry.add(protocol, id));
+}
+
+/// 程序 ID 所属的协议（当前注册表）
+#[inline]
+pub fn protocol_of(program_id: &Pubkey) -> Option<Protocol> {
+    with_registry(|registry| registry.protocol_of(program_id))
+}
+
+/// 协议的程序 ID（当前注册表）
+pub fn program_ids(protocol: Protocol) -> Vec<Pubkey> {
+    with_registry(|registry| registry.program_ids(protocol).to_vec())
+}
+
+/// 日志是否为该协议程序的 invoke / success 行
+pub fn is_program_log(log: &str, protocol: Protocol) -> bool {
+    with_registry(|registry| {
+        registry.program_id_strs(protocol).any(|id| {
+            log.strip_prefix("Program ")
+                .and_then(|rest| rest.strip_prefix(id))
+                .is_some_and(|rest| rest.starts_with(" invoke") || rest.starts_with(" success"))
+        })
+    })
+}
+
+/// 协议程序在 invokes（base58 程序 ID → 调用位置）中最后一次调用的位置
+#[inline]
+pub(crate) fn last_invoke<'a>(invokes: &'a HashMap<&str, Vec<(i32, i32)>>, protocol: Protocol) -> Option<&'a (i32, i32)> {
+    with_registry(|registry| registry.program_id_strs(protocol).find_map(|id| invokes.get(id)?.last()))
+}
+
+/// 同 [`last_invoke`]，invokes 以 Pubkey 为键
+#[inline]
+pub(crate) fn last_invoke_by_key(invokes: &HashMap<Pubkey, Vec<(i32, i32)>>, protocol: Protocol) -> Option<&(i32, i32)> {
+    with_registry(|registry| registry.program_ids(protocol).iter().find_map(|id| invokes.get(id)?.last()))
+}
+
+pub fn get_program_ids_for_protocols(protocols: &[Protocol]) -> Vec<String> {
+    let mut program_ids: Vec<String> = with_registry(|registry| {
+        protocols.iter().flat_map(|p| registry.program_id_strs(*p).map(str::to_string)).collect()
+    });
     program_ids.sort();
     program_ids.dedup();
     program_ids
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster_profiles_and_overrides() {
+        let mainnet = ProgramIdRegistry::for_cluster(Cluster::Mainnet);
+        assert_eq!(mainnet.protocol_of(&RAYDIUM_CLMM_PROGRAM), Some(Protocol::RaydiumClmm));
+        assert_eq!(mainnet.protocol_of(&BONK_PROGRAM), Some(Protocol::Bonk));
+        assert_eq!(mainnet.protocol_of(&ORCA_WHIRLPOOL_PROGRAM), Some(Protocol::OrcaWhirlpool));
+        assert_eq!(mainnet.protocol_of(&METEORA_DLMM_PROGRAM), Some(Protocol::MeteoraDlmm));
+        assert_eq!(
+            get_program_ids_for_protocols(&[Protocol::MeteoraPools]),
+            vec![METEORA_POOLS_PROGRAM_ID.to_string()]
+        );
+        // mainnet 常量与各模块导出的常量一致
+        assert_eq!(crate::instr::program_ids::RAYDIUM_CLMM_PROGRAM_ID, RAYDIUM_CLMM_PROGRAM);
+        assert_eq!(crate::instr::program_ids::BONK_PROGRAM_ID, BONK_PROGRAM);
+        assert_eq!(crate::accounts::program_ids::PUMPSWAP_PROGRAM_ID, PUMPSWAP_PROGRAM);
+        assert_eq!(PUMPFUN_PROGRAM.to_string(), PUMPFUN_PROGRAM_ID);
+
+        let mut devnet = ProgramIdRegistry::for_cluster(Cluster::Devnet);
+        assert_eq!(devnet.program_id_strs(Protocol::RaydiumCpmm).collect::<Vec<_>>(), vec![DEVNET_RAYDIUM_CPMM_PROGRAM_ID]);
+        assert_eq!(devnet.protocol_of(&RAYDIUM_CPMM_PROGRAM), None);
+        assert_eq!(devnet.protocol_of(&PUMPFUN_PROGRAM), Some(Protocol::PumpFun));
+
+        let local = Pubkey::new_unique();
+        devnet.add(Protocol::PumpFun, local);
+        assert_eq!(devnet.program_ids(Protocol::PumpFun), &[PUMPFUN_PROGRAM, local]);
+        devnet.set(Protocol::PumpFun, vec![local]);
+        assert_eq!(devnet.protocol_of(&PUMPFUN_PROGRAM), None);
+        assert_eq!(devnet.protocol_of_str(&local.to_string()), Some(Protocol::PumpFun));
+
+        // 全局注册表：追加地址后解析和过滤器都使用新地址
+        let fork = Pubkey::new_unique();
+        add_program_id(Protocol::RaydiumAmmV4, fork);
+        assert_eq!(protocol_of(&fork), Some(Protocol::RaydiumAmmV4));
+        assert!(get_program_ids_for_protocols(&[Protocol::RaydiumAmmV4]).contains(&fork.to_string()));
+        assert!(is_program_log(&format!("Program {} invoke [1]", fork), Protocol::RaydiumAmmV4));
+        assert!(!is_program_log(&format!("Program {} invoke [1]", fork), Protocol::PumpFun));
+    }
+}
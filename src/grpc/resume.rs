@@ -0,0 +1,143 @@
+//! 断线续传模块 - 重连时通过 `from_slot` 回放断线期间的交易
+//!
+//! - 记录已输出事件的最大 slot，重连时从其前 `DEDUP_WINDOW_SLOTS` 个 slot 开始回放，
+//!   补回乱序晚到、断线时尚未输出的较早 slot 交易
+//! - 回放区间内已输出过的交易按签名去重
+//! - 服务端无法回放到该 slot 时，输出 `DexEvent::SlotGap` 报告缺口
+
+use crate::core::{EventMetadata, SlotGapEvent};
+use crate::DexEvent;
+use solana_sdk::signature::Signature;
+use std::collections::{BTreeMap, HashSet};
+
+/// 保留签名的 slot 数量，也是重连回放的回看距离（processed 级别下不同 slot 的交易可能交错到达）
+const DEDUP_WINDOW_SLOTS: u64 = 64;
+
+/// 续传状态，跨重连保留
+#[derive(Default)]
+pub(crate) struct ResumeTracker {
+    /// 已输出事件的最大 slot
+    last_slot: Option<u64>,
+    /// 最近 slot 已输出事件的交易签名
+    delivered: BTreeMap<u64, HashSet<Signature>>,
+    /// 回放阶段：slot <= 该值的交易需要去重
+    replay_until: Option<u64>,
+    /// 缺口起点和原因，结束 slot 待新连接的第一条更新确定
+    pending_gap: Option<(u64, String)>,
+}
+
+impl ResumeTracker {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// 重连时的 from_slot；回放失败后的下一次连接从最新位置开始
+    #[inline]
+    pub(crate) fn resume_slot(&self) -> Option<u64> {
+        if self.pending_gap.is_some() {
+            return None;
+        }
+        self.replay_from()
+    }
+
+    /// 回放起点：最大 slot 往前一个去重窗口，窗口内已输出的交易都能按签名去重
+    #[inline]
+    fn replay_from(&self) -> Option<u64> {
+        self.last_slot.map(|slot| slot.saturating_sub(DEDUP_WINDOW_SLOTS))
+    }
+
+    /// 以 from_slot 重连：此前输出过的交易进入去重
+    pub(crate) fn begin_replay(&mut self) {
+        self.replay_until = self.last_slot;
+    }
+
+    /// 服务端只能从 first_available 开始回放，返回 [from_slot, first_available) 的缺口事件
+    pub(crate) fn replay_truncated(&mut self, first_available: u64, grpc_us: i64) -> Option<DexEvent> {
+        let from_slot = self.replay_from().filter(|s| *s < first_available)?;
+        Some(gap_event(from_slot, first_available, "replay window starts later".to_string(), grpc_us))
+    }
+
+    /// 服务端拒绝 from_slot：下一次连接不带 from_slot，收到第一条更新时输出缺口
+    pub(crate) fn replay_unavailable(&mut self, reason: String) {
+        if let Some(from_slot) = self.replay_from() {
+            self.pending_gap = Some((from_slot, reason));
+        }
+        self.replay_until = None;
+    }
+
+    /// 新连接收到第一条更新时调用，输出待报告的缺口
+    #[inline]
+    pub(crate) fn take_gap(&mut self, slot: u64, grpc_us: i64) -> Option<DexEvent> {
+        let (from_slot, reason) = self.pending_gap.take()?;
+        Some(gap_event(from_slot, slot, reason, grpc_us))
+    }
+
+    /// 回放中已输出过的交易
+    #[inline]
+    pub(crate) fn is_duplicate(&self, slot: u64, signature: &Signature) -> bool {
+        match self.replay_until {
+            Some(until) if slot <= until => {
+                self.delivered.get(&slot).is_some_and(|sigs| sigs.contains(signature))
+            }
+            _ => false,
+        }
+    }
+
+    /// 记录已输出事件的交易
+    #[inline]
+    pub(crate) fn record(&mut self, slot: u64, signature: Signature) {
+        self.delivered.entry(slot).or_default().insert(signature);
+        if self.last_slot.is_none_or(|last| slot > last) {
+            self.last_slot = Some(slot);
+            let keep_from = slot.saturating_sub(DEDUP_WINDOW_SLOTS);
+            self.delivered = self.delivered.split_off(&keep_from);
+        }
+    }
+}
+
+fn gap_event(from_slot: u64, to_slot: u64, reason: String, grpc_us: i64) -> DexEvent {
+    DexEvent::SlotGap(SlotGapEvent {
+        metadata: EventMetadata { slot: to_slot, grpc_recv_us: grpc_us, ..Default::default() },
+        from_slot,
+        to_slot,
+        reason,
+    })
+}
+
+/// 订阅错误是否由 from_slot 无法回放引起
+pub(crate) fn is_replay_error(message: &str) -> bool {
+    message.contains("from_slot") || message.contains("replay")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sig(n: u8) -> Signature {
+        Signature::from([n; 64])
+    }
+
+    #[test]
+    fn test_resume_covers_out_of_order_slots() {
+        let mut tracker = ResumeTracker::new();
+        assert_eq!(tracker.resume_slot(), None);
+
+        // processed 级别下 slot 1001 的交易晚于 1002 到达
+        tracker.record(1000, sig(1));
+        tracker.record(1002, sig(2));
+        tracker.record(1001, sig(3));
+        assert_eq!(tracker.resume_slot(), Some(1002 - DEDUP_WINDOW_SLOTS));
+
+        // 回放区间内已输出的交易去重，断线时尚未输出的交易照常输出
+        tracker.begin_replay();
+        assert!(tracker.is_duplicate(1000, &sig(1)));
+        assert!(tracker.is_duplicate(1001, &sig(3)));
+        assert!(!tracker.is_duplicate(1001, &sig(4)));
+        assert!(!tracker.is_duplicate(1003, &sig(2)));
+
+        // 超出窗口的旧签名被清理，回放起点随最大 slot 前移
+        tracker.record(2000, sig(5));
+        assert_eq!(tracker.resume_slot(), Some(2000 - DEDUP_WINDOW_SLOTS));
+        assert!(tracker.delivered.keys().all(|slot| *slot >= 2000 - DEDUP_WINDOW_SLOTS));
+    }
+}
@@ -1,6 +1,8 @@
+use super::error::GrpcError;
+use crate::core::events::DexEvent;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use yellowstone_grpc_proto::geyser::{
+    CommitmentLevel,
     subscribe_request_filter_accounts_filter::Filter,
     subscribe_request_filter_accounts_filter_memcmp::Data, SubscribeRequestFilterAccountsFilter,
     SubscribeRequestFilterAccountsFilterMemcmp,
@@ -26,22 +28,82 @@ pub enum OrderMode {
     MicroBatch,
 }
 
+/// 订阅确认级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Commitment {
+    /// 最低延迟，可能被回滚（默认）
+    #[default]
+    Processed,
+    /// 超级多数投票确认
+    Confirmed,
+    /// 最终确认，不会回滚
+    Finalized,
+}
+
+impl From<Commitment> for CommitmentLevel {
+    fn from(commitment: Commitment) -> Self {
+        match commitment {
+            Commitment::Processed => CommitmentLevel::Processed,
+            Commitment::Confirmed => CommitmentLevel::Confirmed,
+            Commitment::Finalized => CommitmentLevel::Finalized,
+        }
+    }
+}
+
+/// Processed 级别下对分叉（dead / 被放弃的 slot）事件的处理策略
+///
+/// 非 Emit 策略会自动订阅 slot 状态；commitment 为 Confirmed / Finalized 时不生效。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ForkPolicy {
+    /// 收到即输出，不处理回滚（默认）
+    #[default]
+    Emit,
+    /// 暂扣交易事件，slot confirmed 后按 tx_index 顺序输出；dead 或被放弃的 slot 直接丢弃
+    Withhold,
+    /// 照常输出；slot dead 时输出 `DexEvent::SlotDead`，携带该 slot 已输出事件的签名
+    Retract,
+}
+
+/// 事件输出写满时的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OverflowPolicy {
+    /// 丢弃新事件（默认）
+    #[default]
+    DropNewest,
+    /// 挤出最旧的事件，保留新事件（仅队列输出；mpsc 通道 / Stream 输出无法挤出，订阅时返回配置错误）
+    DropOldest,
+    /// 暂停读取订阅流直到消费者腾出空间，通过 gRPC 流控反压到服务端
+    Block,
+    /// 溢出到无界缓冲，空间释放后按顺序补发
+    Spill,
+}
+
+/// 客户端配置；反序列化时缺省字段取 `Default` 的值
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ClientConfig {
     /// 是否启用性能监控
     pub enable_metrics: bool,
-    /// 连接超时时间（毫秒）
+    /// 连接超时时间（毫秒），0 表示不限
     pub connection_timeout_ms: u64,
-    /// 请求超时时间（毫秒）
+    /// 请求超时时间（毫秒），只约束订阅建立和 unary 请求，不约束已建立的流；0 表示不限
     pub request_timeout_ms: u64,
     /// 是否启用TLS
     pub enable_tls: bool,
+    /// 连续失败后的最大重试次数，超过后订阅停止；0 表示无限重试
     pub max_retries: u32,
+    /// 首次重试等待时间（毫秒），之后每次翻倍，最长 60s
     pub retry_delay_ms: u64,
+    /// 该客户端同时活跃的订阅数上限
     pub max_concurrent_streams: u32,
+    /// HTTP/2 keep-alive 间隔（毫秒）；流空闲超过该时间时发送 Yellowstone ping。0 表示关闭
     pub keep_alive_interval_ms: u64,
+    /// keep-alive / ping 无响应的超时时间（毫秒），超时视为连接断开
     pub keep_alive_timeout_ms: u64,
+    /// 客户端请求缓冲大小
     pub buffer_size: usize,
+    /// 单条消息最大解码长度（字节）
+    pub max_decoding_message_size: usize,
     /// 事件输出顺序模式
     pub order_mode: OrderMode,
     /// 有序模式下，slot 超时时间（毫秒）
@@ -50,8 +112,29 @@ pub struct ClientConfig {
     /// MicroBatch 模式下的时间窗口大小（微秒）
     /// 默认 100μs，可根据网络状况调整
     pub micro_batch_us: u64,
+    /// 事件输出容量（ArrayQueue / mpsc / broadcast）
+    pub queue_capacity: usize,
+    /// 输出写满时的处理策略，丢弃的事件计入 metrics 的 dropped_events
+    pub overflow_policy: OverflowPolicy,
+    /// 订阅确认级别
+    pub commitment: Commitment,
+    /// 订阅 slot 状态，输出 SlotConfirmed / SlotFinalized / SlotDead 事件
+    pub slot_status: bool,
+    /// 分叉事件处理策略（仅 Processed 生效）
+    pub fork_policy: ForkPolicy,
+    /// 断线重连时从最后处理的 slot 回放（from_slot），已输出的交易按签名去重
+    pub resume_from_slot: bool,
+    /// 订阅区块元数据，输出 BlockMeta 事件
+    pub block_meta: bool,
+    /// 以完整区块订阅交易（替代交易订阅）：事件使用链上出块时间，区块结束后才输出
+    ///
+    /// 区块过滤只支持 account_include（取所有 TransactionFilter 的并集）。
+    pub blocks: bool,
 }
 
+/// 重试等待时间上限（毫秒）
+pub const MAX_RETRY_DELAY_MS: u64 = 60_000;
+
 impl Default for ClientConfig {
     fn default() -> Self {
         Self {
@@ -59,35 +142,106 @@ impl Default for ClientConfig {
             connection_timeout_ms: 8000,
             request_timeout_ms: 15000,
             enable_tls: true,
-            max_retries: 3,
+            max_retries: 0,
             retry_delay_ms: 1000,
             max_concurrent_streams: 100,
             keep_alive_interval_ms: 30000,
             keep_alive_timeout_ms: 5000,
             buffer_size: 8192,
+            max_decoding_message_size: 1024 * 1024 * 1024,
             order_mode: OrderMode::Unordered,
             order_timeout_ms: 100,
             micro_batch_us: 100, // 100μs 默认窗口
+            queue_capacity: 100_000,
+            overflow_policy: OverflowPolicy::DropNewest,
+            commitment: Commitment::Processed,
+            slot_status: false,
+            fork_policy: ForkPolicy::Emit,
+            resume_from_slot: true,
+            block_meta: false,
+            blocks: false,
         }
     }
 }
 
 impl ClientConfig {
+    /// 实际生效的分叉策略（非 Processed 级别不会收到分叉事件）
+    #[inline]
+    pub fn effective_fork_policy(&self) -> ForkPolicy {
+        match self.commitment {
+            Commitment::Processed => self.fork_policy,
+            _ => ForkPolicy::Emit,
+        }
+    }
+
+    /// 是否需要订阅 slot 状态
+    #[inline]
+    pub fn subscribes_slots(&self) -> bool {
+        self.slot_status || self.effective_fork_policy() != ForkPolicy::Emit
+    }
+
+    /// 检查配置，拒绝无效值和互相矛盾的组合
+    pub fn validate(&self) -> Result<(), GrpcError> {
+        let invalid = |msg: &str| Err(GrpcError::Config(msg.to_string()));
+        if self.retry_delay_ms == 0 || self.retry_delay_ms > MAX_RETRY_DELAY_MS {
+            return invalid("retry_delay_ms must be between 1 and 60000");
+        }
+        if self.max_concurrent_streams == 0 {
+            return invalid("max_concurrent_streams must be greater than 0");
+        }
+        if self.buffer_size == 0 || self.max_decoding_message_size == 0 || self.queue_capacity == 0 {
+            return invalid("buffer_size, max_decoding_message_size and queue_capacity must be greater than 0");
+        }
+        if self.keep_alive_interval_ms > 0
+            && (self.keep_alive_timeout_ms == 0 || self.keep_alive_timeout_ms >= self.keep_alive_interval_ms)
+        {
+            return invalid("keep_alive_timeout_ms must be greater than 0 and less than keep_alive_interval_ms");
+        }
+        match self.order_mode {
+            OrderMode::Ordered | OrderMode::StreamingOrdered if self.order_timeout_ms < 2 => {
+                invalid("order_timeout_ms must be at least 2 in Ordered / StreamingOrdered mode")
+            }
+            OrderMode::MicroBatch if self.micro_batch_us == 0 => {
+                invalid("micro_batch_us must be greater than 0 in MicroBatch mode")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 输出到 mpsc 通道（channel / stream 订阅）时的额外校验：通道发送端无法挤出旧事件，不支持 DropOldest
+    pub(crate) fn validate_channel_target(&self) -> Result<(), GrpcError> {
+        if self.overflow_policy == OverflowPolicy::DropOldest {
+            return Err(GrpcError::Config(
+                "overflow_policy DropOldest requires the queue output; channel and stream outputs cannot evict".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     pub fn low_latency() -> Self {
         Self {
             enable_metrics: false,
             connection_timeout_ms: 5000,
             request_timeout_ms: 10000,
             enable_tls: true,
-            max_retries: 1,
+            max_retries: 0,
             retry_delay_ms: 100,
             max_concurrent_streams: 200,
             keep_alive_interval_ms: 10000,
             keep_alive_timeout_ms: 2000,
             buffer_size: 16384,
+            max_decoding_message_size: 1024 * 1024 * 1024,
             order_mode: OrderMode::Unordered,
             order_timeout_ms: 50,
             micro_batch_us: 50, // 50μs 更激进的窗口
+            queue_capacity: 100_000,
+            overflow_policy: OverflowPolicy::DropNewest,
+            commitment: Commitment::Processed,
+            slot_status: false,
+            fork_policy: ForkPolicy::Emit,
+            resume_from_slot: true,
+            block_meta: false,
+            blocks: false,
         }
     }
 
@@ -97,24 +251,38 @@ impl ClientConfig {
             connection_timeout_ms: 10000,
             request_timeout_ms: 30000,
             enable_tls: true,
-            max_retries: 5,
+            max_retries: 0,
             retry_delay_ms: 2000,
             max_concurrent_streams: 500,
             keep_alive_interval_ms: 60000,
             keep_alive_timeout_ms: 10000,
             buffer_size: 32768,
+            max_decoding_message_size: 1024 * 1024 * 1024,
             order_mode: OrderMode::Unordered,
             order_timeout_ms: 200,
             micro_batch_us: 200, // 200μs 高吞吐模式
+            queue_capacity: 100_000,
+            overflow_policy: OverflowPolicy::DropNewest,
+            commitment: Commitment::Processed,
+            slot_status: false,
+            fork_policy: ForkPolicy::Emit,
+            resume_from_slot: true,
+            block_meta: false,
+            blocks: false,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TransactionFilter {
     pub account_include: Vec<String>,
     pub account_exclude: Vec<String>,
     pub account_required: Vec<String>,
+    /// 投票交易：Some(false) 排除（默认），Some(true) 仅投票交易，None 不过滤
+    pub vote: Option<bool>,
+    /// 失败交易：Some(false) 排除（默认），Some(true) 仅失败交易，None 不过滤
+    pub failed: Option<bool>,
 }
 
 impl TransactionFilter {
@@ -123,6 +291,8 @@ impl TransactionFilter {
             account_include: Vec::new(),
             account_exclude: Vec::new(),
             account_required: Vec::new(),
+            vote: Some(false),
+            failed: Some(false),
         }
     }
 
@@ -141,13 +311,21 @@ impl TransactionFilter {
         self
     }
 
+    /// 是否同时包含投票交易
+    pub fn include_votes(mut self, include: bool) -> Self {
+        self.vote = if include { None } else { Some(false) };
+        self
+    }
+
+    /// 是否同时包含失败交易（事件的 `metadata.is_failed` 标记失败）
+    pub fn include_failed(mut self, include: bool) -> Self {
+        self.failed = if include { None } else { Some(false) };
+        self
+    }
+
     /// 从程序ID列表创建过滤器
     pub fn from_program_ids(program_ids: Vec<String>) -> Self {
-        Self {
-            account_include: program_ids,
-            account_exclude: Vec::new(),
-            account_required: Vec::new(),
-        }
+        Self { account_include: program_ids, ..Self::new() }
     }
 }
 
@@ -157,10 +335,13 @@ impl Default for TransactionFilter {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AccountFilter {
     pub account: Vec<String>,
     pub owner: Vec<String>,
+    /// 配置文件中写作 `AccountFilterData` 列表（memcmp / datasize）
+    #[serde(with = "account_filters_serde")]
     pub filters: Vec<SubscribeRequestFilterAccountsFilter>,
 }
 
@@ -196,30 +377,121 @@ impl Default for AccountFilter {
     }
 }
 
-#[derive(Debug, Clone)]
+/// 账户数据过滤条件；memcmp 和 datasize 同时设置时两者都需满足
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AccountFilterData {
     pub memcmp: Option<AccountFilterMemcmp>,
     pub datasize: Option<u64>,
 }
 
-#[derive(Debug, Clone)]
+/// 从 offset 开始匹配 bytes（配置文件中为 base58 字符串）
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountFilterMemcmp {
     pub offset: u64,
+    #[serde(with = "base58_bytes")]
     pub bytes: Vec<u8>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+impl AccountFilterData {
+    /// 转为 Yellowstone 过滤条件
+    pub fn to_filters(&self) -> Vec<SubscribeRequestFilterAccountsFilter> {
+        let memcmp = self.memcmp.as_ref().map(|m| {
+            Filter::Memcmp(SubscribeRequestFilterAccountsFilterMemcmp {
+                offset: m.offset,
+                data: Some(Data::Bytes(m.bytes.clone())),
+            })
+        });
+        let datasize = self.datasize.map(Filter::Datasize);
+        memcmp
+            .into_iter()
+            .chain(datasize)
+            .map(|filter| SubscribeRequestFilterAccountsFilter { filter: Some(filter) })
+            .collect()
+    }
+
+    /// 从 Yellowstone 过滤条件转换；不支持的条件返回 None
+    pub fn from_filter(filter: &SubscribeRequestFilterAccountsFilter) -> Option<Self> {
+        match filter.filter.as_ref()? {
+            Filter::Memcmp(m) => {
+                let bytes = match m.data.as_ref()? {
+                    Data::Bytes(bytes) => bytes.clone(),
+                    Data::Base58(s) => bs58::decode(s).into_vec().ok()?,
+                    Data::Base64(s) => {
+                        use base64::Engine;
+                        base64::engine::general_purpose::STANDARD.decode(s).ok()?
+                    }
+                };
+                Some(Self { memcmp: Some(AccountFilterMemcmp { offset: m.offset, bytes }), datasize: None })
+            }
+            Filter::Datasize(size) => Some(Self { memcmp: None, datasize: Some(*size) }),
+            _ => None,
+        }
+    }
+}
+
+mod account_filters_serde {
+    use super::{AccountFilterData, SubscribeRequestFilterAccountsFilter};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(filters: &[SubscribeRequestFilterAccountsFilter], s: S) -> Result<S::Ok, S::Error> {
+        filters.iter().filter_map(AccountFilterData::from_filter).collect::<Vec<_>>().serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<SubscribeRequestFilterAccountsFilter>, D::Error> {
+        let data = Vec::<AccountFilterData>::deserialize(d)?;
+        Ok(data.iter().flat_map(AccountFilterData::to_filters).collect())
+    }
+}
+
+mod base58_bytes {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&bs58::encode(bytes).into_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(d)?;
+        bs58::decode(&s).into_vec().map_err(|e| D::Error::custom(format!("invalid base58 bytes: {}", e)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Protocol {
     PumpFun,
     PumpSwap,
+    /// Raydium LaunchLab（Launchpad）
+    #[serde(alias = "RaydiumLaunchpad")]
     Bonk,
     RaydiumCpmm,
     RaydiumClmm,
     RaydiumAmmV4,
     MeteoraDammV2,
+    OrcaWhirlpool,
+    MeteoraPools,
+    MeteoraDlmm,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl Protocol {
+    /// 协议可能产出的事件类型
+    pub fn event_types(self) -> &'static [EventType] {
+        match self {
+            Protocol::PumpFun => PUMPFUN_EVENT_TYPES,
+            Protocol::PumpSwap => PUMPSWAP_EVENT_TYPES,
+            Protocol::Bonk => BONK_EVENT_TYPES,
+            Protocol::RaydiumCpmm => RAYDIUM_CPMM_EVENT_TYPES,
+            Protocol::RaydiumClmm => RAYDIUM_CLMM_EVENT_TYPES,
+            Protocol::RaydiumAmmV4 => RAYDIUM_AMM_V4_EVENT_TYPES,
+            Protocol::MeteoraDammV2 => METEORA_DAMM_V2_EVENT_TYPES,
+            Protocol::OrcaWhirlpool => ORCA_WHIRLPOOL_EVENT_TYPES,
+            Protocol::MeteoraPools => METEORA_POOLS_EVENT_TYPES,
+            Protocol::MeteoraDlmm => METEORA_DLMM_EVENT_TYPES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EventType {
     // Block events
     BlockMeta,
@@ -248,41 +520,51 @@ pub enum EventType {
     // PumpSwapFeesClaimed,
 
     // Raydium CPMM events
-    // RaydiumCpmmSwap,
-    // RaydiumCpmmDeposit,
-    // RaydiumCpmmWithdraw,
-    // RaydiumCpmmInitialize,
+    RaydiumCpmmSwap,
+    RaydiumCpmmDeposit,
+    RaydiumCpmmWithdraw,
+    RaydiumCpmmInitialize,
 
     // Raydium CLMM events
-    // RaydiumClmmSwap,
-    // RaydiumClmmCreatePool,
-    // RaydiumClmmOpenPosition,
-    // RaydiumClmmClosePosition,
-    // RaydiumClmmIncreaseLiquidity,
-    // RaydiumClmmDecreaseLiquidity,
-    // RaydiumClmmOpenPositionWithTokenExtNft,
-    // RaydiumClmmCollectFee,
+    RaydiumClmmSwap,
+    RaydiumClmmCreatePool,
+    RaydiumClmmOpenPosition,
+    RaydiumClmmClosePosition,
+    RaydiumClmmIncreaseLiquidity,
+    RaydiumClmmDecreaseLiquidity,
+    RaydiumClmmOpenPositionWithTokenExtNft,
+    RaydiumClmmCollectFee,
 
     // Raydium AMM V4 events
-    // RaydiumAmmV4Swap,
-    // RaydiumAmmV4Deposit,
-    // RaydiumAmmV4Withdraw,
-    // RaydiumAmmV4Initialize2,
-    // RaydiumAmmV4WithdrawPnl,
+    RaydiumAmmV4Swap,
+    RaydiumAmmV4Deposit,
+    RaydiumAmmV4Withdraw,
+    RaydiumAmmV4Initialize2,
+    RaydiumAmmV4WithdrawPnl,
 
     // Orca Whirlpool events
-    // OrcaWhirlpoolSwap,
-    // OrcaWhirlpoolLiquidityIncreased,
-    // OrcaWhirlpoolLiquidityDecreased,
-    // OrcaWhirlpoolPoolInitialized,
-
-    // Meteora events
-    // MeteoraPoolsSwap,
-    // MeteoraPoolsAddLiquidity,
-    // MeteoraPoolsRemoveLiquidity,
-    // MeteoraPoolsBootstrapLiquidity,
-    // MeteoraPoolsPoolCreated,
-    // MeteoraPoolsSetPoolFees,
+    OrcaWhirlpoolSwap,
+    OrcaWhirlpoolLiquidityIncreased,
+    OrcaWhirlpoolLiquidityDecreased,
+    OrcaWhirlpoolPoolInitialized,
+
+    // Meteora Pools events
+    MeteoraPoolsSwap,
+    MeteoraPoolsAddLiquidity,
+    MeteoraPoolsRemoveLiquidity,
+    MeteoraPoolsBootstrapLiquidity,
+    MeteoraPoolsPoolCreated,
+    MeteoraPoolsSetPoolFees,
+
+    // Meteora DLMM events
+    MeteoraDlmmSwap,
+    MeteoraDlmmAddLiquidity,
+    MeteoraDlmmRemoveLiquidity,
+    MeteoraDlmmInitializePool,
+    MeteoraDlmmInitializeBinArray,
+    MeteoraDlmmCreatePosition,
+    MeteoraDlmmClosePosition,
+    MeteoraDlmmClaimFee,
 
     // Meteora DAMM V2 events
     MeteoraDammV2Swap,
@@ -304,7 +586,157 @@ pub enum EventType {
     AccountPumpSwapPool,
 }
 
-#[derive(Debug, Clone)]
+// ====== 各协议的事件类型 ======
+
+const PUMPFUN_EVENT_TYPES: &[EventType] = &[
+    EventType::PumpFunTrade,
+    EventType::PumpFunBuy,
+    EventType::PumpFunSell,
+    EventType::PumpFunBuyExactSolIn,
+    EventType::PumpFunCreate,
+    EventType::PumpFunComplete,
+    EventType::PumpFunMigrate,
+];
+
+const PUMPSWAP_EVENT_TYPES: &[EventType] = &[
+    EventType::PumpSwapBuy,
+    EventType::PumpSwapSell,
+    EventType::PumpSwapCreatePool,
+    EventType::PumpSwapLiquidityAdded,
+    EventType::PumpSwapLiquidityRemoved,
+];
+
+const BONK_EVENT_TYPES: &[EventType] =
+    &[EventType::BonkTrade, EventType::BonkPoolCreate, EventType::BonkMigrateAmm];
+
+const RAYDIUM_CPMM_EVENT_TYPES: &[EventType] = &[
+    EventType::RaydiumCpmmSwap,
+    EventType::RaydiumCpmmDeposit,
+    EventType::RaydiumCpmmWithdraw,
+    EventType::RaydiumCpmmInitialize,
+];
+
+const RAYDIUM_CLMM_EVENT_TYPES: &[EventType] = &[
+    EventType::RaydiumClmmSwap,
+    EventType::RaydiumClmmCreatePool,
+    EventType::RaydiumClmmOpenPosition,
+    EventType::RaydiumClmmClosePosition,
+    EventType::RaydiumClmmIncreaseLiquidity,
+    EventType::RaydiumClmmDecreaseLiquidity,
+    EventType::RaydiumClmmOpenPositionWithTokenExtNft,
+    EventType::RaydiumClmmCollectFee,
+];
+
+const RAYDIUM_AMM_V4_EVENT_TYPES: &[EventType] = &[
+    EventType::RaydiumAmmV4Swap,
+    EventType::RaydiumAmmV4Deposit,
+    EventType::RaydiumAmmV4Withdraw,
+    EventType::RaydiumAmmV4Initialize2,
+    EventType::RaydiumAmmV4WithdrawPnl,
+];
+
+const ORCA_WHIRLPOOL_EVENT_TYPES: &[EventType] = &[
+    EventType::OrcaWhirlpoolSwap,
+    EventType::OrcaWhirlpoolLiquidityIncreased,
+    EventType::OrcaWhirlpoolLiquidityDecreased,
+    EventType::OrcaWhirlpoolPoolInitialized,
+];
+
+const METEORA_POOLS_EVENT_TYPES: &[EventType] = &[
+    EventType::MeteoraPoolsSwap,
+    EventType::MeteoraPoolsAddLiquidity,
+    EventType::MeteoraPoolsRemoveLiquidity,
+    EventType::MeteoraPoolsBootstrapLiquidity,
+    EventType::MeteoraPoolsPoolCreated,
+    EventType::MeteoraPoolsSetPoolFees,
+];
+
+const METEORA_DLMM_EVENT_TYPES: &[EventType] = &[
+    EventType::MeteoraDlmmSwap,
+    EventType::MeteoraDlmmAddLiquidity,
+    EventType::MeteoraDlmmRemoveLiquidity,
+    EventType::MeteoraDlmmInitializePool,
+    EventType::MeteoraDlmmInitializeBinArray,
+    EventType::MeteoraDlmmCreatePosition,
+    EventType::MeteoraDlmmClosePosition,
+    EventType::MeteoraDlmmClaimFee,
+];
+
+const METEORA_DAMM_V2_EVENT_TYPES: &[EventType] = &[
+    EventType::MeteoraDammV2Swap,
+    EventType::MeteoraDammV2AddLiquidity,
+    EventType::MeteoraDammV2RemoveLiquidity,
+    EventType::MeteoraDammV2CreatePosition,
+    EventType::MeteoraDammV2ClosePosition,
+];
+
+impl EventType {
+    /// 已解析事件对应的 EventType（PumpFun 交易、PumpSwapTrade 等无一一对应类型的返回 None）
+    pub fn of_event(event: &DexEvent) -> Option<EventType> {
+        macro_rules! same_name {
+            ($($variant:ident),* $(,)?) => {
+                match event {
+                    $(DexEvent::$variant(_) => Some(EventType::$variant),)*
+                    _ => None,
+                }
+            };
+        }
+        same_name!(
+            PumpFunCreate,
+            PumpFunMigrate,
+            PumpSwapBuy,
+            PumpSwapSell,
+            PumpSwapCreatePool,
+            PumpSwapLiquidityAdded,
+            PumpSwapLiquidityRemoved,
+            BonkTrade,
+            BonkPoolCreate,
+            BonkMigrateAmm,
+            RaydiumCpmmSwap,
+            RaydiumCpmmDeposit,
+            RaydiumCpmmWithdraw,
+            RaydiumCpmmInitialize,
+            RaydiumClmmSwap,
+            RaydiumClmmCreatePool,
+            RaydiumClmmOpenPosition,
+            RaydiumClmmClosePosition,
+            RaydiumClmmIncreaseLiquidity,
+            RaydiumClmmDecreaseLiquidity,
+            RaydiumClmmOpenPositionWithTokenExtNft,
+            RaydiumClmmCollectFee,
+            RaydiumAmmV4Swap,
+            RaydiumAmmV4Deposit,
+            RaydiumAmmV4Withdraw,
+            RaydiumAmmV4Initialize2,
+            RaydiumAmmV4WithdrawPnl,
+            OrcaWhirlpoolSwap,
+            OrcaWhirlpoolLiquidityIncreased,
+            OrcaWhirlpoolLiquidityDecreased,
+            OrcaWhirlpoolPoolInitialized,
+            MeteoraPoolsSwap,
+            MeteoraPoolsAddLiquidity,
+            MeteoraPoolsRemoveLiquidity,
+            MeteoraPoolsBootstrapLiquidity,
+            MeteoraPoolsPoolCreated,
+            MeteoraPoolsSetPoolFees,
+            MeteoraDlmmSwap,
+            MeteoraDlmmAddLiquidity,
+            MeteoraDlmmRemoveLiquidity,
+            MeteoraDlmmInitializePool,
+            MeteoraDlmmInitializeBinArray,
+            MeteoraDlmmCreatePosition,
+            MeteoraDlmmClosePosition,
+            MeteoraDlmmClaimFee,
+            MeteoraDammV2Swap,
+            MeteoraDammV2AddLiquidity,
+            MeteoraDammV2RemoveLiquidity,
+            MeteoraDammV2CreatePosition,
+            MeteoraDammV2ClosePosition,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventTypeFilter {
     pub include_only: Option<Vec<EventType>>,
     pub exclude_types: Option<Vec<EventType>>,
@@ -343,128 +775,88 @@ impl EventTypeFilter {
         true
     }
 
+    /// 协议的任一事件类型是否会被保留：include_only 命中任一类型，或 exclude 未排除全部类型
     #[inline]
-    pub fn includes_pumpfun(&self) -> bool {
+    fn includes_any(&self, types: &[EventType]) -> bool {
         if let Some(ref include_only) = self.include_only {
-            return include_only.iter().any(|t| {
-                matches!(
-                    t,
-                    EventType::PumpFunTrade
-                        | EventType::PumpFunBuy
-                        | EventType::PumpFunSell
-                        | EventType::PumpFunBuyExactSolIn
-                        | EventType::PumpFunCreate
-                        | EventType::PumpFunComplete
-                        | EventType::PumpFunMigrate
-                )
-            });
+            return types.iter().any(|t| include_only.contains(t));
         }
-
         if let Some(ref exclude_types) = self.exclude_types {
-            return !exclude_types.iter().any(|t| {
-                matches!(
-                    t,
-                    EventType::PumpFunTrade
-                        | EventType::PumpFunBuy
-                        | EventType::PumpFunSell
-                        | EventType::PumpFunBuyExactSolIn
-                        | EventType::PumpFunCreate
-                        | EventType::PumpFunComplete
-                        | EventType::PumpFunMigrate
-                )
-            });
+            return types.iter().any(|t| !exclude_types.contains(t));
         }
-
         true
     }
 
+    /// Check if any event of the protocol is included in the filter
+    #[inline]
+    pub fn includes_protocol(&self, protocol: Protocol) -> bool {
+        self.includes_any(protocol.event_types())
+    }
+
+    #[inline]
+    pub fn includes_pumpfun(&self) -> bool {
+        self.includes_any(PUMPFUN_EVENT_TYPES)
+    }
+
     #[inline]
     pub fn includes_meteora_damm_v2(&self) -> bool {
-        if let Some(ref include_only) = self.include_only {
-            return include_only.iter().any(|t| {
-                matches!(
-                    t,
-                    EventType::MeteoraDammV2Swap
-                        | EventType::MeteoraDammV2AddLiquidity
-                        | EventType::MeteoraDammV2CreatePosition
-                        | EventType::MeteoraDammV2ClosePosition
-                        | EventType::MeteoraDammV2RemoveLiquidity
-                )
-            });
-        }
-        if let Some(ref exclude_types) = self.exclude_types {
-            return !exclude_types.iter().any(|t| {
-                matches!(
-                    t,
-                    EventType::MeteoraDammV2Swap
-                        | EventType::MeteoraDammV2AddLiquidity
-                        | EventType::MeteoraDammV2CreatePosition
-                        | EventType::MeteoraDammV2ClosePosition
-                        | EventType::MeteoraDammV2RemoveLiquidity
-                )
-            });
-        }
-        true
+        self.includes_any(METEORA_DAMM_V2_EVENT_TYPES)
     }
 
     /// Check if PumpSwap protocol events are included in the filter
     #[inline]
     pub fn includes_pumpswap(&self) -> bool {
-        if let Some(ref include_only) = self.include_only {
-            return include_only.iter().any(|t| {
-                matches!(
-                    t,
-                    EventType::PumpSwapBuy
-                        | EventType::PumpSwapSell
-                        | EventType::PumpSwapCreatePool
-                        | EventType::PumpSwapLiquidityAdded
-                        | EventType::PumpSwapLiquidityRemoved
-                )
-            });
-        }
-        if let Some(ref exclude_types) = self.exclude_types {
-            return !exclude_types.iter().any(|t| {
-                matches!(
-                    t,
-                    EventType::PumpSwapBuy
-                        | EventType::PumpSwapSell
-                        | EventType::PumpSwapCreatePool
-                        | EventType::PumpSwapLiquidityAdded
-                        | EventType::PumpSwapLiquidityRemoved
-                )
-            });
-        }
-        true
+        self.includes_any(PUMPSWAP_EVENT_TYPES)
     }
 
     /// Check if Raydium Launchpad (Bonk) events are included in the filter
     #[inline]
     pub fn includes_raydium_launchpad(&self) -> bool {
-        if let Some(ref include_only) = self.include_only {
-            return include_only.iter().any(|t| {
-                matches!(
-                    t,
-                    EventType::BonkTrade
-                        | EventType::BonkPoolCreate
-                        | EventType::BonkMigrateAmm
-                )
-            });
-        }
-        if let Some(ref exclude_types) = self.exclude_types {
-            return !exclude_types.iter().any(|t| {
-                matches!(
-                    t,
-                    EventType::BonkTrade
-                        | EventType::BonkPoolCreate
-                        | EventType::BonkMigrateAmm
-                )
-            });
-        }
-        true
+        self.includes_any(BONK_EVENT_TYPES)
+    }
+
+    /// Check if Raydium CPMM events are included in the filter
+    #[inline]
+    pub fn includes_raydium_cpmm(&self) -> bool {
+        self.includes_any(RAYDIUM_CPMM_EVENT_TYPES)
+    }
+
+    /// Check if Raydium CLMM events are included in the filter
+    #[inline]
+    pub fn includes_raydium_clmm(&self) -> bool {
+        self.includes_any(RAYDIUM_CLMM_EVENT_TYPES)
+    }
+
+    /// Check if Raydium AMM V4 events are included in the filter
+    #[inline]
+    pub fn includes_raydium_amm_v4(&self) -> bool {
+        self.includes_any(RAYDIUM_AMM_V4_EVENT_TYPES)
+    }
+
+    /// Check if Orca Whirlpool events are included in the filter
+    #[inline]
+    pub fn includes_orca_whirlpool(&self) -> bool {
+        self.includes_any(ORCA_WHIRLPOOL_EVENT_TYPES)
+    }
+
+    /// Check if Meteora Pools (AMM) events are included in the filter
+    #[inline]
+    pub fn includes_meteora_pools(&self) -> bool {
+        self.includes_any(METEORA_POOLS_EVENT_TYPES)
+    }
+
+    /// Check if Meteora DLMM events are included in the filter
+    #[inline]
+    pub fn includes_meteora_dlmm(&self) -> bool {
+        self.includes_any(METEORA_DLMM_EVENT_TYPES)
     }
 }
 
-#[derive(Debug, Clone)]
+/// slot 区间过滤（含两端），用于有界采集
+///
+/// 区间外的交易在解析前丢弃；实时订阅收到 max_slot 之后的更新即结束。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SlotFilter {
     pub min_slot: Option<u64>,
     pub max_slot: Option<u64>,
@@ -475,6 +867,23 @@ impl SlotFilter {
         Self { min_slot: None, max_slot: None }
     }
 
+    /// [min_slot, max_slot] 区间
+    pub fn range(min_slot: u64, max_slot: u64) -> Self {
+        Self { min_slot: Some(min_slot), max_slot: Some(max_slot) }
+    }
+
+    /// slot 是否在区间内
+    #[inline]
+    pub fn contains(&self, slot: u64) -> bool {
+        self.min_slot.is_none_or(|min| slot >= min) && self.max_slot.is_none_or(|max| slot <= max)
+    }
+
+    /// slot 是否已超过 max_slot
+    #[inline]
+    pub fn is_past(&self, slot: u64) -> bool {
+        self.max_slot.is_some_and(|max| slot > max)
+    }
+
     pub fn min_slot(mut self, slot: u64) -> Self {
         self.min_slot = Some(slot);
         self
@@ -486,8 +895,20 @@ impl SlotFilter {
     }
 }
 
-impl Default for SlotFilter {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_names() {
+        let protocols: Vec<Protocol> =
+            serde_json::from_str(r#"["OrcaWhirlpool", "MeteoraPools", "MeteoraDlmm", "RaydiumLaunchpad", "Bonk"]"#).unwrap();
+        assert_eq!(
+            protocols,
+            vec![Protocol::OrcaWhirlpool, Protocol::MeteoraPools, Protocol::MeteoraDlmm, Protocol::Bonk, Protocol::Bonk]
+        );
+        let filter = EventTypeFilter::include_only(vec![EventType::MeteoraDlmmSwap]);
+        assert!(filter.includes_protocol(Protocol::MeteoraDlmm));
+        assert!(!filter.includes_protocol(Protocol::OrcaWhirlpool));
     }
 }
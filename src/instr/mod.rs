@@ -14,18 +14,25 @@ pub mod raydium_clmm;
 pub mod raydium_cpmm;
 pub mod raydium_launchpad;
 pub mod utils;
-use crate::grpc::types::{EventType, EventTypeFilter};
+use crate::grpc::types::{EventType, EventTypeFilter, Protocol};
 use crate::logs::perf_hints::unlikely;
 
 // 重新导出主要解析函数
+pub use meteora_amm::parse_instruction as parse_meteora_pools_instruction;
 pub use meteora_damm::parse_instruction as parse_meteora_damm_instruction;
+pub use meteora_dlmm::parse_instruction as parse_meteora_dlmm_instruction;
+pub use orca_whirlpool::parse_instruction as parse_orca_whirlpool_instruction;
 pub use pump::parse_instruction as parse_pumpfun_instruction;
+pub use pump_amm::parse_instruction as parse_pumpswap_instruction;
+pub use raydium_amm::parse_instruction as parse_raydium_amm_v4_instruction;
+pub use raydium_clmm::parse_instruction as parse_raydium_clmm_instruction;
+pub use raydium_cpmm::parse_instruction as parse_raydium_cpmm_instruction;
+pub use raydium_launchpad::parse_instruction as parse_raydium_launchpad_instruction;
 
 // 重新导出工具函数
 pub use utils::*;
 
 use crate::core::events::DexEvent;
-use program_ids::*;
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
 
 /// 统一的指令解析入口函数
@@ -49,15 +56,15 @@ pub fn parse_instruction_unified(
     // 提前过滤和解析
     if let Some(filter) = event_type_filter {
         if let Some(ref include_only) = filter.include_only {
+            // 只订阅账户 / 区块类事件时无需解析指令
             let should_parse = include_only.iter().any(|t| {
-                matches!(
+                !matches!(
                     t,
-                    EventType::PumpFunMigrate
-                        | EventType::MeteoraDammV2Swap
-                        | EventType::MeteoraDammV2AddLiquidity
-                        | EventType::MeteoraDammV2CreatePosition
-                        | EventType::MeteoraDammV2ClosePosition
-                        | EventType::MeteoraDammV2RemoveLiquidity
+                    EventType::BlockMeta
+                        | EventType::TokenAccount
+                        | EventType::NonceAccount
+                        | EventType::AccountPumpSwapGlobalConfig
+                        | EventType::AccountPumpSwapPool
                 )
             });
             if unlikely(!should_parse) {
@@ -66,14 +73,16 @@ pub fn parse_instruction_unified(
         }
     }
 
-    // 根据程序 ID 路由到相应的解析器，按使用频率排序
+    // 根据程序 ID 路由到相应的解析器，按使用频率排序；
+    // 协议由注册表确定（跟随集群配置和用户覆盖）
+    let protocol = crate::grpc::program_ids::protocol_of(program_id);
 
     // Pumpfun
-    if *program_id == PUMPFUN_PROGRAM_ID {
+    if protocol == Some(Protocol::PumpFun) {
         if event_type_filter.is_some() && !event_type_filter.unwrap().includes_pumpfun() {
             return None;
         }
-        return parse_pumpfun_instruction(
+        let event = parse_pumpfun_instruction(
             instruction_data,
             accounts,
             signature,
@@ -81,10 +90,14 @@ pub fn parse_instruction_unified(
             tx_index,
             block_time_us,
             grpc_recv_us,
-        );
+        )?;
+        return match event_type_filter {
+            Some(filter) if !pumpfun_event_wanted(filter, &event) => None,
+            _ => Some(event),
+        };
     }
     // Meteora DAMM
-    else if *program_id == METEORA_DAMM_V2_PROGRAM_ID {
+    else if protocol == Some(Protocol::MeteoraDammV2) {
         if event_type_filter.is_some() && !event_type_filter.unwrap().includes_meteora_damm_v2() {
             return None;
         }
@@ -99,5 +112,270 @@ pub fn parse_instruction_unified(
         );
     }
 
-    None
+    // 其余协议的解析器签名一致，统一走分发表
+    let protocol = protocol?;
+    let parser: InstructionParser = match protocol {
+        Protocol::PumpSwap => parse_pumpswap_instruction,
+        Protocol::RaydiumAmmV4 => parse_raydium_amm_v4_instruction,
+        Protocol::RaydiumClmm => parse_raydium_clmm_instruction,
+        Protocol::RaydiumCpmm => parse_raydium_cpmm_instruction,
+        Protocol::Bonk => parse_raydium_launchpad_instruction,
+        Protocol::OrcaWhirlpool => parse_orca_whirlpool_instruction,
+        Protocol::MeteoraPools => parse_meteora_pools_instruction,
+        Protocol::MeteoraDlmm => parse_meteora_dlmm_instruction,
+        Protocol::PumpFun | Protocol::MeteoraDammV2 => return None,
+    };
+    if let Some(filter) = event_type_filter {
+        if !filter.includes_protocol(protocol) {
+            return None;
+        }
+    }
+    let event = parser(instruction_data, accounts, signature, slot, tx_index, block_time_us)?;
+    match event_type_filter {
+        Some(filter) if !typed_event_wanted(filter, &event) => None,
+        _ => Some(event),
+    }
+}
+
+/// 同 [`parse_instruction_unified`]，逐个回调指令产出的事件
+///
+/// Orca two_hop_swap / two_hop_swap_v2 每跳产出一个事件（共享 route_id），其余指令至多一个
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn parse_instruction_events(
+    instruction_data: &[u8],
+    accounts: &[Pubkey],
+    signature: Signature,
+    slot: u64,
+    tx_index: u64,
+    block_time_us: Option<i64>,
+    grpc_recv_us: i64,
+    event_type_filter: Option<&EventTypeFilter>,
+    program_id: &Pubkey,
+    mut emit: impl FnMut(DexEvent),
+) {
+    if orca_whirlpool::is_two_hop_swap(instruction_data)
+        && crate::grpc::program_ids::protocol_of(program_id) == Some(Protocol::OrcaWhirlpool)
+    {
+        if event_type_filter.is_some_and(|filter| !filter.includes_orca_whirlpool()) {
+            return;
+        }
+        let legs = orca_whirlpool::parse_two_hop_swap_instruction(
+            instruction_data,
+            accounts,
+            signature,
+            slot,
+            tx_index,
+            block_time_us,
+        );
+        for event in legs.into_iter().flatten() {
+            if event_type_filter.is_none_or(|filter| typed_event_wanted(filter, &event)) {
+                emit(event);
+            }
+        }
+        return;
+    }
+
+    if let Some(event) = parse_instruction_unified(
+        instruction_data,
+        accounts,
+        signature,
+        slot,
+        tx_index,
+        block_time_us,
+        grpc_recv_us,
+        event_type_filter,
+        program_id,
+    ) {
+        emit(event);
+    }
+}
+
+/// 不带 grpc_recv_us 的协议指令解析器签名
+type InstructionParser =
+    fn(&[u8], &[Pubkey], Signature, u64, u64, Option<i64>) -> Option<DexEvent>;
+
+/// 二次过滤：同一协议的指令可能产出多种事件
+#[inline]
+fn typed_event_wanted(filter: &EventTypeFilter, event: &DexEvent) -> bool {
+    EventType::of_event(event).is_none_or(|event_type| filter.should_include(event_type))
+}
+
+/// PumpFun 二次过滤：指令解析可能产出 Buy/Sell/Create/Migrate 多种事件
+#[inline]
+fn pumpfun_event_wanted(filter: &EventTypeFilter, event: &DexEvent) -> bool {
+    let (event_type, is_trade) = match event {
+        DexEvent::PumpFunBuy(_) => (EventType::PumpFunBuy, true),
+        DexEvent::PumpFunSell(_) => (EventType::PumpFunSell, true),
+        DexEvent::PumpFunBuyExactSolIn(_) => (EventType::PumpFunBuyExactSolIn, true),
+        DexEvent::PumpFunTrade(_) => (EventType::PumpFunTrade, true),
+        DexEvent::PumpFunCreate(_) => (EventType::PumpFunCreate, false),
+        DexEvent::PumpFunMigrate(_) => (EventType::PumpFunMigrate, false),
+        _ => return true,
+    };
+
+    if let Some(ref include_only) = filter.include_only {
+        return include_only.contains(&event_type)
+            || (is_trade && include_only.contains(&EventType::PumpFunTrade));
+    }
+    if let Some(ref exclude_types) = filter.exclude_types {
+        let excluded = exclude_types.contains(&event_type)
+            || (is_trade && exclude_types.contains(&EventType::PumpFunTrade));
+        return !excluded;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use program_ids::*;
+
+    fn raydium_amm_swap_ix() -> (Vec<u8>, Vec<Pubkey>) {
+        let mut data = vec![raydium_amm::discriminators::SWAP_BASE_IN];
+        data.extend_from_slice(&1_000_000u64.to_le_bytes());
+        data.extend_from_slice(&900u64.to_le_bytes());
+        let accounts = (0..18).map(|_| Pubkey::new_unique()).collect();
+        (data, accounts)
+    }
+
+    fn pumpswap_buy_ix() -> (Vec<u8>, Vec<Pubkey>) {
+        let mut data = pump_amm::discriminators::BUY.to_vec();
+        data.extend_from_slice(&500u64.to_le_bytes());
+        data.extend_from_slice(&7_000u64.to_le_bytes());
+        let accounts = (0..13).map(|_| Pubkey::new_unique()).collect();
+        (data, accounts)
+    }
+
+    fn dispatch(
+        data: &[u8],
+        accounts: &[Pubkey],
+        filter: Option<&EventTypeFilter>,
+        program_id: &Pubkey,
+    ) -> Option<DexEvent> {
+        parse_instruction_unified(
+            data,
+            accounts,
+            Signature::default(),
+            1,
+            0,
+            None,
+            0,
+            filter,
+            program_id,
+        )
+    }
+
+    #[test]
+    fn test_dispatch_raydium_amm_v4() {
+        let (data, accounts) = raydium_amm_swap_ix();
+        match dispatch(&data, &accounts, None, &RAYDIUM_AMM_V4_PROGRAM_ID) {
+            Some(DexEvent::RaydiumAmmV4Swap(e)) => {
+                assert_eq!(e.amm, accounts[1]);
+                assert_eq!(e.amount_in, 1_000_000);
+                assert_eq!(e.minimum_amount_out, 900);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        // gRPC inner 指令不带账户，不应产出事件
+        assert!(dispatch(&data, &[], None, &RAYDIUM_AMM_V4_PROGRAM_ID).is_none());
+
+        let only_pumpfun = EventTypeFilter::include_only(vec![EventType::PumpFunTrade]);
+        assert!(dispatch(&data, &accounts, Some(&only_pumpfun), &RAYDIUM_AMM_V4_PROGRAM_ID).is_none());
+    }
+
+    #[test]
+    fn test_dispatch_raydium_amm_v4_respects_filter() {
+        let (data, accounts) = raydium_amm_swap_ix();
+
+        let swaps = EventTypeFilter::include_only(vec![EventType::RaydiumAmmV4Swap]);
+        assert!(dispatch(&data, &accounts, Some(&swaps), &RAYDIUM_AMM_V4_PROGRAM_ID).is_some());
+
+        // 只订阅 Orca swap 时整个 Raydium 协议被跳过
+        let orca_swaps = EventTypeFilter::include_only(vec![EventType::OrcaWhirlpoolSwap]);
+        assert!(dispatch(&data, &accounts, Some(&orca_swaps), &RAYDIUM_AMM_V4_PROGRAM_ID).is_none());
+
+        let deposits = EventTypeFilter::include_only(vec![EventType::RaydiumAmmV4Deposit]);
+        assert!(dispatch(&data, &accounts, Some(&deposits), &RAYDIUM_AMM_V4_PROGRAM_ID).is_none());
+
+        // 排除同协议的其他类型不影响 swap
+        let no_deposits = EventTypeFilter::exclude_types(vec![EventType::RaydiumAmmV4Deposit]);
+        assert!(dispatch(&data, &accounts, Some(&no_deposits), &RAYDIUM_AMM_V4_PROGRAM_ID).is_some());
+        assert!(no_deposits.includes_raydium_amm_v4());
+        assert!(!orca_swaps.includes_raydium_amm_v4());
+        assert!(orca_swaps.includes_orca_whirlpool());
+    }
+
+    #[test]
+    fn test_orca_two_hop_swap_v2_emits_both_legs() {
+        let data = crate::test_fixtures::orca_two_hop_swap_v2_data();
+        let accounts: Vec<Pubkey> = (0..27).map(|_| Pubkey::new_unique()).collect();
+
+        let parse = |filter: Option<&EventTypeFilter>| {
+            let mut events = Vec::new();
+            parse_instruction_events(
+                &data,
+                &accounts,
+                Signature::default(),
+                1,
+                0,
+                None,
+                0,
+                filter,
+                &ORCA_WHIRLPOOL_PROGRAM_ID,
+                |e| events.push(e),
+            );
+            events
+        };
+
+        let legs: Vec<_> = parse(None)
+            .into_iter()
+            .map(|e| match e {
+                DexEvent::OrcaWhirlpoolSwap(e) => e,
+                other => panic!("unexpected event: {:?}", other),
+            })
+            .collect();
+        let [first, second] = legs.as_slice() else { panic!("expected two legs") };
+        assert_eq!((first.hop, second.hop), (1, 2));
+        assert_eq!(first.route_id, second.route_id);
+        assert_eq!((first.whirlpool, second.whirlpool), (accounts[0], accounts[1]));
+        assert_eq!((first.input_amount, second.output_amount), (1_000, 900));
+        // 第一跳 A -> B：A 为输入侧；第二跳 B -> A：A 为输出侧
+        assert_eq!((first.token_program_a, first.token_program_b), (accounts[5], accounts[6]));
+        assert_eq!(first.transfer_hook_accounts_a, vec![accounts[24]]);
+        assert_eq!((second.token_program_a, second.token_program_b), (accounts[7], accounts[6]));
+        assert_eq!(second.transfer_hook_accounts_a, vec![accounts[25], accounts[26]]);
+        assert_eq!(second.memo_program, accounts[23]);
+
+        let raydium_only = EventTypeFilter::include_only(vec![EventType::RaydiumAmmV4Swap]);
+        assert!(parse(Some(&raydium_only)).is_empty());
+        // 单事件接口只返回第一跳
+        match dispatch(&data, &accounts, None, &ORCA_WHIRLPOOL_PROGRAM_ID) {
+            Some(DexEvent::OrcaWhirlpoolSwap(e)) => assert_eq!(e.hop, 1),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dispatch_pumpswap_respects_filter() {
+        let (data, accounts) = pumpswap_buy_ix();
+        match dispatch(&data, &accounts, None, &PUMPSWAP_PROGRAM_ID) {
+            Some(DexEvent::PumpSwapBuy(e)) => {
+                assert_eq!(e.pool, accounts[0]);
+                assert_eq!(e.base_amount_out, 500);
+                assert_eq!(e.max_quote_amount_in, 7_000);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        let buys = EventTypeFilter::include_only(vec![EventType::PumpSwapBuy]);
+        assert!(dispatch(&data, &accounts, Some(&buys), &PUMPSWAP_PROGRAM_ID).is_some());
+
+        let sells = EventTypeFilter::include_only(vec![EventType::PumpSwapSell]);
+        assert!(dispatch(&data, &accounts, Some(&sells), &PUMPSWAP_PROGRAM_ID).is_none());
+
+        let no_buys = EventTypeFilter::exclude_types(vec![EventType::PumpSwapBuy]);
+        assert!(dispatch(&data, &accounts, Some(&no_buys), &PUMPSWAP_PROGRAM_ID).is_none());
+    }
 }
@@ -104,9 +104,45 @@ pub mod discriminators {
     pub const TWO_HOP_SWAP_V2: [u8; 8] = [186, 143, 209, 29, 254, 2, 194, 117];
 }
 
+/// RemainingAccountsInfo 中的 AccountsType（V2 指令）
+pub mod accounts_type {
+    pub const TRANSFER_HOOK_A: u8 = 0;
+    pub const TRANSFER_HOOK_B: u8 = 1;
+    pub const TRANSFER_HOOK_REWARD: u8 = 2;
+    pub const TRANSFER_HOOK_INPUT: u8 = 3;
+    pub const TRANSFER_HOOK_INTERMEDIATE: u8 = 4;
+    pub const TRANSFER_HOOK_OUTPUT: u8 = 5;
+    pub const SUPPLEMENTAL_TICK_ARRAYS: u8 = 6;
+    pub const SUPPLEMENTAL_TICK_ARRAYS_ONE: u8 = 7;
+    pub const SUPPLEMENTAL_TICK_ARRAYS_TWO: u8 = 8;
+}
+
+/// swap_v2 固定账户数，之后为 remaining accounts
+const SWAP_V2_ACCOUNTS_LEN: usize = 15;
+/// two_hop_swap_v2 固定账户数，之后为 remaining accounts
+const TWO_HOP_SWAP_V2_ACCOUNTS_LEN: usize = 24;
+
 /// Orca Whirlpool 程序 ID
 pub const PROGRAM_ID_PUBKEY: Pubkey = program_ids::ORCA_WHIRLPOOL_PROGRAM_ID;
 
+/// 是否为 two_hop_swap / two_hop_swap_v2 指令
+#[inline]
+pub fn is_two_hop_swap(instruction_data: &[u8]) -> bool {
+    matches!(
+        instruction_data.get(..8),
+        Some(d) if d == discriminators::TWO_HOP_SWAP || d == discriminators::TWO_HOP_SWAP_V2
+    )
+}
+
+/// 是否为 swap / swap_v2 / two_hop_swap / two_hop_swap_v2 指令
+#[inline]
+pub fn is_swap(instruction_data: &[u8]) -> bool {
+    matches!(
+        instruction_data.get(..8),
+        Some(d) if d == discriminators::SWAP || d == discriminators::SWAP_V2 || is_two_hop_swap(instruction_data)
+    )
+}
+
 /// 主要的 Orca Whirlpool 指令解析函数
 pub fn parse_instruction(
     instruction_data: &[u8],
@@ -125,9 +161,19 @@ pub fn parse_instruction(
     let data = &instruction_data[8..];
 
     match instruction_type {
-        OrcaWhirlpoolInstruction::Swap | OrcaWhirlpoolInstruction::SwapV2 => {
+        OrcaWhirlpoolInstruction::Swap => {
             parse_swap_instruction(data, accounts, signature, slot, tx_index, block_time_us)
         },
+        OrcaWhirlpoolInstruction::SwapV2 => {
+            parse_swap_v2_instruction(data, accounts, signature, slot, tx_index, block_time_us)
+        },
+        // 单事件接口只返回第一跳，两跳都需要时使用 `parse_two_hop_swap_instruction`
+        OrcaWhirlpoolInstruction::TwoHopSwap | OrcaWhirlpoolInstruction::TwoHopSwapV2 => {
+            let [first, _] = parse_two_hop_swap_instruction(
+                instruction_data, accounts, signature, slot, tx_index, block_time_us,
+            )?;
+            Some(first)
+        },
         OrcaWhirlpoolInstruction::IncreaseLiquidity | OrcaWhirlpoolInstruction::IncreaseLiquidityV2 => {
             parse_increase_liquidity_instruction(data, accounts, signature, slot, tx_index, block_time_us)
         },
@@ -166,7 +212,8 @@ fn parse_swap_instruction(
 
     let a_to_b = read_bool(data, offset)?;
 
-    let whirlpool = get_account(accounts, 1)?;
+    let token_program = get_account(accounts, 0)?;
+    let whirlpool = get_account(accounts, 2)?;
     let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, whirlpool);
 
     Some(DexEvent::OrcaWhirlpoolSwap(OrcaWhirlpoolSwapEvent {
@@ -204,9 +251,197 @@ fn parse_swap_instruction(
         // tick_array_0: Pubkey::default(),
         // tick_array_1: Pubkey::default(),
         // tick_array_2: Pubkey::default(),
+
+        token_program_a: token_program,
+        token_program_b: token_program,
+        ..Default::default()
+    }))
+}
+
+/// 解析 SwapV2 指令（Token-2022：A / B 各自的 token program、memo 与 transfer hook 账户）
+fn parse_swap_v2_instruction(
+    data: &[u8],
+    accounts: &[Pubkey],
+    signature: Signature,
+    slot: u64,
+    tx_index: u64,
+    block_time_us: Option<i64>,
+) -> Option<DexEvent> {
+    let amount = read_u64_le(data, 0)?;
+    let other_amount_threshold = read_u64_le(data, 8)?;
+    let sqrt_price_limit = read_u128_le(data, 16)?;
+    let amount_specified_is_input = read_bool(data, 32)?;
+    let a_to_b = read_bool(data, 33)?;
+
+    // 0: tokenProgramA, 1: tokenProgramB, 2: memoProgram, 3: tokenAuthority, 4: whirlpool
+    let whirlpool = get_account(accounts, 4)?;
+    let slices = remaining_account_slices(data, 34, accounts.get(SWAP_V2_ACCOUNTS_LEN..).unwrap_or_default());
+    let (input_amount, output_amount) = swap_amounts(amount, other_amount_threshold, amount_specified_is_input);
+
+    Some(DexEvent::OrcaWhirlpoolSwap(OrcaWhirlpoolSwapEvent {
+        metadata: create_metadata_simple(signature, slot, tx_index, block_time_us, whirlpool),
+        whirlpool,
+        a_to_b,
+        pre_sqrt_price: sqrt_price_limit, // 从指令获取初始值，日志会覆盖
+        input_amount,
+        output_amount,
+        token_program_a: get_account(accounts, 0)?,
+        token_program_b: get_account(accounts, 1)?,
+        memo_program: get_account(accounts, 2)?,
+        transfer_hook_accounts_a: hook_accounts(&slices, accounts_type::TRANSFER_HOOK_A),
+        transfer_hook_accounts_b: hook_accounts(&slices, accounts_type::TRANSFER_HOOK_B),
+        ..Default::default()
     }))
 }
 
+/// 解析 two_hop_swap / two_hop_swap_v2 指令（含 discriminator）
+///
+/// 两跳各产出一个 swap 事件（hop = 1 / 2），共享同一个 route_id。
+/// 指令只给出路由两端的数量：第一跳的输入与第二跳的输出，中间数量由日志得到。
+pub fn parse_two_hop_swap_instruction(
+    instruction_data: &[u8],
+    accounts: &[Pubkey],
+    signature: Signature,
+    slot: u64,
+    tx_index: u64,
+    block_time_us: Option<i64>,
+) -> Option<[DexEvent; 2]> {
+    let discriminator: [u8; 8] = instruction_data.get(..8)?.try_into().ok()?;
+    let is_v2 = match discriminator {
+        discriminators::TWO_HOP_SWAP => false,
+        discriminators::TWO_HOP_SWAP_V2 => true,
+        _ => return None,
+    };
+    let data = &instruction_data[8..];
+
+    let amount = read_u64_le(data, 0)?;
+    let other_amount_threshold = read_u64_le(data, 8)?;
+    let amount_specified_is_input = read_bool(data, 16)?;
+    let a_to_b_one = read_bool(data, 17)?;
+    let a_to_b_two = read_bool(data, 18)?;
+    let sqrt_price_limit_one = read_u128_le(data, 19)?;
+    let sqrt_price_limit_two = read_u128_le(data, 35)?;
+
+    // v1: 0: tokenProgram, 1: tokenAuthority, 2: whirlpoolOne, 3: whirlpoolTwo
+    // v2: 0: whirlpoolOne, 1: whirlpoolTwo
+    let (whirlpool_one, whirlpool_two) = if is_v2 {
+        (get_account(accounts, 0)?, get_account(accounts, 1)?)
+    } else {
+        (get_account(accounts, 2)?, get_account(accounts, 3)?)
+    };
+    let route_id = route_id(&signature, instruction_data, &[whirlpool_one, whirlpool_two]);
+    let (first_input, last_output) = swap_amounts(amount, other_amount_threshold, amount_specified_is_input);
+
+    let leg = |hop: u8, whirlpool: Pubkey, a_to_b: bool, sqrt_price_limit: u128| OrcaWhirlpoolSwapEvent {
+        metadata: create_metadata_simple(signature, slot, tx_index, block_time_us, whirlpool),
+        whirlpool,
+        a_to_b,
+        pre_sqrt_price: sqrt_price_limit,
+        hop,
+        route_id,
+        ..Default::default()
+    };
+    let mut first = leg(1, whirlpool_one, a_to_b_one, sqrt_price_limit_one);
+    first.input_amount = first_input;
+    let mut second = leg(2, whirlpool_two, a_to_b_two, sqrt_price_limit_two);
+    second.output_amount = last_output;
+
+    if is_v2 {
+        // 5: tokenProgramInput, 6: tokenProgramIntermediate, 7: tokenProgramOutput, 23: memoProgram
+        let program_input = get_account(accounts, 5)?;
+        let program_intermediate = get_account(accounts, 6)?;
+        let program_output = get_account(accounts, 7)?;
+        let memo_program = get_account(accounts, 23)?;
+        let slices =
+            remaining_account_slices(data, 51, accounts.get(TWO_HOP_SWAP_V2_ACCOUNTS_LEN..).unwrap_or_default());
+        let hooks_intermediate = hook_accounts(&slices, accounts_type::TRANSFER_HOOK_INTERMEDIATE);
+
+        set_token_sides(
+            &mut first,
+            a_to_b_one,
+            (program_input, hook_accounts(&slices, accounts_type::TRANSFER_HOOK_INPUT)),
+            (program_intermediate, hooks_intermediate.clone()),
+        );
+        set_token_sides(
+            &mut second,
+            a_to_b_two,
+            (program_intermediate, hooks_intermediate),
+            (program_output, hook_accounts(&slices, accounts_type::TRANSFER_HOOK_OUTPUT)),
+        );
+        first.memo_program = memo_program;
+        second.memo_program = memo_program;
+    } else {
+        let token_program = get_account(accounts, 0)?;
+        for event in [&mut first, &mut second] {
+            event.token_program_a = token_program;
+            event.token_program_b = token_program;
+        }
+    }
+
+    Some([DexEvent::OrcaWhirlpoolSwap(first), DexEvent::OrcaWhirlpoolSwap(second)])
+}
+
+/// 由指令参数得到 (输入, 输出) 初始值，实际值由日志覆盖
+#[inline]
+fn swap_amounts(amount: u64, other_amount_threshold: u64, amount_specified_is_input: bool) -> (u64, u64) {
+    if amount_specified_is_input {
+        (amount, other_amount_threshold)
+    } else {
+        (0, amount)
+    }
+}
+
+/// 按交易方向把 (输入侧, 输出侧) 的 token program 与 transfer hook 账户写入 A / B 两侧
+fn set_token_sides(
+    event: &mut OrcaWhirlpoolSwapEvent,
+    a_to_b: bool,
+    input: (Pubkey, Vec<Pubkey>),
+    output: (Pubkey, Vec<Pubkey>),
+) {
+    let ((program_a, hooks_a), (program_b, hooks_b)) = if a_to_b { (input, output) } else { (output, input) };
+    event.token_program_a = program_a;
+    event.token_program_b = program_b;
+    event.transfer_hook_accounts_a = hooks_a;
+    event.transfer_hook_accounts_b = hooks_b;
+}
+
+/// 按 Option<RemainingAccountsInfo> 切分 remaining accounts，返回 (AccountsType, 账户) 列表
+fn remaining_account_slices<'a>(data: &[u8], offset: usize, remaining: &'a [Pubkey]) -> Vec<(u8, &'a [Pubkey])> {
+    let mut slices = Vec::new();
+    if read_u8(data, offset) != Some(1) {
+        return slices;
+    }
+    let Some(count) = read_u32_le(data, offset + 1) else {
+        return slices;
+    };
+    let mut start = 0;
+    for i in 0..count as usize {
+        let item = offset + 5 + i * 2;
+        let (Some(accounts_type), Some(length)) = (read_u8(data, item), read_u8(data, item + 1)) else {
+            break;
+        };
+        let end = (start + length as usize).min(remaining.len());
+        slices.push((accounts_type, &remaining[start..end]));
+        start = end;
+    }
+    slices
+}
+
+/// 指定类型的 remaining accounts
+fn hook_accounts(slices: &[(u8, &[Pubkey])], accounts_type: u8) -> Vec<Pubkey> {
+    slices.iter().filter(|(t, _)| *t == accounts_type).flat_map(|(_, accounts)| accounts.iter().copied()).collect()
+}
+
+/// 两跳路由 ID：签名、指令数据与两个池地址的 FNV-1a 哈希
+fn route_id(signature: &Signature, instruction_data: &[u8], whirlpools: &[Pubkey; 2]) -> u64 {
+    signature
+        .as_ref()
+        .iter()
+        .chain(instruction_data)
+        .chain(whirlpools.iter().flat_map(|w| w.as_ref()))
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
 /// 解析 Increase Liquidity 指令
 fn parse_increase_liquidity_instruction(
     data: &[u8],
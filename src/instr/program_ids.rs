@@ -3,37 +3,22 @@
 //! This module contains optimized Pubkey constants for all DEX protocols.
 //! Using Pubkey constants instead of string constants allows for direct
 //! comparison without expensive string conversion operations.
+//!
+//! Mainnet values are re-exported from the canonical registry in
+//! `crate::grpc::program_ids`; use `protocol_of` there to honour the active
+//! cluster profile and user overrides.
 
-use solana_sdk::pubkey;
 use solana_sdk::pubkey::Pubkey;
-
-/// PumpFun program ID as Pubkey constant
-pub const PUMPFUN_PROGRAM_ID: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
-
-/// Bonk program ID as Pubkey constant
-pub const BONK_PROGRAM_ID: Pubkey = pubkey!("DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1");
-
-/// PumpSwap program ID as Pubkey constant (same as PumpFun)
-pub const PUMPSWAP_PROGRAM_ID: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
-
-/// Raydium CLMM program ID as Pubkey constant
-/// Note: Original string was too long, using corrected version
-pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey = pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUQpMDdHFWF5LCATdCR");
-
-/// Raydium CPMM program ID as Pubkey constant
-pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
-
-/// Raydium AMM V4 program ID as Pubkey constant
-pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey = pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
-
-/// Orca Whirlpool program ID as Pubkey constant
-pub const ORCA_WHIRLPOOL_PROGRAM_ID: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
-
-/// Meteora Pools program ID as Pubkey constant
-pub const METEORA_POOLS_PROGRAM_ID: Pubkey = pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
-
-/// Meteora DAMM V2 program ID as Pubkey constant
-pub const METEORA_DAMM_V2_PROGRAM_ID: Pubkey = pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
-
-/// Meteora DLMM program ID as Pubkey constant
-pub const METEORA_DLMM_PROGRAM_ID: Pubkey = pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
\ No newline at end of file
+use crate::grpc::program_ids as registry;
+
+pub const PUMPFUN_PROGRAM_ID: Pubkey = registry::PUMPFUN_PROGRAM;
+/// Raydium LaunchLab (Bonk)
+pub const BONK_PROGRAM_ID: Pubkey = registry::BONK_PROGRAM;
+pub const PUMPSWAP_PROGRAM_ID: Pubkey = registry::PUMPSWAP_PROGRAM;
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey = registry::RAYDIUM_CLMM_PROGRAM;
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = registry::RAYDIUM_CPMM_PROGRAM;
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey = registry::RAYDIUM_AMM_V4_PROGRAM;
+pub const ORCA_WHIRLPOOL_PROGRAM_ID: Pubkey = registry::ORCA_WHIRLPOOL_PROGRAM;
+pub const METEORA_POOLS_PROGRAM_ID: Pubkey = registry::METEORA_POOLS_PROGRAM;
+pub const METEORA_DAMM_V2_PROGRAM_ID: Pubkey = registry::METEORA_DAMM_V2_PROGRAM;
+pub const METEORA_DLMM_PROGRAM_ID: Pubkey = registry::METEORA_DLMM_PROGRAM;
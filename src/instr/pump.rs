@@ -27,7 +27,10 @@ pub const PROGRAM_ID_PUBKEY: Pubkey = program_ids::PUMPFUN_PROGRAM_ID;
 /// Main PumpFun instruction parser
 ///
 /// Note: Full event data (amounts, fees, reserves) is parsed from logs.
-/// Instruction parsing only handles MIGRATE_EVENT_LOG which is not available in logs.
+/// With an empty account list (inner instructions from gRPC) only MIGRATE_EVENT_LOG
+/// is handled, since it is not available in logs. When the outer instruction
+/// accounts are supplied (e.g. ShredStream, no meta/logs), buy/sell/create are
+/// decoded from instruction args as a fallback.
 pub fn parse_instruction(
     instruction_data: &[u8],
     accounts: &[Pubkey],
@@ -45,7 +48,7 @@ pub fn parse_instruction(
 
     let cpi_discriminator: [u8; 8] = instruction_data[8..16].try_into().ok()?;
     if cpi_discriminator == discriminators::MIGRATE_EVENT_LOG {
-        parse_migrate_log_instruction(
+        return parse_migrate_log_instruction(
             &instruction_data[16..],
             accounts,
             signature,
@@ -53,9 +56,30 @@ pub fn parse_instruction(
             tx_index,
             block_time_us,
             grpc_recv_us,
-        )
-    } else {
-        None
+        );
+    }
+
+    // Outer instruction fallback: requires the instruction accounts
+    if accounts.is_empty() {
+        return None;
+    }
+
+    let discriminator: [u8; 8] = instruction_data[0..8].try_into().ok()?;
+    let data = &instruction_data[8..];
+    match discriminator {
+        discriminators::BUY => parse_buy_instruction(
+            data, accounts, signature, slot, tx_index, block_time_us, grpc_recv_us, false,
+        ),
+        discriminators::BUY_EXACT_SOL_IN => parse_buy_instruction(
+            data, accounts, signature, slot, tx_index, block_time_us, grpc_recv_us, true,
+        ),
+        discriminators::SELL => parse_sell_instruction(
+            data, accounts, signature, slot, tx_index, block_time_us, grpc_recv_us,
+        ),
+        discriminators::CREATE => parse_create_instruction(
+            data, accounts, signature, slot, tx_index, block_time_us, grpc_recv_us,
+        ),
+        _ => None,
     }
 }
 
@@ -64,7 +88,9 @@ pub fn parse_instruction(
 /// Account indices (from pump.json):
 /// 0: global, 1: fee_recipient, 2: mint, 3: bonding_curve,
 /// 4: associated_bonding_curve, 5: associated_user, 6: user
-#[allow(dead_code)]
+///
+/// Amounts are the instruction limits (max cost / min out), not the executed amounts.
+#[allow(clippy::too_many_arguments)]
 fn parse_buy_instruction(
     data: &[u8],
     accounts: &[Pubkey],
@@ -73,17 +99,21 @@ fn parse_buy_instruction(
     tx_index: u64,
     block_time_us: Option<i64>,
     grpc_recv_us: i64,
+    exact_sol_in: bool,
 ) -> Option<DexEvent> {
     if accounts.len() < 7 {
         return None;
     }
 
-    // Parse args: amount/spendable_sol_in (u64), max_sol_cost/min_tokens_out (u64)
-    let (sol_amount, token_amount) = if data.len() >= 16 {
+    // Parse args:
+    // buy: amount (u64), max_sol_cost (u64)
+    // buy_exact_sol_in: spendable_sol_in (u64), min_tokens_out (u64)
+    let (first, second) = if data.len() >= 16 {
         (read_u64_le(data, 0).unwrap_or(0), read_u64_le(data, 8).unwrap_or(0))
     } else {
         (0, 0)
     };
+    let (sol_amount, token_amount) = if exact_sol_in { (first, second) } else { (second, first) };
 
     let mint = get_account(accounts, 2)?;
     let metadata = create_metadata(
@@ -91,17 +121,26 @@ fn parse_buy_instruction(
         block_time_us.unwrap_or_default(), grpc_recv_us
     );
 
-    Some(DexEvent::PumpFunTrade(PumpFunTradeEvent {
+    let event = PumpFunTradeEvent {
         metadata,
         mint,
         is_buy: true,
         bonding_curve: get_account(accounts, 3).unwrap_or_default(),
+        associated_bonding_curve: get_account(accounts, 4).unwrap_or_default(),
         user: get_account(accounts, 6).unwrap_or_default(),
         sol_amount,
         token_amount,
         fee_recipient: get_account(accounts, 1).unwrap_or_default(),
+        creator_vault: get_account(accounts, 9).unwrap_or_default(),
+        ix_name: if exact_sol_in { "buy_exact_sol_in" } else { "buy" }.to_string(),
         ..Default::default()
-    }))
+    };
+
+    Some(if exact_sol_in {
+        DexEvent::PumpFunBuyExactSolIn(event)
+    } else {
+        DexEvent::PumpFunBuy(event)
+    })
 }
 
 /// Parse sell instruction
@@ -109,7 +148,8 @@ fn parse_buy_instruction(
 /// Account indices (from pump.json):
 /// 0: global, 1: fee_recipient, 2: mint, 3: bonding_curve,
 /// 4: associated_bonding_curve, 5: associated_user, 6: user
-#[allow(dead_code)]
+///
+/// Amounts are the instruction limits (amount / min sol output), not the executed amounts.
 fn parse_sell_instruction(
     data: &[u8],
     accounts: &[Pubkey],
@@ -136,15 +176,18 @@ fn parse_sell_instruction(
         block_time_us.unwrap_or_default(), grpc_recv_us
     );
 
-    Some(DexEvent::PumpFunTrade(PumpFunTradeEvent {
+    Some(DexEvent::PumpFunSell(PumpFunTradeEvent {
         metadata,
         mint,
         is_buy: false,
         bonding_curve: get_account(accounts, 3).unwrap_or_default(),
+        associated_bonding_curve: get_account(accounts, 4).unwrap_or_default(),
         user: get_account(accounts, 6).unwrap_or_default(),
         sol_amount,
         token_amount,
         fee_recipient: get_account(accounts, 1).unwrap_or_default(),
+        creator_vault: get_account(accounts, 8).unwrap_or_default(),
+        ix_name: "sell".to_string(),
         ..Default::default()
     }))
 }
@@ -154,7 +197,6 @@ fn parse_sell_instruction(
 /// Account indices (from pump.json):
 /// 0: mint, 1: mint_authority, 2: bonding_curve, 3: associated_bonding_curve,
 /// 4: global, 5: mpl_token_metadata, 6: metadata, 7: user
-#[allow(dead_code)]
 fn parse_create_instruction(
     data: &[u8],
     accounts: &[Pubkey],
@@ -27,21 +27,43 @@ pub mod discriminators {
 pub const PROGRAM_ID_PUBKEY: Pubkey = program_ids::PUMPSWAP_PROGRAM_ID;
 
 /// Main PumpSwap instruction parser
-/// 
+///
 /// Note: Full event data (amounts, fees, etc.) is parsed from logs.
-/// Instruction parsing returns None to avoid duplicate events.
+/// gRPC inner instructions carry no accounts, so only outer instructions
+/// (ShredStream / RPC, accounts resolved) are decoded here.
 pub fn parse_instruction(
-    _instruction_data: &[u8],
-    _accounts: &[Pubkey],
-    _signature: Signature,
-    _slot: u64,
-    _tx_index: u64,
-    _block_time_us: Option<i64>,
+    instruction_data: &[u8],
+    accounts: &[Pubkey],
+    signature: Signature,
+    slot: u64,
+    tx_index: u64,
+    block_time_us: Option<i64>,
 ) -> Option<DexEvent> {
-    // All PumpSwap events (BuyEvent, SellEvent, CreatePoolEvent, DepositEvent, WithdrawEvent)
-    // are parsed from logs which contain complete execution results.
-    // Returning None here avoids duplicate events and improves performance.
-    None
+    if accounts.is_empty() || instruction_data.len() < 8 {
+        return None;
+    }
+
+    let discriminator: [u8; 8] = instruction_data[0..8].try_into().ok()?;
+    let data = &instruction_data[8..];
+
+    match discriminator {
+        discriminators::BUY | discriminators::BUY_EXACT_QUOTE_IN => {
+            parse_buy_instruction(data, accounts, signature, slot, tx_index, block_time_us)
+        }
+        discriminators::SELL => {
+            parse_sell_instruction(data, accounts, signature, slot, tx_index, block_time_us)
+        }
+        discriminators::CREATE_POOL => {
+            parse_create_pool_instruction(data, accounts, signature, slot, tx_index, block_time_us)
+        }
+        discriminators::DEPOSIT => {
+            parse_deposit_instruction(data, accounts, signature, slot, tx_index, block_time_us)
+        }
+        discriminators::WITHDRAW => {
+            parse_withdraw_instruction(data, accounts, signature, slot, tx_index, block_time_us)
+        }
+        _ => None,
+    }
 }
 
 /// Parse buy/buy_exact_quote_in instruction
@@ -52,7 +74,6 @@ pub fn parse_instruction(
 /// 7: pool_base_token_account, 8: pool_quote_token_account,
 /// 9: protocol_fee_recipient, 10: protocol_fee_recipient_token_account,
 /// 11: base_token_program, 12: quote_token_program
-#[allow(dead_code)]
 fn parse_buy_instruction(
     data: &[u8],
     accounts: &[Pubkey],
@@ -101,7 +122,6 @@ fn parse_buy_instruction(
 /// Parse sell instruction
 ///
 /// Account indices same as buy
-#[allow(dead_code)]
 fn parse_sell_instruction(
     data: &[u8],
     accounts: &[Pubkey],
@@ -147,7 +167,6 @@ fn parse_sell_instruction(
 }
 
 /// Parse create_pool instruction
-#[allow(dead_code)]
 fn parse_create_pool_instruction(
     _data: &[u8],
     accounts: &[Pubkey],
@@ -175,7 +194,6 @@ fn parse_create_pool_instruction(
 }
 
 /// Parse deposit (add liquidity) instruction
-#[allow(dead_code)]
 fn parse_deposit_instruction(
     _data: &[u8],
     accounts: &[Pubkey],
@@ -205,7 +223,6 @@ fn parse_deposit_instruction(
 }
 
 /// Parse withdraw (remove liquidity) instruction
-#[allow(dead_code)]
 fn parse_withdraw_instruction(
     _data: &[u8],
     accounts: &[Pubkey],
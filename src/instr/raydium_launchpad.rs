@@ -1,22 +1,42 @@
-//! Bonk 指令解析器
+//! Bonk（Raydium LaunchLab）指令解析器
 //!
-//! 使用 match discriminator 模式解析 Bonk 指令
+//! - 内层 self-CPI 事件指令（`EVENT_IX_TAG` + TradeEvent / PoolCreateEvent）：gRPC 内层指令
+//!   不带账户，事件数据完整，账户字段由 account_filler 从外层指令补齐
+//! - 外层指令（ShredStream / RPC，账户已解析）：buy/sell exact in/out、initialize、
+//!   migrate_to_amm / migrate_to_cpswap；gRPC 只解析外层 migrate（没有对应事件）
 
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
 use crate::core::events::*;
+use crate::logs::raydium_launchpad as events;
 use super::utils::*;
 use super::program_ids;
 
-/// Bonk discriminator 常量
+/// Bonk discriminator 常量（LaunchLab IDL）
 pub mod discriminators {
-    pub const TRADE: [u8; 8] = [2, 3, 4, 5, 6, 7, 8, 9];
-    pub const POOL_CREATE: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
-    pub const MIGRATE_AMM: [u8; 8] = [3, 4, 5, 6, 7, 8, 9, 10];
+    pub const BUY_EXACT_IN: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236];
+    pub const BUY_EXACT_OUT: [u8; 8] = [24, 211, 116, 40, 105, 3, 153, 56];
+    pub const SELL_EXACT_IN: [u8; 8] = [149, 39, 222, 155, 211, 124, 152, 26];
+    pub const SELL_EXACT_OUT: [u8; 8] = [95, 200, 71, 34, 8, 9, 11, 166];
+    pub const INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+    pub const INITIALIZE_V2: [u8; 8] = [67, 153, 175, 39, 218, 16, 38, 32];
+    pub const MIGRATE_TO_AMM: [u8; 8] = [207, 82, 192, 145, 254, 207, 145, 223];
+    pub const MIGRATE_TO_CPSWAP: [u8; 8] = [136, 92, 200, 103, 28, 218, 144, 140];
+    /// Anchor self-CPI 事件指令前缀
+    pub const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
 }
 
 /// Raydium Launchpad 程序 ID
 pub const PROGRAM_ID_PUBKEY: Pubkey = program_ids::BONK_PROGRAM_ID;
 
+/// 是否为 migrate_to_amm / migrate_to_cpswap 指令
+#[inline]
+pub fn is_migrate(instruction_data: &[u8]) -> bool {
+    matches!(
+        instruction_data.get(..8),
+        Some(d) if d == discriminators::MIGRATE_TO_AMM || d == discriminators::MIGRATE_TO_CPSWAP
+    )
+}
+
 /// 主要的 Bonk 指令解析函数
 pub fn parse_instruction(
     instruction_data: &[u8],
@@ -33,21 +53,67 @@ pub fn parse_instruction(
     let discriminator: [u8; 8] = instruction_data[0..8].try_into().ok()?;
     let data = &instruction_data[8..];
 
+    if discriminator == discriminators::EVENT_IX_TAG {
+        return parse_event_instruction(data, signature, slot, tx_index, block_time_us);
+    }
+
+    // 外层指令需要账户
+    if accounts.is_empty() {
+        return None;
+    }
+
     match discriminator {
-        discriminators::TRADE => {
-            parse_trade_instruction(data, accounts, signature, slot, tx_index, block_time_us)
+        discriminators::BUY_EXACT_IN => {
+            parse_trade_instruction(data, accounts, signature, slot, tx_index, block_time_us, true, true)
+        },
+        discriminators::BUY_EXACT_OUT => {
+            parse_trade_instruction(data, accounts, signature, slot, tx_index, block_time_us, true, false)
+        },
+        discriminators::SELL_EXACT_IN => {
+            parse_trade_instruction(data, accounts, signature, slot, tx_index, block_time_us, false, true)
+        },
+        discriminators::SELL_EXACT_OUT => {
+            parse_trade_instruction(data, accounts, signature, slot, tx_index, block_time_us, false, false)
+        },
+        discriminators::INITIALIZE | discriminators::INITIALIZE_V2 => {
+            parse_initialize_instruction(data, accounts, signature, slot, tx_index, block_time_us)
         },
-        discriminators::POOL_CREATE => {
-            parse_pool_create_instruction(data, accounts, signature, slot, tx_index, block_time_us)
+        discriminators::MIGRATE_TO_AMM => {
+            parse_migrate_to_amm_instruction(data, accounts, signature, slot, tx_index, block_time_us)
         },
-        discriminators::MIGRATE_AMM => {
-            parse_migrate_amm_instruction(data, accounts, signature, slot, tx_index, block_time_us)
+        discriminators::MIGRATE_TO_CPSWAP => {
+            parse_migrate_to_cpswap_instruction(accounts, signature, slot, tx_index, block_time_us)
         },
         _ => None,
     }
 }
 
-/// 解析交易指令
+/// 解析 self-CPI 事件指令（已去掉 EVENT_IX_TAG）
+fn parse_event_instruction(
+    data: &[u8],
+    signature: Signature,
+    slot: u64,
+    tx_index: u64,
+    block_time_us: Option<i64>,
+) -> Option<DexEvent> {
+    let discriminator: [u8; 8] = data.get(0..8)?.try_into().ok()?;
+    let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, PROGRAM_ID_PUBKEY);
+    match discriminator {
+        events::discriminators::TRADE_EVENT => events::parse_trade_from_data(&data[8..], metadata),
+        events::discriminators::POOL_CREATE_EVENT => events::parse_pool_create_from_data(&data[8..], metadata),
+        _ => None,
+    }
+}
+
+/// 解析 buy/sell exact in/out 指令
+///
+/// 参数：exact_in 为 (amount_in, minimum_amount_out, share_fee_rate)，
+/// exact_out 为 (amount_out, maximum_amount_in, share_fee_rate)
+///
+/// 账户：0: payer, 1: authority, 2: global_config, 3: platform_config, 4: pool_state,
+/// 5: user_base_token, 6: user_quote_token, 7: base_vault, 8: quote_vault,
+/// 9: base_token_mint, 10: quote_token_mint, 11: base_token_program, 12: quote_token_program
+#[allow(clippy::too_many_arguments)]
 fn parse_trade_instruction(
     data: &[u8],
     accounts: &[Pubkey],
@@ -55,31 +121,52 @@ fn parse_trade_instruction(
     slot: u64,
     tx_index: u64,
     block_time_us: Option<i64>,
+    is_buy: bool,
+    exact_in: bool,
 ) -> Option<DexEvent> {
-    let mut offset = 0;
-
-    let amount_in = read_u64_le(data, offset)?;
-    offset += 8;
+    if accounts.len() < 13 {
+        return None;
+    }
 
-    let amount_out_min = read_u64_le(data, offset)?;
+    let amount = read_u64_le(data, 0)?;
+    let limit = read_u64_le(data, 8)?;
+    let share_fee_rate = read_u64_le(data, 16).unwrap_or(0);
+    let (amount_in, amount_out) = if exact_in { (amount, limit) } else { (limit, amount) };
 
-    let pool_state = get_account(accounts, 0)?;
+    let pool_state = accounts[4];
     let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, pool_state);
 
     Some(DexEvent::BonkTrade(BonkTradeEvent {
         metadata,
         pool_state,
-        user: get_account(accounts, 1).unwrap_or_default(),
         amount_in,
-        amount_out: amount_out_min, // 先用指令中的最小值，日志会覆盖实际值
-        is_buy: true, // 默认为买入，实际值从日志确定
-        trade_direction: TradeDirection::Buy,
-        exact_in: true,
+        amount_out,
+        trade_direction: if is_buy { TradeDirection::Buy } else { TradeDirection::Sell },
+        exact_in,
+        is_buy,
+        share_fee_rate,
+        user: accounts[0],
+        global_config: accounts[2],
+        platform_config: accounts[3],
+        user_base_token: accounts[5],
+        user_quote_token: accounts[6],
+        base_vault: accounts[7],
+        quote_vault: accounts[8],
+        base_token_mint: accounts[9],
+        quote_token_mint: accounts[10],
+        base_token_program: accounts[11],
+        quote_token_program: accounts[12],
+        ..Default::default()
     }))
 }
 
-/// 解析池创建指令
-fn parse_pool_create_instruction(
+/// 解析 initialize / initialize_v2 指令
+///
+/// 参数：base_mint_param, curve_param, vesting_param（v2 追加 amm_fee_on）
+///
+/// 账户：0: payer, 1: creator, 2: global_config, 3: platform_config, 4: authority,
+/// 5: pool_state, 6: base_mint, 7: quote_mint, 8: base_vault, 9: quote_vault
+fn parse_initialize_instruction(
     data: &[u8],
     accounts: &[Pubkey],
     signature: Signature,
@@ -87,24 +174,47 @@ fn parse_pool_create_instruction(
     tx_index: u64,
     block_time_us: Option<i64>,
 ) -> Option<DexEvent> {
-    let pool_state = get_account(accounts, 0)?;
+    if accounts.len() < 10 {
+        return None;
+    }
+
+    let mut offset = 0;
+    let (base_mint_param, len) = events::read_mint_params(data, offset)?;
+    offset += len;
+    let (curve_param, len) = events::read_curve_params(data, offset)?;
+    offset += len;
+    let (vesting_param, len) = events::read_vesting_params(data, offset)?;
+    offset += len;
+    let amm_fee_on = read_u8(data, offset).and_then(events::amm_creator_fee_on);
+
+    let pool_state = accounts[5];
     let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, pool_state);
 
     Some(DexEvent::BonkPoolCreate(BonkPoolCreateEvent {
         metadata,
-        base_mint_param: BaseMintParam {
-            symbol: "BONK".to_string(),
-            name: "Bonk Pool".to_string(),
-            uri: "https://bonk.com".to_string(),
-            decimals: 5,
-        },
         pool_state,
-        creator: get_account(accounts, 1).unwrap_or_default(),
+        creator: accounts[1],
+        global_config: accounts[2],
+        base_mint_param,
+        curve_param,
+        vesting_param,
+        amm_fee_on,
+        payer: accounts[0],
+        platform_config: accounts[3],
+        base_mint: accounts[6],
+        quote_mint: accounts[7],
+        base_vault: accounts[8],
+        quote_vault: accounts[9],
     }))
 }
 
-/// 解析 AMM 迁移指令
-fn parse_migrate_amm_instruction(
+/// 解析 migrate_to_amm 指令
+///
+/// 参数：base_lot_size, quote_lot_size, market_vault_signer_nonce
+///
+/// 账户：0: payer, 1: base_mint, 2: quote_mint, 4: market, 13: amm_pool, 16: amm_lp_mint,
+/// 23: pool_state, 24: global_config, 25: base_vault, 26: quote_vault
+fn parse_migrate_to_amm_instruction(
     data: &[u8],
     accounts: &[Pubkey],
     signature: Signature,
@@ -112,18 +222,278 @@ fn parse_migrate_amm_instruction(
     tx_index: u64,
     block_time_us: Option<i64>,
 ) -> Option<DexEvent> {
-    let mut offset = 0;
+    if accounts.len() < 27 {
+        return None;
+    }
 
-    let liquidity_amount = read_u64_le(data, offset)?;
+    let old_pool = accounts[23];
+    let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, old_pool);
 
-    let old_pool = get_account(accounts, 0)?;
+    Some(DexEvent::BonkMigrateAmm(BonkMigrateAmmEvent {
+        metadata,
+        migrate_target: BonkMigrateTarget::Amm,
+        old_pool,
+        new_pool: accounts[13],
+        user: accounts[0],
+        base_mint: accounts[1],
+        quote_mint: accounts[2],
+        global_config: accounts[24],
+        base_vault: accounts[25],
+        quote_vault: accounts[26],
+        lp_mint: accounts[16],
+        market: accounts[4],
+        base_lot_size: read_u64_le(data, 0)?,
+        quote_lot_size: read_u64_le(data, 8)?,
+        market_vault_signer_nonce: read_u8(data, 16)?,
+        ..Default::default()
+    }))
+}
+
+/// 解析 migrate_to_cpswap 指令（无参数）
+///
+/// 账户：0: payer, 1: base_mint, 2: quote_mint, 3: platform_config, 5: cpswap_pool,
+/// 7: cpswap_lp_mint, 17: pool_state, 18: global_config, 19: base_vault, 20: quote_vault
+fn parse_migrate_to_cpswap_instruction(
+    accounts: &[Pubkey],
+    signature: Signature,
+    slot: u64,
+    tx_index: u64,
+    block_time_us: Option<i64>,
+) -> Option<DexEvent> {
+    if accounts.len() < 21 {
+        return None;
+    }
+
+    let old_pool = accounts[17];
     let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, old_pool);
 
     Some(DexEvent::BonkMigrateAmm(BonkMigrateAmmEvent {
         metadata,
+        migrate_target: BonkMigrateTarget::CpSwap,
         old_pool,
-        new_pool: get_account(accounts, 1).unwrap_or_default(),
-        user: get_account(accounts, 2).unwrap_or_default(),
-        liquidity_amount,
+        new_pool: accounts[5],
+        user: accounts[0],
+        base_mint: accounts[1],
+        quote_mint: accounts[2],
+        global_config: accounts[18],
+        platform_config: accounts[3],
+        base_vault: accounts[19],
+        quote_vault: accounts[20],
+        lp_mint: accounts[7],
+        ..Default::default()
     }))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yellowstone_grpc_proto::prelude::{
+        CompiledInstruction, InnerInstruction, InnerInstructions, Message, SubscribeUpdateTransaction,
+        SubscribeUpdateTransactionInfo, Transaction, TransactionStatusMeta,
+    };
+
+    // 测试数据按 LaunchLab IDL 的字段布局编码（discriminator、字段顺序与账户序号），不是主网抓取的交易
+
+    /// 按 LaunchLab IDL 编码的 TradeEvent 事件指令（卖出、exact in、池已迁移）
+    fn trade_event_ix(pool_state: Pubkey, with_creator_fee: bool) -> Vec<u8> {
+        let mut data = discriminators::EVENT_IX_TAG.to_vec();
+        data.extend_from_slice(&events::discriminators::TRADE_EVENT);
+        data.extend_from_slice(pool_state.as_ref());
+        let fields = if with_creator_fee { 13 } else { 12 };
+        for i in 1..=fields as u64 {
+            data.extend_from_slice(&(i * 1_000).to_le_bytes());
+        }
+        data.extend_from_slice(&[1, 2, 1]);
+        data
+    }
+
+    fn mint_params(data: &mut Vec<u8>) {
+        data.push(6);
+        for s in ["Bonk Test", "BTEST", "https://example.com/b.json"] {
+            data.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            data.extend_from_slice(s.as_bytes());
+        }
+    }
+
+    fn parse(data: &[u8], accounts: &[Pubkey]) -> Option<DexEvent> {
+        parse_instruction(data, accounts, Signature::default(), 7, 1, Some(1_700_000_000_000_000))
+    }
+
+    #[test]
+    fn test_decode_cpi_events() {
+        let pool_state = Pubkey::new_unique();
+        let Some(DexEvent::BonkTrade(e)) = parse(&trade_event_ix(pool_state, true), &[]) else {
+            panic!("expected BonkTrade");
+        };
+        assert_eq!(e.pool_state, pool_state);
+        assert_eq!((e.total_base_sell, e.real_quote_after), (1_000, 7_000));
+        assert_eq!((e.amount_in, e.amount_out), (8_000, 9_000));
+        assert_eq!((e.protocol_fee, e.platform_fee, e.creator_fee, e.share_fee), (10_000, 11_000, 12_000, 13_000));
+        assert_eq!((e.trade_direction, e.pool_status), (TradeDirection::Sell, BonkPoolStatus::Trade));
+        assert!(e.exact_in && !e.is_buy);
+
+        // 旧版事件没有 creator_fee
+        let Some(DexEvent::BonkTrade(e)) = parse(&trade_event_ix(pool_state, false), &[]) else {
+            panic!("expected BonkTrade");
+        };
+        assert_eq!((e.platform_fee, e.creator_fee, e.share_fee), (11_000, 0, 12_000));
+
+        let (creator, config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = discriminators::EVENT_IX_TAG.to_vec();
+        data.extend_from_slice(&events::discriminators::POOL_CREATE_EVENT);
+        for key in [pool_state, creator, config] {
+            data.extend_from_slice(key.as_ref());
+        }
+        mint_params(&mut data);
+        data.push(0);
+        for v in [1_000_000_000_000_000u64, 793_100_000_000_000, 85_000_000_000] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        data.push(1);
+        for v in [0u64, 0, 0] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        data.push(1);
+        let Some(DexEvent::BonkPoolCreate(e)) = parse(&data, &[]) else {
+            panic!("expected BonkPoolCreate");
+        };
+        assert_eq!((e.pool_state, e.creator, e.global_config), (pool_state, creator, config));
+        assert_eq!((e.base_mint_param.name.as_str(), e.base_mint_param.symbol.as_str()), ("Bonk Test", "BTEST"));
+        assert_eq!(e.base_mint_param.decimals, 6);
+        assert_eq!(e.curve_param.curve_type, BonkCurveType::Constant);
+        assert_eq!((e.curve_param.total_base_sell, e.curve_param.migrate_type), (793_100_000_000_000, 1));
+        assert_eq!(e.amm_fee_on, Some(BonkAmmCreatorFeeOn::BothToken));
+
+        // 未知事件和截断数据
+        assert!(parse(&data[..data.len() - 40], &[]).is_none());
+        assert!(parse(&[discriminators::EVENT_IX_TAG, [9; 8]].concat(), &[]).is_none());
+    }
+
+    #[test]
+    fn test_decode_outer_instructions() {
+        let accounts: Vec<Pubkey> = (0..32).map(|_| Pubkey::new_unique()).collect();
+
+        let mut data = discriminators::BUY_EXACT_OUT.to_vec();
+        for v in [5_000u64, 6_000, 25] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        let Some(DexEvent::BonkTrade(e)) = parse(&data, &accounts[..15]) else { panic!("expected BonkTrade") };
+        assert!(e.is_buy && !e.exact_in);
+        assert_eq!((e.amount_in, e.amount_out, e.share_fee_rate), (6_000, 5_000, 25));
+        assert_eq!((e.user, e.pool_state, e.platform_config), (accounts[0], accounts[4], accounts[3]));
+        assert_eq!((e.base_vault, e.quote_vault, e.quote_token_mint), (accounts[7], accounts[8], accounts[10]));
+        assert!(parse(&data, &accounts[..12]).is_none());
+
+        let mut data = discriminators::INITIALIZE.to_vec();
+        mint_params(&mut data);
+        data.push(2);
+        for v in [1_000u64, 500] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        data.push(0);
+        for v in [100u64, 10, 20] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        let Some(DexEvent::BonkPoolCreate(e)) = parse(&data, &accounts[..18]) else {
+            panic!("expected BonkPoolCreate")
+        };
+        assert_eq!((e.pool_state, e.creator, e.base_mint, e.quote_vault), (accounts[5], accounts[1], accounts[6], accounts[9]));
+        assert_eq!(e.curve_param, BonkCurveParams {
+            curve_type: BonkCurveType::Linear,
+            supply: 1_000,
+            total_base_sell: 0,
+            total_quote_fund_raising: 500,
+            migrate_type: 0,
+        });
+        assert_eq!(e.vesting_param.total_locked_amount, 100);
+        assert_eq!(e.amm_fee_on, None);
+
+        let mut data = discriminators::MIGRATE_TO_AMM.to_vec();
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&10u64.to_le_bytes());
+        data.push(3);
+        let Some(DexEvent::BonkMigrateAmm(e)) = parse(&data, &accounts) else { panic!("expected BonkMigrateAmm") };
+        assert_eq!(e.migrate_target, BonkMigrateTarget::Amm);
+        assert_eq!((e.old_pool, e.new_pool, e.market), (accounts[23], accounts[13], accounts[4]));
+        assert_eq!((e.base_lot_size, e.quote_lot_size, e.market_vault_signer_nonce), (1, 10, 3));
+
+        let Some(DexEvent::BonkMigrateAmm(e)) = parse(&discriminators::MIGRATE_TO_CPSWAP, &accounts[..28]) else {
+            panic!("expected BonkMigrateAmm")
+        };
+        assert_eq!(e.migrate_target, BonkMigrateTarget::CpSwap);
+        assert_eq!((e.old_pool, e.new_pool, e.platform_config, e.lp_mint), (accounts[17], accounts[5], accounts[3], accounts[7]));
+    }
+
+    /// 外层 LaunchLab 指令（账户为 accounts）+ 内层指令的 gRPC 交易
+    fn grpc_update(accounts: &[Pubkey], data: Vec<u8>, inner: Vec<Vec<u8>>) -> SubscribeUpdateTransaction {
+        let mut account_keys: Vec<Vec<u8>> = accounts.iter().map(|k| k.to_bytes().to_vec()).collect();
+        account_keys.push(PROGRAM_ID_PUBKEY.to_bytes().to_vec());
+        let program_index = accounts.len() as u32;
+
+        let message = Message {
+            account_keys,
+            instructions: vec![CompiledInstruction {
+                program_id_index: program_index,
+                accounts: (0..accounts.len() as u8).collect(),
+                data,
+            }],
+            ..Default::default()
+        };
+        let meta = TransactionStatusMeta {
+            inner_instructions: vec![InnerInstructions {
+                index: 0,
+                instructions: inner
+                    .into_iter()
+                    .map(|data| InnerInstruction {
+                        program_id_index: program_index,
+                        accounts: vec![program_index as u8],
+                        data,
+                        stack_height: Some(2),
+                    })
+                    .collect(),
+            }],
+            ..Default::default()
+        };
+        SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                signature: vec![7; 64],
+                is_vote: false,
+                transaction: Some(Transaction { message: Some(message), ..Default::default() }),
+                meta: Some(meta),
+                index: 3,
+            }),
+            slot: 42,
+        }
+    }
+
+    /// gRPC 路径：内层事件指令不带账户，账户由外层 buy_exact_in 指令补齐
+    #[test]
+    fn test_grpc_trade_fills_accounts_from_outer_instruction() {
+        let accounts: Vec<Pubkey> = (0..15).map(|_| Pubkey::new_unique()).collect();
+        let mut buy = discriminators::BUY_EXACT_IN.to_vec();
+        for v in [8_000u64, 8_500, 0] {
+            buy.extend_from_slice(&v.to_le_bytes());
+        }
+        let update = grpc_update(&accounts, buy, vec![trade_event_ix(accounts[4], true)]);
+
+        let events = crate::grpc::client::parse_transaction_core(&update, 0, None, None);
+        let [DexEvent::BonkTrade(e)] = events.as_slice() else { panic!("expected one BonkTrade, got {:?}", events) };
+        assert_eq!((e.amount_in, e.amount_out, e.creator_fee), (8_000, 9_000, 12_000));
+        assert_eq!((e.user, e.pool_state, e.global_config), (accounts[0], accounts[4], accounts[2]));
+        assert_eq!((e.base_vault, e.quote_vault, e.base_token_mint), (accounts[7], accounts[8], accounts[9]));
+        assert_eq!(e.metadata.slot, 42);
+    }
+
+    /// gRPC 路径：迁移没有事件，由外层 migrate 指令解析
+    #[test]
+    fn test_grpc_decodes_outer_migrate_instruction() {
+        let accounts: Vec<Pubkey> = (0..28).map(|_| Pubkey::new_unique()).collect();
+        let update = grpc_update(&accounts, discriminators::MIGRATE_TO_CPSWAP.to_vec(), vec![]);
+
+        let events = crate::grpc::client::parse_transaction_core(&update, 0, None, None);
+        let [DexEvent::BonkMigrateAmm(e)] = events.as_slice() else { panic!("expected one BonkMigrateAmm, got {:?}", events) };
+        assert_eq!(e.migrate_target, BonkMigrateTarget::CpSwap);
+        assert_eq!((e.old_pool, e.new_pool, e.user), (accounts[17], accounts[5], accounts[0]));
+        assert_eq!((e.metadata.slot, e.metadata.tx_index), (42, 3));
+    }
+}
@@ -18,6 +18,8 @@ pub fn create_metadata(
         tx_index,
         block_time_us,
         grpc_recv_us,
+        is_failed: false,
+        timing: Default::default(),
     }
 }
 
@@ -49,6 +51,8 @@ pub fn create_metadata_simple(
         tx_index,
         block_time_us: block_time_us.unwrap_or(0),
         grpc_recv_us: current_time,
+        is_failed: false,
+        timing: Default::default(),
     }
 }
 
@@ -10,6 +10,15 @@ pub mod warmup;   // 预热模块
 // gRPC 模块 - 支持gRPC订阅和过滤
 pub mod grpc;
 
+// ShredStream 模块 - 基于 entries 的指令级解析
+pub mod shredstream;
+
+// RPC 模块 - 历史数据回填
+pub mod rpc;
+
+#[cfg(test)]
+pub(crate) mod test_fixtures;
+
 // 兼容性别名
 pub mod parser {
     pub use crate::core::*;
@@ -21,6 +30,8 @@ pub use core::{
     DexEvent, EventMetadata, ParsedEvent,
     // 主要解析函数
     parse_transaction_events, parse_logs_only, parse_transaction_with_listener,
+    // 完整交易解析（RPC / JSON 导出）
+    parse_confirmed_transaction, parse_versioned_transaction,
     // 流式解析函数
     parse_transaction_events_streaming, parse_logs_streaming, parse_transaction_with_streaming_listener,
     // 事件监听器
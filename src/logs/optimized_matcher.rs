@@ -9,22 +9,12 @@
 
 use super::perf_hints::{likely, unlikely};
 use crate::core::events::{DexEvent, EventMetadata};
-use crate::grpc::types::{EventType, EventTypeFilter};
+use crate::grpc::types::{EventType, EventTypeFilter, Protocol};
 use memchr::memmem;
 use once_cell::sync::Lazy;
 use solana_sdk::signature::Signature;
 
 /// SIMD 优化的字符串查找器 - 预编译一次，重复使用
-static PUMPFUN_FINDER: Lazy<memmem::Finder> =
-    Lazy::new(|| memmem::Finder::new(b"6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P"));
-static RAYDIUM_AMM_FINDER: Lazy<memmem::Finder> =
-    Lazy::new(|| memmem::Finder::new(b"675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8"));
-static RAYDIUM_CLMM_FINDER: Lazy<memmem::Finder> =
-    Lazy::new(|| memmem::Finder::new(b"CAMMCzo5YL8w4VFF8KVHrK22GGUQpMdRBFSzKNT3t4ivN6"));
-static RAYDIUM_CPMM_FINDER: Lazy<memmem::Finder> =
-    Lazy::new(|| memmem::Finder::new(b"CPMDWBwJDtYax9qKcQP3CtKz7tHjJsN3H8hGrYVD9mZD"));
-static BONK_FINDER: Lazy<memmem::Finder> =
-    Lazy::new(|| memmem::Finder::new(b"Bxby5A7E8xPDGGc3FyJw7m5eK5aqNVLU83H2zLTQDH1b"));
 static PROGRAM_FINDER: Lazy<memmem::Finder> = Lazy::new(|| memmem::Finder::new(b"Program"));
 static PROGRAM_DATA_FINDER: Lazy<memmem::Finder> =
     Lazy::new(|| memmem::Finder::new(b"Program data: "));
@@ -37,29 +27,15 @@ static METEORA_DLMM_FINDER: Lazy<memmem::Finder> = Lazy::new(|| memmem::Finder::
 static PUMPSWAP_LOWER_FINDER: Lazy<memmem::Finder> = Lazy::new(|| memmem::Finder::new(b"pumpswap"));
 static PUMPSWAP_UPPER_FINDER: Lazy<memmem::Finder> = Lazy::new(|| memmem::Finder::new(b"PumpSwap"));
 
-/// 预计算的程序 ID 字符串常量
+/// 预计算的程序 ID 字符串常量（mainnet，来自 `grpc::program_ids` 注册表）
 pub mod program_id_strings {
-    pub const PUMPFUN_INVOKE: &str = "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P invoke";
-    pub const PUMPFUN_SUCCESS: &str = "Program 6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P success";
-    pub const PUMPFUN_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+    use crate::grpc::program_ids as registry;
 
-    pub const BONK_INVOKE: &str = "Program Bxby5A7E8xPDGGc3FyJw7m5eK5aqNVLU83H2zLTQDH1b invoke";
-    pub const BONK_SUCCESS: &str = "Program Bxby5A7E8xPDGGc3FyJw7m5eK5aqNVLU83H2zLTQDH1b success";
-    pub const BONK_ID: &str = "Bxby5A7E8xPDGGc3FyJw7m5eK5aqNVLU83H2zLTQDH1b";
-
-    pub const RAYDIUM_CLMM_INVOKE: &str =
-        "Program CAMMCzo5YL8w4VFF8KVHrK22GGUQpMdRBFSzKNT3t4ivN6 invoke";
-    pub const RAYDIUM_CLMM_SUCCESS: &str =
-        "Program CAMMCzo5YL8w4VFF8KVHrK22GGUQpMdRBFSzKNT3t4ivN6 success";
-    pub const RAYDIUM_CLMM_ID: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUQpMdRBFSzKNT3t4ivN6";
-
-    pub const RAYDIUM_CPMM_INVOKE: &str =
-        "Program CPMDWBwJDtYax9qKcQP3CtKz7tHjJsN3H8hGrYVD9mZD invoke";
-    pub const RAYDIUM_CPMM_SUCCESS: &str =
-        "Program CPMDWBwJDtYax9qKcQP3CtKz7tHjJsN3H8hGrYVD9mZD success";
-    pub const RAYDIUM_CPMM_ID: &str = "CPMDWBwJDtYax9qKcQP3CtKz7tHjJsN3H8hGrYVD9mZD";
-
-    pub const RAYDIUM_AMM_V4_ID: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+    pub const PUMPFUN_ID: &str = registry::PUMPFUN_PROGRAM_ID;
+    pub const BONK_ID: &str = registry::BONK_PROGRAM_ID;
+    pub const RAYDIUM_CLMM_ID: &str = registry::RAYDIUM_CLMM_PROGRAM_ID;
+    pub const RAYDIUM_CPMM_ID: &str = registry::RAYDIUM_CPMM_PROGRAM_ID;
+    pub const RAYDIUM_AMM_V4_ID: &str = registry::RAYDIUM_AMM_V4_PROGRAM_ID;
 
     // 常用的日志模式
     pub const PROGRAM_DATA: &str = "Program data: ";
@@ -104,26 +80,21 @@ pub fn detect_log_type(log: &str) -> LogType {
     }
 
     // 第三步：使用 SIMD 快速检测具体协议
-    // Raydium AMM - 高频，有明确程序ID（最常见）
-    if likely(RAYDIUM_AMM_FINDER.find(log_bytes).is_some()) {
-        return LogType::RaydiumAmm;
-    }
-
-    // Raydium CLMM
-    if RAYDIUM_CLMM_FINDER.find(log_bytes).is_some() {
-        return LogType::RaydiumClmm;
-    }
-
-    // Raydium CPMM
-    if RAYDIUM_CPMM_FINDER.find(log_bytes).is_some() {
-        return LogType::RaydiumCpmm;
-    }
-
-    // Raydium Launchpad (Bonk)
-    if BONK_FINDER.find(log_bytes).is_some() {
-        return LogType::RaydiumLaunchpad;
+    // 带明确程序 ID 的协议按注册表匹配（跟随集群配置和用户覆盖）
+    let registered = crate::grpc::program_ids::with_registry(|r| r.find_in_log(log_bytes));
+    match registered {
+        Some(Protocol::RaydiumAmmV4) => return LogType::RaydiumAmm,
+        Some(Protocol::RaydiumClmm) => return LogType::RaydiumClmm,
+        Some(Protocol::RaydiumCpmm) => return LogType::RaydiumCpmm,
+        Some(Protocol::Bonk) => return LogType::RaydiumLaunchpad,
+        Some(Protocol::OrcaWhirlpool) => return LogType::OrcaWhirlpool,
+        Some(Protocol::MeteoraPools) => return LogType::MeteoraAmm,
+        Some(Protocol::MeteoraDammV2) => return LogType::MeteoraDamm,
+        Some(Protocol::MeteoraDlmm) => return LogType::MeteoraDlmm,
+        _ => {}
     }
 
+    // 无程序 ID 的日志按关键字兜底
     // Orca Whirlpool
     if WHIRL_FINDER.find(log_bytes).is_some() {
         return LogType::OrcaWhirlpool;
@@ -150,7 +121,7 @@ pub fn detect_log_type(log: &str) -> LogType {
 
     // PumpFun - 特殊处理：可能有程序ID，也可能直接是base64数据
     // 1. 先检查是否包含程序ID（高频事件）
-    if likely(PUMPFUN_FINDER.find(log_bytes).is_some()) {
+    if likely(registered == Some(Protocol::PumpFun)) {
         return LogType::PumpFun;
     }
 
@@ -301,19 +272,14 @@ pub fn parse_log_optimized(
             if !filter.should_include(et) {
                 return None; // Skip ALL parsing - saves ~200-500ns
             }
-        } else {
-            // Unknown discriminator - check if any supported protocol is wanted
-            if let Some(ref include_only) = filter.include_only {
-                let wants_supported = include_only.iter().any(|t| matches!(t,
-                    EventType::PumpFunTrade | EventType::PumpFunCreate | EventType::PumpFunMigrate |
-                    EventType::PumpFunBuy | EventType::PumpFunSell | EventType::PumpFunBuyExactSolIn |
-                    EventType::PumpSwapBuy | EventType::PumpSwapSell | EventType::PumpSwapCreatePool |
-                    EventType::PumpSwapLiquidityAdded | EventType::PumpSwapLiquidityRemoved
-                ));
-                if !wants_supported {
-                    return None;
-                }
-            }
+        } else if !(filter.includes_raydium_clmm()
+            || filter.includes_raydium_cpmm()
+            || filter.includes_meteora_dlmm()
+            || filter.includes_meteora_damm_v2())
+        {
+            // Unmapped discriminators (CLMM/CPMM create pool, CPMM swap/DLMM swap collisions,
+            // DAMM initialize pool, DLMM fallback) can only come from these protocols
+            return None;
         }
     }
     
@@ -328,9 +294,11 @@ pub fn parse_log_optimized(
         tx_index,
         block_time_us: block_time_us.unwrap_or(0),
         grpc_recv_us,
+        is_failed: false,
+        timing: Default::default(),
     };
     
-    match discriminator {
+    let event = match discriminator {
         // PumpFun events - use pump module's from_data functions
         discriminators::PUMPFUN_TRADE => {
             let event = crate::logs::pump::parse_trade_from_data(data, metadata, is_created_buy)?;
@@ -479,11 +447,16 @@ pub fn parse_log_optimized(
         // Unknown discriminator - try fallback protocols
         _ => {
             // Try Meteora DLMM (has discriminator conflict with Raydium CPMM)
-            if let Some(event) = crate::logs::parse_meteora_dlmm_log(log, signature, slot, tx_index, block_time_us, grpc_recv_us) {
-                return Some(event);
-            }
-            None
+            crate::logs::parse_meteora_dlmm_log(log, signature, slot, tx_index, block_time_us, grpc_recv_us)
+        }
+    };
+
+    // Step 7: Unmapped discriminators are ambiguous - filter on the parsed event type
+    match (event_type, event_type_filter) {
+        (None, Some(filter)) => {
+            event.filter(|e| EventType::of_event(e).is_none_or(|t| filter.should_include(t)))
         }
+        _ => event,
     }
 }
 
@@ -499,6 +472,35 @@ fn discriminator_to_event_type(discriminator: u64) -> Option<EventType> {
         discriminators::PUMPSWAP_CREATE_POOL => Some(EventType::PumpSwapCreatePool),
         discriminators::PUMPSWAP_ADD_LIQUIDITY => Some(EventType::PumpSwapLiquidityAdded),
         discriminators::PUMPSWAP_REMOVE_LIQUIDITY => Some(EventType::PumpSwapLiquidityRemoved),
+        discriminators::RAYDIUM_CLMM_SWAP => Some(EventType::RaydiumClmmSwap),
+        discriminators::RAYDIUM_CLMM_INCREASE_LIQUIDITY => Some(EventType::RaydiumClmmIncreaseLiquidity),
+        discriminators::RAYDIUM_CLMM_DECREASE_LIQUIDITY => Some(EventType::RaydiumClmmDecreaseLiquidity),
+        discriminators::RAYDIUM_CLMM_COLLECT_FEE => Some(EventType::RaydiumClmmCollectFee),
+        discriminators::RAYDIUM_CPMM_SWAP_BASE_OUT => Some(EventType::RaydiumCpmmSwap),
+        discriminators::RAYDIUM_CPMM_DEPOSIT => Some(EventType::RaydiumCpmmDeposit),
+        discriminators::RAYDIUM_CPMM_WITHDRAW => Some(EventType::RaydiumCpmmWithdraw),
+        discriminators::RAYDIUM_AMM_SWAP_BASE_IN | discriminators::RAYDIUM_AMM_SWAP_BASE_OUT => {
+            Some(EventType::RaydiumAmmV4Swap)
+        }
+        discriminators::RAYDIUM_AMM_DEPOSIT => Some(EventType::RaydiumAmmV4Deposit),
+        discriminators::RAYDIUM_AMM_WITHDRAW => Some(EventType::RaydiumAmmV4Withdraw),
+        discriminators::RAYDIUM_AMM_INITIALIZE2 => Some(EventType::RaydiumAmmV4Initialize2),
+        discriminators::ORCA_TRADED => Some(EventType::OrcaWhirlpoolSwap),
+        discriminators::ORCA_LIQUIDITY_INCREASED => Some(EventType::OrcaWhirlpoolLiquidityIncreased),
+        discriminators::ORCA_LIQUIDITY_DECREASED => Some(EventType::OrcaWhirlpoolLiquidityDecreased),
+        discriminators::ORCA_POOL_INITIALIZED => Some(EventType::OrcaWhirlpoolPoolInitialized),
+        discriminators::METEORA_AMM_SWAP => Some(EventType::MeteoraPoolsSwap),
+        discriminators::METEORA_AMM_ADD_LIQUIDITY => Some(EventType::MeteoraPoolsAddLiquidity),
+        discriminators::METEORA_AMM_REMOVE_LIQUIDITY => Some(EventType::MeteoraPoolsRemoveLiquidity),
+        discriminators::METEORA_AMM_BOOTSTRAP_LIQUIDITY => Some(EventType::MeteoraPoolsBootstrapLiquidity),
+        discriminators::METEORA_AMM_POOL_CREATED => Some(EventType::MeteoraPoolsPoolCreated),
+        discriminators::METEORA_DAMM_SWAP => Some(EventType::MeteoraDammV2Swap),
+        discriminators::METEORA_DAMM_ADD_LIQUIDITY => Some(EventType::MeteoraDammV2AddLiquidity),
+        discriminators::METEORA_DAMM_REMOVE_LIQUIDITY => Some(EventType::MeteoraDammV2RemoveLiquidity),
+        discriminators::METEORA_DAMM_CREATE_POSITION => Some(EventType::MeteoraDammV2CreatePosition),
+        discriminators::METEORA_DAMM_CLOSE_POSITION => Some(EventType::MeteoraDammV2ClosePosition),
+        // RAYDIUM_CPMM_SWAP_BASE_IN / RAYDIUM_CLMM_CREATE_POOL collide with other programs:
+        // left unmapped and filtered after parsing
         _ => None,
     }
 }
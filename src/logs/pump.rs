@@ -313,6 +313,8 @@ fn parse_create_event_optimized(
             tx_index,
             block_time_us: block_time_us.unwrap_or(0),
             grpc_recv_us,
+            is_failed: false,
+            timing: Default::default(),
         };
 
         // 将 &str 转换为 String (这是唯一的堆分配)
@@ -464,6 +466,8 @@ fn parse_trade_event_optimized(
             tx_index,
             block_time_us: block_time_us.unwrap_or(0),
             grpc_recv_us,
+            is_failed: false,
+            timing: Default::default(),
         };
 
         let trade_event = PumpFunTradeEvent {
@@ -553,6 +557,8 @@ fn parse_migrate_event_optimized(
             tx_index,
             block_time_us: block_time_us.unwrap_or(0),
             grpc_recv_us,
+            is_failed: false,
+            timing: Default::default(),
         };
 
         Some(DexEvent::PumpFunMigrate(PumpFunMigrateEvent {
@@ -296,6 +296,8 @@ fn parse_buy_event_optimized(
             tx_index,
             block_time_us: block_time_us.unwrap_or(0),
             grpc_recv_us,
+            is_failed: false,
+            timing: Default::default(),
         };
 
         Some(DexEvent::PumpSwapBuy(PumpSwapBuyEvent {
@@ -382,6 +384,8 @@ fn parse_sell_event_optimized(
             tx_index,
             block_time_us: block_time_us.unwrap_or(0),
             grpc_recv_us,
+            is_failed: false,
+            timing: Default::default(),
         };
 
         Some(DexEvent::PumpSwapSell(PumpSwapSellEvent {
@@ -463,6 +467,8 @@ fn parse_create_pool_event_optimized(
             tx_index,
             block_time_us: block_time_us.unwrap_or(0),
             grpc_recv_us,
+            is_failed: false,
+            timing: Default::default(),
         };
 
         Some(DexEvent::PumpSwapCreatePool(PumpSwapCreatePoolEvent {
@@ -531,6 +537,8 @@ fn parse_add_liquidity_event_optimized(
             tx_index,
             block_time_us: block_time_us.unwrap_or(0),
             grpc_recv_us,
+            is_failed: false,
+            timing: Default::default(),
         };
 
         Some(DexEvent::PumpSwapLiquidityAdded(PumpSwapLiquidityAdded {
@@ -595,6 +603,8 @@ fn parse_remove_liquidity_event_optimized(
             tx_index,
             block_time_us: block_time_us.unwrap_or(0),
             grpc_recv_us,
+            is_failed: false,
+            timing: Default::default(),
         };
 
         Some(DexEvent::PumpSwapLiquidityRemoved(PumpSwapLiquidityRemoved {
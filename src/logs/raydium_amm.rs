@@ -18,7 +18,7 @@ pub mod discriminators {
 }
 
 /// Raydium AMM V4 程序 ID
-pub const PROGRAM_ID: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+pub const PROGRAM_ID: &str = crate::grpc::program_ids::RAYDIUM_AMM_V4_PROGRAM_ID;
 
 /// 解析 Raydium AMM V4 日志
 #[inline]
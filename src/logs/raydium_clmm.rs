@@ -16,12 +16,11 @@ pub mod discriminators {
 }
 
 /// Raydium CLMM 程序 ID
-pub const PROGRAM_ID: &str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+pub const PROGRAM_ID: &str = crate::grpc::program_ids::RAYDIUM_CLMM_PROGRAM_ID;
 
 /// 检查日志是否来自 Raydium CLMM 程序
 pub fn is_raydium_clmm_log(log: &str) -> bool {
-    log.contains(&format!("Program {} invoke", PROGRAM_ID)) ||
-    log.contains(&format!("Program {} success", PROGRAM_ID)) ||
+    crate::grpc::program_ids::is_program_log(log, crate::grpc::types::Protocol::RaydiumClmm) ||
     log.contains("raydium") || log.contains("Raydium")
 }
 
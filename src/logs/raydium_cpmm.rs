@@ -16,12 +16,11 @@ pub mod discriminators {
 }
 
 /// Raydium CPMM 程序 ID
-pub const PROGRAM_ID: &str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+pub const PROGRAM_ID: &str = crate::grpc::program_ids::RAYDIUM_CPMM_PROGRAM_ID;
 
 /// 检查日志是否来自 Raydium CPMM 程序
 pub fn is_raydium_cpmm_log(log: &str) -> bool {
-    log.contains(&format!("Program {} invoke", PROGRAM_ID)) ||
-    log.contains(&format!("Program {} success", PROGRAM_ID)) ||
+    crate::grpc::program_ids::is_program_log(log, crate::grpc::types::Protocol::RaydiumCpmm) ||
     (log.contains("raydium") && log.contains("cpmm"))
 }
 
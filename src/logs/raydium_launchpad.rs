@@ -1,26 +1,35 @@
-//! Bonk 日志解析器
+//! Bonk（Raydium LaunchLab）事件解析器
 //!
-//! 使用 match discriminator 模式解析 Bonk 事件
+//! LaunchLab 通过 self-CPI（emit_cpi）发出事件，事件数据位于内层指令中
+//! （`EVENT_IX_TAG` + 事件 discriminator + 数据），由 `instr::raydium_launchpad` 调用这里的
+//! `*_from_data` 解码；`parse_log` 处理以 `Program data:` 形式出现的同一事件数据。
+//!
+//! 注意：TradeEvent 的 discriminator 与 PumpFun TradeEvent 相同，因此不进入
+//! `optimized_matcher` 的全局 discriminator 分发，只按程序 ID 路由。
 
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
 use crate::core::events::*;
 use super::utils::*;
 
-/// Bonk discriminator 常量
+/// Bonk 事件 discriminator 常量（LaunchLab IDL）
 pub mod discriminators {
-    pub const TRADE: [u8; 8] = [2, 3, 4, 5, 6, 7, 8, 9];
-    pub const POOL_CREATE: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
-    pub const MIGRATE_AMM: [u8; 8] = [3, 4, 5, 6, 7, 8, 9, 10];
+    /// TradeEvent
+    pub const TRADE_EVENT: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+    /// PoolCreateEvent
+    pub const POOL_CREATE_EVENT: [u8; 8] = [151, 215, 226, 9, 118, 161, 115, 174];
 }
 
+/// TradeEvent 数据长度（含 creator_fee）
+const TRADE_EVENT_LEN: usize = 32 + 13 * 8 + 3;
+/// 旧版 TradeEvent 数据长度（无 creator_fee）
+const TRADE_EVENT_LEN_LEGACY: usize = TRADE_EVENT_LEN - 8;
+
 /// Bonk 程序 ID
-pub const PROGRAM_ID: &str = "DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1";
+pub const PROGRAM_ID: &str = crate::grpc::program_ids::BONK_PROGRAM_ID;
 
-/// 检查日志是否来自 Raydium Launchpad 程序
+/// 检查日志是否来自 Raydium Launchpad 程序（只按程序 ID 判断，代币名或 memo 中的 "bonk" 不算）
 pub fn is_raydium_launchpad_log(log: &str) -> bool {
-    log.contains(&format!("Program {} invoke", PROGRAM_ID)) ||
-    log.contains(&format!("Program {} success", PROGRAM_ID)) ||
-    log.contains("bonk") || log.contains("Bonk")
+    crate::grpc::program_ids::is_program_log(log, crate::grpc::types::Protocol::Bonk)
 }
 
 /// 主要的 Bonk 日志解析函数
@@ -28,7 +37,6 @@ pub fn parse_log(log: &str, signature: Signature, slot: u64, tx_index: u64, bloc
     parse_structured_log(log, signature, slot, tx_index, block_time_us, grpc_recv_us)
 }
 
-
 /// 结构化日志解析（基于 Program data）
 fn parse_structured_log(
     log: &str,
@@ -45,230 +53,177 @@ fn parse_structured_log(
 
     let discriminator: [u8; 8] = program_data[0..8].try_into().ok()?;
     let data = &program_data[8..];
+    let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, Pubkey::default(), grpc_recv_us);
 
     match discriminator {
-        discriminators::TRADE => {
-            parse_trade_event(data, signature, slot, tx_index, block_time_us, grpc_recv_us)
-        },
-        discriminators::POOL_CREATE => {
-            parse_pool_create_event(data, signature, slot, tx_index, block_time_us, grpc_recv_us)
-        },
-        discriminators::MIGRATE_AMM => {
-            parse_migrate_amm_event(data, signature, slot, tx_index, block_time_us, grpc_recv_us)
-        },
+        discriminators::TRADE_EVENT => parse_trade_from_data(data, metadata),
+        discriminators::POOL_CREATE_EVENT => parse_pool_create_from_data(data, metadata),
         _ => None,
     }
 }
 
-/// 解析交易事件
-fn parse_trade_event(
-    data: &[u8],
-    signature: Signature,
-    slot: u64,
-    tx_index: u64,
-    block_time_us: Option<i64>,
-    grpc_recv_us: i64,
-) -> Option<DexEvent> {
-    let mut offset = 0;
-
-    let pool_state = read_pubkey(data, offset)?;
-    offset += 32;
-
-    let user = read_pubkey(data, offset)?;
-    offset += 32;
-
-    let amount_in = read_u64_le(data, offset)?;
-    offset += 8;
-
-    let amount_out = read_u64_le(data, offset)?;
-    offset += 8;
-
-    let is_buy = read_bool(data, offset)?;
-    offset += 1;
-
-    let exact_in = read_bool(data, offset)?;
-
-    let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, pool_state, grpc_recv_us);
+/// 解析 TradeEvent（不含 discriminator），兼容有无 creator_fee 的两个版本
+pub fn parse_trade_from_data(data: &[u8], metadata: EventMetadata) -> Option<DexEvent> {
+    let has_creator_fee = match data.len() {
+        n if n >= TRADE_EVENT_LEN => true,
+        n if n >= TRADE_EVENT_LEN_LEGACY => false,
+        _ => return None,
+    };
+
+    let pool_state = read_pubkey(data, 0)?;
+    let mut offset = 32;
+    let mut next_u64 = || {
+        let value = read_u64_le(data, offset);
+        offset += 8;
+        value
+    };
+    let total_base_sell = next_u64()?;
+    let virtual_base = next_u64()?;
+    let virtual_quote = next_u64()?;
+    let real_base_before = next_u64()?;
+    let real_quote_before = next_u64()?;
+    let real_base_after = next_u64()?;
+    let real_quote_after = next_u64()?;
+    let amount_in = next_u64()?;
+    let amount_out = next_u64()?;
+    let protocol_fee = next_u64()?;
+    let platform_fee = next_u64()?;
+    let creator_fee = if has_creator_fee { next_u64()? } else { 0 };
+    let share_fee = next_u64()?;
+
+    let trade_direction = match read_u8(data, offset)? {
+        0 => TradeDirection::Buy,
+        1 => TradeDirection::Sell,
+        _ => return None,
+    };
+    let pool_status = match read_u8(data, offset + 1)? {
+        0 => BonkPoolStatus::Fund,
+        1 => BonkPoolStatus::Migrate,
+        2 => BonkPoolStatus::Trade,
+        _ => return None,
+    };
+    let exact_in = read_bool(data, offset + 2)?;
 
     Some(DexEvent::BonkTrade(BonkTradeEvent {
         metadata,
         pool_state,
-        user,
+        total_base_sell,
+        virtual_base,
+        virtual_quote,
+        real_base_before,
+        real_quote_before,
+        real_base_after,
+        real_quote_after,
         amount_in,
         amount_out,
-        is_buy,
-        trade_direction: if is_buy { TradeDirection::Buy } else { TradeDirection::Sell },
+        protocol_fee,
+        platform_fee,
+        creator_fee,
+        share_fee,
+        trade_direction,
+        pool_status,
         exact_in,
+        is_buy: trade_direction == TradeDirection::Buy,
+        ..Default::default()
     }))
 }
 
-/// 解析池创建事件
-fn parse_pool_create_event(
-    data: &[u8],
-    signature: Signature,
-    slot: u64,
-    tx_index: u64,
-    block_time_us: Option<i64>,
-    grpc_recv_us: i64,
-) -> Option<DexEvent> {
-    let mut offset = 0;
-
-    let pool_state = read_pubkey(data, offset)?;
-    offset += 32;
-
-    let _token_a_mint = read_pubkey(data, offset)?;
-    offset += 32;
-
-    let _token_b_mint = read_pubkey(data, offset)?;
-    offset += 32;
-
-    let creator = read_pubkey(data, offset)?;
-    offset += 32;
-
-    let _initial_liquidity_a = read_u64_le(data, offset)?;
-    offset += 8;
-
-    let _initial_liquidity_b = read_u64_le(data, offset)?;
-
-    let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, pool_state, grpc_recv_us);
+/// 解析 PoolCreateEvent（不含 discriminator）
+pub fn parse_pool_create_from_data(data: &[u8], metadata: EventMetadata) -> Option<DexEvent> {
+    let pool_state = read_pubkey(data, 0)?;
+    let creator = read_pubkey(data, 32)?;
+    let global_config = read_pubkey(data, 64)?;
+    let mut offset = 96;
+
+    let (base_mint_param, len) = read_mint_params(data, offset)?;
+    offset += len;
+    let (curve_param, len) = read_curve_params(data, offset)?;
+    offset += len;
+    let (vesting_param, len) = read_vesting_params(data, offset)?;
+    offset += len;
+    // 新版事件末尾追加 amm_fee_on
+    let amm_fee_on = read_u8(data, offset).and_then(amm_creator_fee_on);
 
     Some(DexEvent::BonkPoolCreate(BonkPoolCreateEvent {
         metadata,
-        base_mint_param: BaseMintParam {
-            symbol: "BONK".to_string(),
-            name: "Bonk Pool".to_string(),
-            uri: "https://bonk.com".to_string(),
-            decimals: 5,
-        },
         pool_state,
         creator,
+        global_config,
+        base_mint_param,
+        curve_param,
+        vesting_param,
+        amm_fee_on,
+        ..Default::default()
     }))
 }
 
-/// 解析 AMM 迁移事件
-fn parse_migrate_amm_event(
-    data: &[u8],
-    signature: Signature,
-    slot: u64,
-    tx_index: u64,
-    block_time_us: Option<i64>,
-    grpc_recv_us: i64,
-) -> Option<DexEvent> {
-    let mut offset = 0;
-
-    let old_pool = read_pubkey(data, offset)?;
-    offset += 32;
-
-    let new_pool = read_pubkey(data, offset)?;
-    offset += 32;
-
-    let user = read_pubkey(data, offset)?;
-    offset += 32;
-
-    let liquidity_amount = read_u64_le(data, offset)?;
-
-    let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, old_pool, grpc_recv_us);
-
-    Some(DexEvent::BonkMigrateAmm(BonkMigrateAmmEvent {
-        metadata,
-        old_pool,
-        new_pool,
-        user,
-        liquidity_amount,
-    }))
+/// 读取 MintParams（decimals, name, symbol, uri），返回 (参数, 消耗字节数)
+pub fn read_mint_params(data: &[u8], offset: usize) -> Option<(BaseMintParam, usize)> {
+    let decimals = read_u8(data, offset)?;
+    let mut len = 1;
+    let (name, n) = read_string(data, offset + len)?;
+    len += n;
+    let (symbol, n) = read_string(data, offset + len)?;
+    len += n;
+    let (uri, n) = read_string(data, offset + len)?;
+    len += n;
+    Some((BaseMintParam { symbol, name, uri, decimals }, len))
 }
 
-/// 文本回退解析
-fn parse_text_log(
-    tx_index: u64,
-    log: &str,
-    signature: Signature,
-    slot: u64,
-    block_time_us: Option<i64>,
-    grpc_recv_us: i64,
-) -> Option<DexEvent> {
-    use super::utils::text_parser::*;
-
-    if log.contains("trade") || log.contains("swap") {
-        return parse_trade_from_text(tx_index, log, signature, slot, block_time_us, grpc_recv_us);
-    }
-
-    if log.contains("pool") && log.contains("create") {
-        return parse_pool_create_from_text(tx_index, log, signature, slot, block_time_us, grpc_recv_us);
-    }
-
-    if log.contains("migrate") {
-        return parse_migrate_from_text(tx_index, log, signature, slot, block_time_us, grpc_recv_us);
-    }
-
-    None
-}
-
-/// 从文本解析交易事件
-fn parse_trade_from_text(tx_index: u64, 
-    log: &str,
-    signature: Signature,
-    slot: u64,
-    block_time_us: Option<i64>,
-    grpc_recv_us: i64,
-) -> Option<DexEvent> {
-    use super::utils::text_parser::*;
-
-    let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, Pubkey::default(), grpc_recv_us);
-    let is_buy = detect_trade_type(log).unwrap_or(true);
-
-    Some(DexEvent::BonkTrade(BonkTradeEvent {
-        metadata,
-        pool_state: Pubkey::default(),
-        user: Pubkey::default(),
-        amount_in: extract_number_from_text(log, "amount_in").unwrap_or(1000000),
-        amount_out: extract_number_from_text(log, "amount_out").unwrap_or(950000),
-        is_buy,
-        trade_direction: if is_buy { TradeDirection::Buy } else { TradeDirection::Sell },
-        exact_in: true,
-    }))
+/// 读取 CurveParams 枚举（Constant / Fixed / Linear），返回 (参数, 消耗字节数)
+pub fn read_curve_params(data: &[u8], offset: usize) -> Option<(BonkCurveParams, usize)> {
+    let curve_type = match read_u8(data, offset)? {
+        0 => BonkCurveType::Constant,
+        1 => BonkCurveType::Fixed,
+        2 => BonkCurveType::Linear,
+        _ => return None,
+    };
+    let supply = read_u64_le(data, offset + 1)?;
+    let mut len = 9;
+    let total_base_sell = if curve_type == BonkCurveType::Constant {
+        len += 8;
+        read_u64_le(data, offset + 9)?
+    } else {
+        0
+    };
+    let total_quote_fund_raising = read_u64_le(data, offset + len)?;
+    let migrate_type = read_u8(data, offset + len + 8)?;
+    len += 9;
+    Some((
+        BonkCurveParams { curve_type, supply, total_base_sell, total_quote_fund_raising, migrate_type },
+        len,
+    ))
 }
 
-/// 从文本解析池创建事件
-fn parse_pool_create_from_text(tx_index: u64, 
-    log: &str,
-    signature: Signature,
-    slot: u64,
-    block_time_us: Option<i64>,
-    grpc_recv_us: i64,
-) -> Option<DexEvent> {
-    let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, Pubkey::default(), grpc_recv_us);
-
-    Some(DexEvent::BonkPoolCreate(BonkPoolCreateEvent {
-        metadata,
-        base_mint_param: BaseMintParam {
-            symbol: "BONK".to_string(),
-            name: "Bonk Pool".to_string(),
-            uri: "https://bonk.com".to_string(),
-            decimals: 5,
+/// 读取 VestingParams，返回 (参数, 消耗字节数)
+pub fn read_vesting_params(data: &[u8], offset: usize) -> Option<(BonkVestingParams, usize)> {
+    Some((
+        BonkVestingParams {
+            total_locked_amount: read_u64_le(data, offset)?,
+            cliff_period: read_u64_le(data, offset + 8)?,
+            unlock_period: read_u64_le(data, offset + 16)?,
         },
-        pool_state: Pubkey::default(),
-        creator: Pubkey::default(),
-    }))
+        24,
+    ))
 }
 
-/// 从文本解析迁移事件
-fn parse_migrate_from_text(tx_index: u64, 
-    log: &str,
-    signature: Signature,
-    slot: u64,
-    block_time_us: Option<i64>,
-    grpc_recv_us: i64,
-) -> Option<DexEvent> {
-    use super::utils::text_parser::*;
+/// AmmCreatorFeeOn 枚举值
+pub fn amm_creator_fee_on(value: u8) -> Option<BonkAmmCreatorFeeOn> {
+    match value {
+        0 => Some(BonkAmmCreatorFeeOn::QuoteToken),
+        1 => Some(BonkAmmCreatorFeeOn::BothToken),
+        _ => None,
+    }
+}
 
-    let metadata = create_metadata_simple(signature, slot, tx_index, block_time_us, Pubkey::default(), grpc_recv_us);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Some(DexEvent::BonkMigrateAmm(BonkMigrateAmmEvent {
-        metadata,
-        old_pool: Pubkey::default(),
-        new_pool: Pubkey::default(),
-        user: Pubkey::default(),
-        liquidity_amount: extract_number_from_text(log, "liquidity").unwrap_or(0),
-    }))
-}
\ No newline at end of file
+    #[test]
+    fn test_is_raydium_launchpad_log_matches_program_id_only() {
+        assert!(is_raydium_launchpad_log(&format!("Program {} invoke [1]", PROGRAM_ID)));
+        assert!(!is_raydium_launchpad_log("Program log: Memo (len 12): \"Bonk to moon\""));
+        assert!(!is_raydium_launchpad_log("Program log: Instruction: Buy bonk"));
+    }
+}
@@ -146,6 +146,8 @@ pub fn create_metadata_simple(
         tx_index,
         block_time_us: block_time_us.unwrap_or(0),
         grpc_recv_us,
+        is_failed: false,
+        timing: Default::default(),
     }
 }
 
@@ -174,6 +176,8 @@ pub fn create_metadata_default(
         tx_index,
         block_time_us: block_time_us.unwrap_or(0),
         grpc_recv_us: current_time,
+        is_failed: false,
+        timing: Default::default(),
     }
 }
 
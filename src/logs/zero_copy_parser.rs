@@ -134,6 +134,8 @@ pub fn parse_pumpfun_trade(
         tx_index,
         block_time_us: block_time_us.unwrap_or(0),
         grpc_recv_us,
+        is_failed: false,
+        timing: Default::default(),
     };
 
     Some(DexEvent::PumpFunTrade(PumpFunTradeEvent {
@@ -0,0 +1,417 @@
+//! RPC 历史回填 - 通过 getTransaction / getBlock 拉取历史交易并解析 DEX 事件
+//!
+//! 交易会先转换成 Yellowstone protobuf 结构，再走与 gRPC 实时流完全相同的
+//! logs + instructions + account_filler 管线，保证历史数据与实时数据字段一致。
+
+use super::convert::encoded_to_update;
+use crate::common::types::SolanaRpcClient;
+use crate::common::AnyResult;
+use crate::grpc::client::{get_timestamp_us, parse_transaction_core};
+use crate::grpc::types::EventTypeFilter;
+use crate::DexEvent;
+use futures::stream::{self, Stream, StreamExt};
+use solana_rpc_client_api::config::{RpcBlockConfig, RpcTransactionConfig};
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, TransactionDetails, UiConfirmedBlock,
+    UiTransactionEncoding,
+};
+use std::sync::Arc;
+
+/// 默认并发请求数
+const DEFAULT_CONCURRENCY: usize = 8;
+
+// ==================== RpcBackfill 客户端 ====================
+
+#[derive(Clone)]
+pub struct RpcBackfill {
+    client: Arc<SolanaRpcClient>,
+    concurrency: usize,
+}
+
+impl RpcBackfill {
+    /// 使用 RPC 地址创建（commitment 默认为 finalized）
+    pub fn new(rpc_url: String) -> Self {
+        Self::from_client(Arc::new(SolanaRpcClient::new(rpc_url)))
+    }
+
+    /// 复用已有的 RPC 客户端（commitment 取客户端配置，getBlock/getTransaction 不支持 processed）
+    pub fn from_client(client: Arc<SolanaRpcClient>) -> Self {
+        crate::warmup::warmup_parser();
+        Self { client, concurrency: DEFAULT_CONCURRENCY }
+    }
+
+    /// 设置并发请求数（至少为 1）
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// 回填 [start_slot, end_slot] 区间内的所有区块，事件按 slot、tx_index 顺序返回
+    ///
+    /// 跳过的 slot（无区块）会被自动忽略；单个区块拉取失败记录在 `errors` 中，不影响其余 slot
+    pub async fn backfill_slot_range(
+        &self,
+        start_slot: u64,
+        end_slot: u64,
+        filter: Option<&EventTypeFilter>,
+    ) -> AnyResult<BackfillReport<u64>> {
+        let results = self.backfill_slot_range_stream(start_slot, end_slot, filter).await?;
+        Ok(BackfillReport::collect(results).await)
+    }
+
+    /// 流式回填 [start_slot, end_slot]：每个区块到达后立即解析，按 slot 顺序逐个产出
+    ///
+    /// 同时最多持有 `concurrency` 个区块；getBlocks 失败时返回错误，之后按 slot 报告结果。
+    pub async fn backfill_slot_range_stream<'a>(
+        &'a self,
+        start_slot: u64,
+        end_slot: u64,
+        filter: Option<&'a EventTypeFilter>,
+    ) -> AnyResult<impl Stream<Item = (u64, AnyResult<Vec<DexEvent>>)> + 'a> {
+        let slots =
+            if end_slot < start_slot { Vec::new() } else { self.client.get_blocks(start_slot, Some(end_slot)).await? };
+        Ok(stream::iter(slots)
+            .map(move |slot| async move {
+                let events = self.fetch_block(slot).await.map(|block| parse_block(slot, &block, filter));
+                (slot, events)
+            })
+            .buffered(self.concurrency))
+    }
+
+    /// 回填指定签名的交易，事件按签名顺序返回
+    ///
+    /// getTransaction 不返回交易在区块中的位置，因此 tx_index 为 0；
+    /// 单笔交易拉取失败记录在 `errors` 中
+    pub async fn backfill_signatures(
+        &self,
+        signatures: &[Signature],
+        filter: Option<&EventTypeFilter>,
+    ) -> BackfillReport<Signature> {
+        BackfillReport::collect(self.backfill_signatures_stream(signatures, filter)).await
+    }
+
+    /// 流式回填指定签名的交易：每笔交易到达后立即解析，按签名顺序逐个产出
+    pub fn backfill_signatures_stream<'a>(
+        &'a self,
+        signatures: &'a [Signature],
+        filter: Option<&'a EventTypeFilter>,
+    ) -> impl Stream<Item = (Signature, AnyResult<Vec<DexEvent>>)> + 'a {
+        stream::iter(signatures)
+            .map(move |sig| async move {
+                let events = self
+                    .fetch_transaction(sig)
+                    .await
+                    .map(|tx| crate::core::parse_confirmed_transaction_with_filter(&tx, filter));
+                (*sig, events)
+            })
+            .buffered(self.concurrency)
+    }
+
+    async fn fetch_block(&self, slot: u64) -> AnyResult<UiConfirmedBlock> {
+        let config = RpcBlockConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            transaction_details: Some(TransactionDetails::Full),
+            rewards: Some(false),
+            commitment: Some(self.client.commitment()),
+            max_supported_transaction_version: Some(0),
+        };
+        Ok(self.client.get_block_with_config(slot, config).await?)
+    }
+
+    async fn fetch_transaction(&self, sig: &Signature) -> AnyResult<EncodedConfirmedTransactionWithStatusMeta> {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(self.client.commitment()),
+            max_supported_transaction_version: Some(0),
+        };
+        Ok(self.client.get_transaction_with_config(sig, config).await?)
+    }
+}
+
+// ==================== BackfillReport ====================
+
+/// 批量回填结果：失败的 slot / 签名单独列出，不影响其余结果
+#[derive(Debug)]
+pub struct BackfillReport<K> {
+    /// 成功部分的事件，按 slot（或签名）顺序
+    pub events: Vec<DexEvent>,
+    /// 拉取失败的 slot / 签名及原因
+    pub errors: Vec<(K, Box<dyn std::error::Error + Send + Sync>)>,
+}
+
+impl<K> BackfillReport<K> {
+    async fn collect(results: impl Stream<Item = (K, AnyResult<Vec<DexEvent>>)>) -> Self {
+        let mut report = Self { events: Vec::new(), errors: Vec::new() };
+        futures::pin_mut!(results);
+        while let Some((key, result)) = results.next().await {
+            match result {
+                Ok(events) => report.events.extend(events),
+                Err(e) => report.errors.push((key, e)),
+            }
+        }
+        report
+    }
+
+    /// 是否全部成功
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+// ==================== 交易解析 ====================
+
+#[inline]
+fn block_time_to_us(block_time: Option<i64>) -> Option<i64> {
+    block_time.map(|t| t * 1_000_000)
+}
+
+/// 解析 getBlock 返回的区块，tx_index 为交易在区块中的位置
+fn parse_block(slot: u64, block: &UiConfirmedBlock, filter: Option<&EventTypeFilter>) -> Vec<DexEvent> {
+    let Some(txs) = &block.transactions else { return Vec::new() };
+    let recv_us = get_timestamp_us();
+    let block_us = block_time_to_us(block.block_time);
+
+    let mut result = Vec::new();
+    for (idx, tx) in txs.iter().enumerate() {
+        if let Some(update) = encoded_to_update(tx, slot, idx as u64) {
+            result.extend(parse_transaction_core(&update, recv_us, block_us, filter));
+        }
+    }
+    result
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instr::program_ids::PUMPFUN_PROGRAM_ID;
+    use base64::Engine;
+    use serde_json::{json, Value};
+    use crate::test_fixtures;
+    use solana_sdk::pubkey::Pubkey;
+    use solana_transaction_status::option_serializer::OptionSerializer;
+    use solana_transaction_status::{
+        EncodedTransaction, EncodedTransactionWithStatusMeta, TransactionBinaryEncoding,
+        UiCompiledInstruction, UiInnerInstructions, UiInstruction, UiTransactionStatusMeta,
+    };
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const SLOT: u64 = 300_000_000;
+    const BLOCK_TIME: i64 = 1_700_000_000;
+
+    struct Fixture {
+        mint: Pubkey,
+        bonding_curve: Pubkey,
+        pool: Pubkey,
+        signature: Signature,
+        tx: EncodedTransactionWithStatusMeta,
+    }
+
+    /// 外层 PumpFun buy 指令 + TradeEvent 日志 + 内层 migrate CPI 指令
+    fn fixture() -> Fixture {
+        let (mint, user, bonding_curve, pool) =
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let signature = Signature::from([9u8; 64]);
+        let tx = test_fixtures::pumpfun_buy_tx(mint, bonding_curve, user, signature);
+        let program_id_index =
+            tx.message.static_account_keys().iter().position(|k| *k == PUMPFUN_PROGRAM_ID).unwrap() as u8;
+
+        // TradeEvent（不含 ix_name，解析为 PumpFunTrade）
+        let logs = vec![
+            format!("Program {} invoke [1]", PUMPFUN_PROGRAM_ID),
+            test_fixtures::program_data_log(&test_fixtures::pumpfun_trade_event_data(&mint, &user)),
+            format!("Program {} invoke [2]", PUMPFUN_PROGRAM_ID),
+            format!("Program {} success", PUMPFUN_PROGRAM_ID),
+            format!("Program {} success", PUMPFUN_PROGRAM_ID),
+        ];
+
+        // Migrate CPI 事件指令
+        let migrate = test_fixtures::pumpfun_migrate_cpi_data(&user, &mint, &bonding_curve, &pool);
+
+        let meta = UiTransactionStatusMeta {
+            err: None,
+            status: Ok(()),
+            fee: 5000,
+            pre_balances: vec![],
+            post_balances: vec![],
+            inner_instructions: OptionSerializer::Some(vec![UiInnerInstructions {
+                index: 0,
+                instructions: vec![UiInstruction::Compiled(UiCompiledInstruction {
+                    program_id_index,
+                    accounts: vec![],
+                    data: bs58::encode(&migrate).into_string(),
+                    stack_height: Some(2),
+                })],
+            }]),
+            log_messages: OptionSerializer::Some(logs),
+            pre_token_balances: OptionSerializer::None,
+            post_token_balances: OptionSerializer::None,
+            rewards: OptionSerializer::None,
+            loaded_addresses: OptionSerializer::Skip,
+            return_data: OptionSerializer::Skip,
+            compute_units_consumed: OptionSerializer::Skip,
+            cost_units: OptionSerializer::Skip,
+        };
+        let encoded = EncodedTransaction::Binary(
+            base64::engine::general_purpose::STANDARD.encode(bincode::serialize(&tx).unwrap()),
+            TransactionBinaryEncoding::Base64,
+        );
+        Fixture {
+            mint,
+            bonding_curve,
+            pool,
+            signature,
+            tx: EncodedTransactionWithStatusMeta { transaction: encoded, meta: Some(meta), version: None },
+        }
+    }
+
+    /// 极简 JSON-RPC 桩：按 method 返回预置结果；getBlocks 返回 blocks，
+    /// 其中 SLOT 以外的区块与 fixture 以外的签名返回 RPC 错误
+    async fn spawn_rpc_stub(tx: EncodedTransactionWithStatusMeta, blocks: Vec<u64>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else { return };
+                let (tx, blocks) = (tx.clone(), blocks.clone());
+                tokio::spawn(async move {
+                    let mut buf = Vec::new();
+                    loop {
+                        // 读取一个完整 HTTP 请求
+                        let mut chunk = [0u8; 4096];
+                        let header_end = loop {
+                            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                                break pos + 4;
+                            }
+                            match socket.read(&mut chunk).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                            }
+                        };
+                        let headers = String::from_utf8_lossy(&buf[..header_end]).to_lowercase();
+                        let len: usize = headers
+                            .lines()
+                            .find_map(|l| l.strip_prefix("content-length:").map(|v| v.trim().parse().unwrap()))
+                            .unwrap_or(0);
+                        while buf.len() < header_end + len {
+                            match socket.read(&mut chunk).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                            }
+                        }
+                        let req: Value = serde_json::from_slice(&buf[header_end..header_end + len]).unwrap();
+                        buf.drain(..header_end + len);
+
+                        let signature = tx.transaction.decode().unwrap().signatures[0].to_string();
+                        let missing = match req["method"].as_str().unwrap() {
+                            "getBlock" => req["params"][0] != json!(SLOT),
+                            "getTransaction" => req["params"][0] != json!(signature),
+                            _ => false,
+                        };
+                        let result = match req["method"].as_str().unwrap() {
+                            _ if missing => Value::Null,
+                            "getBlocks" => json!(blocks),
+                            "getBlock" => json!({
+                                "previousBlockhash": "11111111111111111111111111111111",
+                                "blockhash": "11111111111111111111111111111111",
+                                "parentSlot": SLOT - 1,
+                                "transactions": [tx],
+                                "blockTime": BLOCK_TIME,
+                                "blockHeight": 1,
+                            }),
+                            "getTransaction" => {
+                                let mut v = serde_json::to_value(&tx).unwrap();
+                                v["slot"] = json!(SLOT);
+                                v["blockTime"] = json!(BLOCK_TIME);
+                                v
+                            }
+                            other => panic!("unexpected method {}", other),
+                        };
+                        let body = if missing {
+                            json!({"jsonrpc": "2.0", "id": req["id"], "error": {"code": -32004, "message": "Block not available"}})
+                        } else {
+                            json!({"jsonrpc": "2.0", "id": req["id"], "result": result})
+                        }
+                        .to_string();
+                        let resp = format!(
+                            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        if socket.write_all(resp.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    fn backfill(url: String) -> RpcBackfill {
+        // 直接构造，避免 warmup 的全局状态影响 warmup::tests
+        RpcBackfill { client: Arc::new(SolanaRpcClient::new(url)), concurrency: DEFAULT_CONCURRENCY }
+    }
+
+    fn assert_events(events: &[DexEvent], f: &Fixture, tx_index: u64) {
+        assert_eq!(events.len(), 2, "{:?}", events);
+        match &events[0] {
+            DexEvent::PumpFunTrade(e) => {
+                assert_eq!(e.mint, f.mint);
+                // account_filler 从外层指令补全的账户
+                assert_eq!(e.bonding_curve, f.bonding_curve);
+                assert_eq!(e.metadata.signature, f.signature);
+                assert_eq!(e.metadata.slot, SLOT);
+                assert_eq!(e.metadata.tx_index, tx_index);
+                assert_eq!(e.metadata.block_time_us, BLOCK_TIME * 1_000_000);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+        match &events[1] {
+            DexEvent::PumpFunMigrate(e) => assert_eq!(e.pool, f.pool),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_backfill_slot_range() {
+        let f = fixture();
+        let url = spawn_rpc_stub(f.tx.clone(), vec![SLOT]).await;
+        let report = backfill(url).backfill_slot_range(SLOT, SLOT + 10, None).await.unwrap();
+        assert!(report.is_complete());
+        assert_events(&report.events, &f, 0);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_signatures() {
+        let f = fixture();
+        let url = spawn_rpc_stub(f.tx.clone(), vec![SLOT]).await;
+        let report = backfill(url).backfill_signatures(&[f.signature], None).await;
+        assert!(report.is_complete());
+        assert_events(&report.events, &f, 0);
+    }
+
+    #[tokio::test]
+    async fn test_backfill_reports_failed_slots() {
+        let f = fixture();
+        let url = spawn_rpc_stub(f.tx.clone(), vec![SLOT - 1, SLOT, SLOT + 1]).await;
+        let backfill = backfill(url);
+
+        // 流式：按 slot 顺序逐个产出，失败的 slot 不影响其余 slot
+        let results: Vec<_> =
+            backfill.backfill_slot_range_stream(SLOT - 1, SLOT + 1, None).await.unwrap().collect().await;
+        let outcome: Vec<_> = results.iter().map(|(slot, r)| (*slot, r.is_ok())).collect();
+        assert_eq!(outcome, vec![(SLOT - 1, false), (SLOT, true), (SLOT + 1, false)]);
+
+        let report = backfill.backfill_slot_range(SLOT - 1, SLOT + 1, None).await.unwrap();
+        assert_events(&report.events, &f, 0);
+        assert_eq!(report.errors.iter().map(|(slot, _)| *slot).collect::<Vec<_>>(), vec![SLOT - 1, SLOT + 1]);
+
+        let unknown = Signature::from([3u8; 64]);
+        let report = backfill.backfill_signatures(&[unknown, f.signature], None).await;
+        assert_events(&report.events, &f, 0);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].0, unknown);
+    }
+}
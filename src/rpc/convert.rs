@@ -0,0 +1,276 @@
+//! RPC (UI) 类型 → Yellowstone protobuf 类型转换
+//!
+//! 解析管线（logs + instructions + account_filler）基于 Yellowstone protobuf 类型实现，
+//! 这里把 RPC 返回的交易转换成相同的结构，保证历史数据与实时流的字段填充一致。
+
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{
+    compiled_instruction::CompiledInstruction as SolCompiledInstruction, legacy, v0,
+    MessageHeader as SolMessageHeader, VersionedMessage,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{TransactionError as SolTransactionError, VersionedTransaction};
+use solana_transaction_status::parse_accounts::ParsedAccountSource;
+use solana_transaction_status::{
+    EncodedTransaction, EncodedTransactionWithStatusMeta, UiAddressTableLookup, UiInnerInstructions,
+    UiInstruction, UiLoadedAddresses, UiMessage, UiParsedInstruction, UiTransaction,
+    UiTransactionStatusMeta,
+};
+use std::str::FromStr;
+use yellowstone_grpc_proto::prelude::{
+    CompiledInstruction, InnerInstruction, InnerInstructions, Message, MessageAddressTableLookup,
+    MessageHeader, SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo, Transaction,
+    TransactionError, TransactionStatusMeta,
+};
+
+/// 转换带 meta 的编码交易（meta 缺失或交易无法解码时返回 None）
+pub(crate) fn encoded_to_update(
+    tx: &EncodedTransactionWithStatusMeta,
+    slot: u64,
+    index: u64,
+) -> Option<SubscribeUpdateTransaction> {
+    let versioned = decode_transaction(&tx.transaction)?;
+    let meta = tx.meta.as_ref()?;
+    Some(versioned_to_update(&versioned, meta, slot, index))
+}
+
+/// 解码交易：支持 base58/base64 二进制编码，以及 json / jsonParsed 编码（浏览器导出的 JSON）
+pub(crate) fn decode_transaction(tx: &EncodedTransaction) -> Option<VersionedTransaction> {
+    match tx {
+        EncodedTransaction::Json(ui) => decode_json_transaction(ui),
+        _ => tx.decode(),
+    }
+}
+
+fn decode_json_transaction(ui: &UiTransaction) -> Option<VersionedTransaction> {
+    let signatures =
+        ui.signatures.iter().map(|s| Signature::from_str(s).ok()).collect::<Option<Vec<_>>>()?;
+
+    let (header, account_keys, recent_blockhash, instructions, lookups) = match &ui.message {
+        UiMessage::Raw(raw) => {
+            let instructions = raw
+                .instructions
+                .iter()
+                .map(|ix| {
+                    Some(SolCompiledInstruction {
+                        program_id_index: ix.program_id_index,
+                        accounts: ix.accounts.clone(),
+                        data: bs58::decode(&ix.data).into_vec().ok()?,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let keys = raw.account_keys.iter().map(|k| Pubkey::from_str(k).ok()).collect::<Option<Vec<_>>>()?;
+            (raw.header, keys, &raw.recent_blockhash, instructions, raw.address_table_lookups.as_ref())
+        }
+        UiMessage::Parsed(parsed) => {
+            // jsonParsed：账户表包含 ALT 加载的地址，静态账户为 source != LookupTable 的部分
+            let all_keys = parsed
+                .account_keys
+                .iter()
+                .map(|a| Pubkey::from_str(&a.pubkey).ok())
+                .collect::<Option<Vec<_>>>()?;
+            let statics: Vec<_> = parsed
+                .account_keys
+                .iter()
+                .filter(|a| !matches!(a.source, Some(ParsedAccountSource::LookupTable)))
+                .collect();
+            let header = SolMessageHeader {
+                num_required_signatures: statics.iter().filter(|a| a.signer).count() as u8,
+                num_readonly_signed_accounts: statics.iter().filter(|a| a.signer && !a.writable).count() as u8,
+                num_readonly_unsigned_accounts: statics.iter().filter(|a| !a.signer && !a.writable).count() as u8,
+            };
+            let index_of = |k: &str| -> u8 {
+                Pubkey::from_str(k)
+                    .ok()
+                    .and_then(|k| all_keys.iter().position(|x| *x == k))
+                    .unwrap_or(u8::MAX as usize) as u8
+            };
+            let instructions = parsed
+                .instructions
+                .iter()
+                .map(|ix| match ix {
+                    UiInstruction::Compiled(c) => SolCompiledInstruction {
+                        program_id_index: c.program_id_index,
+                        accounts: c.accounts.clone(),
+                        data: bs58::decode(&c.data).into_vec().unwrap_or_default(),
+                    },
+                    UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(p)) => SolCompiledInstruction {
+                        program_id_index: index_of(&p.program_id),
+                        accounts: p.accounts.iter().map(|a| index_of(a)).collect(),
+                        data: bs58::decode(&p.data).into_vec().unwrap_or_default(),
+                    },
+                    UiInstruction::Parsed(UiParsedInstruction::Parsed(p)) => SolCompiledInstruction {
+                        program_id_index: index_of(&p.program_id),
+                        accounts: Vec::new(),
+                        data: Vec::new(),
+                    },
+                })
+                .collect();
+            let keys = all_keys.into_iter().take(statics.len()).collect();
+            (header, keys, &parsed.recent_blockhash, instructions, parsed.address_table_lookups.as_ref())
+        }
+    };
+
+    let recent_blockhash = Hash::from_str(recent_blockhash).ok()?;
+    let message = match lookups {
+        Some(lookups) => VersionedMessage::V0(v0::Message {
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+            address_table_lookups: lookups.iter().map(convert_lookup).collect::<Option<Vec<_>>>()?,
+        }),
+        None => VersionedMessage::Legacy(legacy::Message { header, account_keys, recent_blockhash, instructions }),
+    };
+    Some(VersionedTransaction { signatures, message })
+}
+
+fn convert_lookup(l: &UiAddressTableLookup) -> Option<v0::MessageAddressTableLookup> {
+    Some(v0::MessageAddressTableLookup {
+        account_key: Pubkey::from_str(&l.account_key).ok()?,
+        writable_indexes: l.writable_indexes.clone(),
+        readonly_indexes: l.readonly_indexes.clone(),
+    })
+}
+
+/// 转换已解码交易 + UI meta
+pub(crate) fn versioned_to_update(
+    tx: &VersionedTransaction,
+    meta: &UiTransactionStatusMeta,
+    slot: u64,
+    index: u64,
+) -> SubscribeUpdateTransaction {
+    let transaction = versioned_to_proto(tx);
+    let meta = ui_meta_to_proto(meta, tx.message.static_account_keys());
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: tx.signatures.first().map(|s| s.as_ref().to_vec()).unwrap_or_else(|| vec![0u8; 64]),
+            is_vote: false,
+            transaction: Some(transaction),
+            meta: Some(meta),
+            index,
+        }),
+        slot,
+    }
+}
+
+fn versioned_to_proto(tx: &VersionedTransaction) -> Transaction {
+    let header = tx.message.header();
+    let instructions = tx
+        .message
+        .instructions()
+        .iter()
+        .map(|ix| CompiledInstruction {
+            program_id_index: ix.program_id_index as u32,
+            accounts: ix.accounts.clone(),
+            data: ix.data.clone(),
+        })
+        .collect();
+    let address_table_lookups = tx
+        .message
+        .address_table_lookups()
+        .unwrap_or_default()
+        .iter()
+        .map(|l| MessageAddressTableLookup {
+            account_key: l.account_key.to_bytes().to_vec(),
+            writable_indexes: l.writable_indexes.clone(),
+            readonly_indexes: l.readonly_indexes.clone(),
+        })
+        .collect();
+
+    Transaction {
+        signatures: tx.signatures.iter().map(|s| s.as_ref().to_vec()).collect(),
+        message: Some(Message {
+            header: Some(MessageHeader {
+                num_required_signatures: header.num_required_signatures as u32,
+                num_readonly_signed_accounts: header.num_readonly_signed_accounts as u32,
+                num_readonly_unsigned_accounts: header.num_readonly_unsigned_accounts as u32,
+            }),
+            account_keys: tx.message.static_account_keys().iter().map(|k| k.to_bytes().to_vec()).collect(),
+            recent_blockhash: tx.message.recent_blockhash().to_bytes().to_vec(),
+            instructions,
+            versioned: matches!(tx.message, VersionedMessage::V0(_)),
+            address_table_lookups,
+        }),
+    }
+}
+
+fn ui_meta_to_proto(meta: &UiTransactionStatusMeta, static_keys: &[Pubkey]) -> TransactionStatusMeta {
+    let loaded: Option<&UiLoadedAddresses> = meta.loaded_addresses.as_ref().into();
+    let (loaded_writable, loaded_readonly) = match loaded {
+        Some(loaded) => (parse_pubkeys(&loaded.writable), parse_pubkeys(&loaded.readonly)),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    // jsonParsed 编码下 inner instruction 只给出账户地址，需要按完整账户表反查索引
+    let all_keys: Vec<Pubkey> = static_keys
+        .iter()
+        .copied()
+        .chain(loaded_writable.iter().chain(loaded_readonly.iter()).copied())
+        .collect();
+
+    let log_messages: Option<&Vec<String>> = meta.log_messages.as_ref().into();
+    let inner_instructions: Option<&Vec<UiInnerInstructions>> = meta.inner_instructions.as_ref().into();
+
+    TransactionStatusMeta {
+        fee: meta.fee,
+        pre_balances: meta.pre_balances.clone(),
+        post_balances: meta.post_balances.clone(),
+        inner_instructions_none: inner_instructions.is_none(),
+        inner_instructions: inner_instructions
+            .map(|v| v.iter().map(|inner| convert_inner_instructions(inner, &all_keys)).collect())
+            .unwrap_or_default(),
+        log_messages_none: log_messages.is_none(),
+        log_messages: log_messages.cloned().unwrap_or_default(),
+        loaded_writable_addresses: loaded_writable.iter().map(|k| k.to_bytes().to_vec()).collect(),
+        loaded_readonly_addresses: loaded_readonly.iter().map(|k| k.to_bytes().to_vec()).collect(),
+        compute_units_consumed: Option::from(meta.compute_units_consumed.clone()),
+        err: meta.err.as_ref().map(|e| TransactionError {
+            err: bincode::serialize(&SolTransactionError::from(e.clone())).unwrap_or_default(),
+        }),
+        ..Default::default()
+    }
+}
+
+fn convert_inner_instructions(inner: &UiInnerInstructions, all_keys: &[Pubkey]) -> InnerInstructions {
+    let key_index = |s: &str| -> u32 {
+        Pubkey::from_str(s)
+            .ok()
+            .and_then(|k| all_keys.iter().position(|x| *x == k))
+            .unwrap_or(u8::MAX as usize) as u32
+    };
+
+    let instructions = inner
+        .instructions
+        .iter()
+        .map(|ix| match ix {
+            UiInstruction::Compiled(c) => InnerInstruction {
+                program_id_index: c.program_id_index as u32,
+                accounts: c.accounts.clone(),
+                data: bs58::decode(&c.data).into_vec().unwrap_or_default(),
+                stack_height: c.stack_height,
+            },
+            UiInstruction::Parsed(UiParsedInstruction::PartiallyDecoded(p)) => InnerInstruction {
+                program_id_index: key_index(&p.program_id),
+                accounts: p.accounts.iter().map(|a| key_index(a) as u8).collect(),
+                data: bs58::decode(&p.data).into_vec().unwrap_or_default(),
+                stack_height: p.stack_height,
+            },
+            // 已被 RPC 完全解析的指令（System/Token 等）不含原始数据，保留占位以维持 inner 索引
+            UiInstruction::Parsed(UiParsedInstruction::Parsed(p)) => InnerInstruction {
+                program_id_index: key_index(&p.program_id),
+                accounts: Vec::new(),
+                data: Vec::new(),
+                stack_height: p.stack_height,
+            },
+        })
+        .collect();
+
+    InnerInstructions { index: inner.index as u32, instructions }
+}
+
+#[inline]
+fn parse_pubkeys(keys: &[String]) -> Vec<Pubkey> {
+    keys.iter().map(|k| Pubkey::from_str(k).unwrap_or_default()).collect()
+}
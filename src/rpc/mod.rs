@@ -0,0 +1,9 @@
+//! RPC 模块 - 基于 getTransaction / getBlock 的历史数据回填
+//!
+//! 与 gRPC 实时流共用同一条解析管线，历史事件与实时事件字段填充一致。
+
+pub mod backfill;
+pub(crate) mod convert;
+
+// 重新导出主要API
+pub use backfill::{BackfillReport, RpcBackfill};
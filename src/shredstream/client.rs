@@ -0,0 +1,354 @@
+//! ShredStream 客户端 - 订阅 Jito ShredStream proxy 的 entries 并解析 DEX 事件
+//!
+//! 与 Yellowstone gRPC 不同，entries 中的交易不带 meta，
+//! 因此只解析外层指令，`block_time_us` 恒为 0。
+
+use super::config::ShredStreamConfig;
+use super::proto::{shredstream_proxy_client::ShredstreamProxyClient, SubscribeEntriesRequest};
+use crate::grpc::client::{get_timestamp_us, wait_for_shutdown};
+use crate::grpc::types::{EventTypeFilter, TransactionFilter};
+use crate::DexEvent;
+use crossbeam_queue::ArrayQueue;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::time::Duration;
+use tonic::transport::Endpoint;
+
+// ==================== ShredStreamClient 客户端 ====================
+
+#[derive(Clone)]
+pub struct ShredStreamClient {
+    endpoint: String,
+    config: ShredStreamConfig,
+    /// 各订阅的停止信号
+    subscriptions: Arc<std::sync::Mutex<Vec<Arc<watch::Sender<bool>>>>>,
+}
+
+impl ShredStreamClient {
+    pub fn new(endpoint: String) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::new_with_config(endpoint, ShredStreamConfig::default())
+    }
+
+    pub fn new_with_config(
+        endpoint: String,
+        config: ShredStreamConfig,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        crate::warmup::warmup_parser();
+        Ok(Self { endpoint, config, subscriptions: Default::default() })
+    }
+
+    /// 订阅 DEX 事件（自动重连，调用 `stop()` 结束）
+    ///
+    /// `transaction_filters` 按静态账户列表匹配（多个过滤器之间为"或"关系，为空则不过滤）
+    pub async fn subscribe_dex_events(
+        &self,
+        transaction_filters: Vec<TransactionFilter>,
+        event_type_filter: Option<EventTypeFilter>,
+    ) -> Result<Arc<ArrayQueue<DexEvent>>, Box<dyn std::error::Error>> {
+        let queue = Arc::new(ArrayQueue::new(self.config.queue_capacity));
+        let queue_clone = Arc::clone(&queue);
+        let self_clone = self.clone();
+        let key_filters: Vec<KeyFilter> = transaction_filters.iter().map(KeyFilter::from).collect();
+        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+        self.subscriptions.lock().unwrap().push(Arc::new(shutdown_tx));
+
+        tokio::spawn(async move {
+            let mut delay = 1u64;
+            loop {
+                let result = tokio::select! {
+                    result = self_clone.stream_events(&key_filters, &event_type_filter, &queue_clone) => result,
+                    _ = wait_for_shutdown(&mut shutdown_rx) => break,
+                };
+                match result {
+                    Ok(_) => delay = 1,
+                    Err(e) => log::error!("ShredStream error: {} - retry in {}s", e, delay),
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_secs(delay)) => {}
+                    _ = wait_for_shutdown(&mut shutdown_rx) => break,
+                }
+                delay = (delay * 2).min(60);
+            }
+            log::info!("ShredStream subscription stopped: {}", self_clone.endpoint);
+        });
+
+        Ok(queue)
+    }
+
+    /// 停止该客户端上的全部订阅（包括正在等待重连的订阅）
+    pub async fn stop(&self) {
+        for tx in self.subscriptions.lock().unwrap().drain(..) {
+            let _ = tx.send(true);
+        }
+    }
+
+    // ==================== 核心事件流处理 ====================
+
+    async fn stream_events(
+        &self,
+        key_filters: &[KeyFilter],
+        event_filter: &Option<EventTypeFilter>,
+        queue: &Arc<ArrayQueue<DexEvent>>,
+    ) -> Result<(), String> {
+        let mut endpoint = Endpoint::from_shared(self.endpoint.clone()).map_err(|e| e.to_string())?;
+        if self.config.connection_timeout_ms > 0 {
+            endpoint = endpoint.connect_timeout(Duration::from_millis(self.config.connection_timeout_ms));
+        }
+
+        let channel = endpoint.connect().await.map_err(|e| e.to_string())?;
+        let mut client = ShredstreamProxyClient::new(channel)
+            .max_decoding_message_size(self.config.max_decoding_message_size);
+
+        let mut stream = client
+            .subscribe_entries(SubscribeEntriesRequest {})
+            .await
+            .map_err(|e| e.to_string())?
+            .into_inner();
+
+        log::info!("ShredStream connected: {}", self.endpoint);
+
+        // 同一 slot 的 entries 可能分多条消息推送，tx_index 需跨消息累加
+        let mut current_slot = 0u64;
+        let mut tx_index = 0u64;
+
+        loop {
+            match stream.message().await {
+                Ok(Some(msg)) => {
+                    let recv_us = get_timestamp_us();
+                    if msg.slot != current_slot {
+                        current_slot = msg.slot;
+                        tx_index = 0;
+                    }
+                    let entries: Vec<solana_entry::entry::Entry> = match bincode::deserialize(&msg.entries) {
+                        Ok(entries) => entries,
+                        Err(e) => {
+                            log::error!("ShredStream entries decode error (slot {}): {}", msg.slot, e);
+                            continue;
+                        }
+                    };
+                    for entry in &entries {
+                        for tx in &entry.transactions {
+                            let idx = tx_index;
+                            tx_index += 1;
+                            if !matches_key_filters(tx, key_filters) {
+                                continue;
+                            }
+                            for e in parse_versioned_transaction_instructions(tx, msg.slot, idx, recv_us, event_filter.as_ref()) {
+                                let _ = queue.push(e);
+                            }
+                        }
+                    }
+                }
+                Ok(None) => return Ok(()),
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+    }
+}
+
+// ==================== 交易过滤 ====================
+
+/// 预解析的 TransactionFilter（避免每笔交易重复解析字符串）
+struct KeyFilter {
+    include: Vec<Pubkey>,
+    exclude: Vec<Pubkey>,
+    required: Vec<Pubkey>,
+}
+
+impl From<&TransactionFilter> for KeyFilter {
+    fn from(f: &TransactionFilter) -> Self {
+        let parse = |v: &[String]| v.iter().filter_map(|s| Pubkey::from_str(s).ok()).collect();
+        Self {
+            include: parse(&f.account_include),
+            exclude: parse(&f.account_exclude),
+            required: parse(&f.account_required),
+        }
+    }
+}
+
+#[inline]
+fn matches_key_filters(tx: &VersionedTransaction, filters: &[KeyFilter]) -> bool {
+    if filters.is_empty() {
+        return true;
+    }
+    let keys = tx.message.static_account_keys();
+    filters.iter().any(|f| {
+        (f.include.is_empty() || f.include.iter().any(|k| keys.contains(k)))
+            && !f.exclude.iter().any(|k| keys.contains(k))
+            && f.required.iter().all(|k| keys.contains(k))
+    })
+}
+
+// ==================== 交易解析 ====================
+
+/// 解析一条 ShredStream 消息中的 entries（bincode 序列化的 `Vec<Entry>`）
+///
+/// tx_index 从 0 开始按 entry 内顺序编号
+pub fn parse_entries(
+    slot: u64,
+    entries: &[u8],
+    recv_us: i64,
+    filter: Option<&EventTypeFilter>,
+) -> Result<Vec<DexEvent>, bincode::Error> {
+    let entries: Vec<solana_entry::entry::Entry> = bincode::deserialize(entries)?;
+    let mut result = Vec::new();
+    let mut tx_index = 0u64;
+    for entry in &entries {
+        for tx in &entry.transactions {
+            result.extend(parse_versioned_transaction_instructions(tx, slot, tx_index, recv_us, filter));
+            tx_index += 1;
+        }
+    }
+    Ok(result)
+}
+
+/// 仅基于外层指令解析交易（无 meta 时的降级路径）
+///
+/// 通过 ALT 加载的账户无法解析，以 `Pubkey::default()` 占位
+pub fn parse_versioned_transaction_instructions(
+    tx: &VersionedTransaction,
+    slot: u64,
+    tx_index: u64,
+    recv_us: i64,
+    filter: Option<&EventTypeFilter>,
+) -> Vec<DexEvent> {
+    let Some(sig) = tx.signatures.first().copied() else { return Vec::new() };
+    let keys = tx.message.static_account_keys();
+    let instructions = match &tx.message {
+        VersionedMessage::Legacy(m) => &m.instructions,
+        VersionedMessage::V0(m) => &m.instructions,
+    };
+
+    let mut result = Vec::new();
+    for ix in instructions {
+        let Some(pid) = keys.get(ix.program_id_index as usize) else { continue };
+        let accounts: Vec<Pubkey> = ix
+            .accounts
+            .iter()
+            .map(|&i| keys.get(i as usize).copied().unwrap_or_default())
+            .collect();
+        crate::instr::parse_instruction_events(
+            &ix.data, &accounts, sig, slot, tx_index, None, recv_us, filter, pid,
+            |e| result.push(e),
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::types::EventType;
+    use crate::instr::program_ids::PUMPFUN_PROGRAM_ID;
+    use crate::shredstream::proto::{
+        shredstream_proxy_server::{ShredstreamProxy, ShredstreamProxyServer},
+        Entry,
+    };
+    use futures::{Stream, StreamExt};
+    use solana_sdk::hash::Hash;
+    use crate::test_fixtures;
+    use solana_sdk::signature::Signature;
+    use std::pin::Pin;
+
+    fn pumpfun_buy_tx(mint: Pubkey, user: Pubkey) -> VersionedTransaction {
+        test_fixtures::pumpfun_buy_tx(mint, Pubkey::new_unique(), user, Signature::from([7u8; 64]))
+    }
+
+    fn entries_bytes(txs: Vec<VersionedTransaction>) -> Vec<u8> {
+        let entries = vec![solana_entry::entry::Entry { num_hashes: 1, hash: Hash::default(), transactions: txs }];
+        bincode::serialize(&entries).unwrap()
+    }
+
+    #[test]
+    fn test_parse_entries_pumpfun_buy() {
+        let (mint, user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let bytes = entries_bytes(vec![pumpfun_buy_tx(mint, user)]);
+
+        let events = parse_entries(42, &bytes, 1, None).unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            DexEvent::PumpFunBuy(e) => {
+                assert_eq!(e.mint, mint);
+                assert_eq!(e.user, user);
+                assert_eq!(e.token_amount, 1_000_000);
+                assert_eq!(e.sol_amount, 50_000_000);
+                assert_eq!(e.metadata.slot, 42);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        let sells_only = EventTypeFilter::include_only(vec![EventType::PumpFunSell]);
+        assert!(parse_entries(42, &bytes, 1, Some(&sells_only)).unwrap().is_empty());
+    }
+
+    struct MockProxy {
+        payload: Vec<u8>,
+    }
+
+    #[tonic::async_trait]
+    impl ShredstreamProxy for MockProxy {
+        type SubscribeEntriesStream =
+            Pin<Box<dyn Stream<Item = Result<Entry, tonic::Status>> + Send + 'static>>;
+
+        async fn subscribe_entries(
+            &self,
+            _request: tonic::Request<SubscribeEntriesRequest>,
+        ) -> Result<tonic::Response<Self::SubscribeEntriesStream>, tonic::Status> {
+            let entry = Entry { slot: 7, entries: self.payload.clone() };
+            let stream = futures::stream::iter(vec![Ok(entry)]).chain(futures::stream::pending());
+            Ok(tonic::Response::new(Box::pin(stream)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_with_mock_proxy() {
+        let (mint, user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let payload = entries_bytes(vec![pumpfun_buy_tx(mint, user)]);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(ShredstreamProxyServer::new(MockProxy { payload }))
+                .serve_with_incoming(tonic::transport::server::TcpIncoming::from(listener)),
+        );
+
+        // 直接构造，避免 warmup 的全局状态影响 warmup::tests
+        let client = ShredStreamClient {
+            endpoint: format!("http://{}", addr),
+            config: ShredStreamConfig::default(),
+            subscriptions: Default::default(),
+        };
+        let filters = vec![TransactionFilter::new().include_account(PUMPFUN_PROGRAM_ID.to_string())];
+        let queue = client.subscribe_dex_events(filters, None).await.unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(e) = queue.pop() {
+                    return e;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("no event from mock proxy");
+
+        match event {
+            DexEvent::PumpFunBuy(e) => {
+                assert_eq!(e.mint, mint);
+                assert_eq!(e.metadata.slot, 7);
+                assert_eq!(e.metadata.tx_index, 0);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        // stop() 结束订阅任务：任务退出后停止信号的接收端随之释放
+        let shutdown = Arc::clone(&client.subscriptions.lock().unwrap()[0]);
+        client.stop().await;
+        tokio::time::timeout(Duration::from_secs(5), shutdown.closed()).await.expect("subscription still running");
+        assert!(client.subscriptions.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShredStreamConfig {
+    /// 连接超时时间（毫秒）
+    pub connection_timeout_ms: u64,
+    /// 单条 gRPC 消息最大解码大小（字节）
+    pub max_decoding_message_size: usize,
+    /// 事件队列容量
+    pub queue_capacity: usize,
+}
+
+impl Default for ShredStreamConfig {
+    fn default() -> Self {
+        Self {
+            connection_timeout_ms: 8000,
+            max_decoding_message_size: 1024 * 1024 * 1024,
+            queue_capacity: 100_000,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! ShredStream 模块 - 基于 Jito ShredStream proxy 的超低延迟事件订阅
+//!
+//! ShredStream 直接推送 entries（交易尚未执行），因此：
+//! - 没有 meta / logs / inner instructions
+//! - 只能基于外层指令数据解析（instruction-only fallback）
+//! - 通过地址查找表（ALT）引用的账户无法解析，以默认公钥占位
+
+pub mod client;
+pub mod config;
+pub mod proto;
+
+// 重新导出主要API
+pub use client::{parse_entries, parse_versioned_transaction_instructions, ShredStreamClient};
+pub use config::ShredStreamConfig;
@@ -0,0 +1,245 @@
+//! Jito ShredStream proxy 协议定义
+//!
+//! 对应 `shredstream.proto` 中的 `ShredstreamProxy` 服务：
+//!
+//! ```proto
+//! service ShredstreamProxy {
+//!   rpc SubscribeEntries(SubscribeEntriesRequest) returns (stream Entry);
+//! }
+//! message SubscribeEntriesRequest {}
+//! message Entry {
+//!   uint64 slot = 1;
+//!   bytes entries = 2; // bincode 序列化的 Vec<solana_entry::entry::Entry>
+//! }
+//! ```
+//!
+//! 消息体很小，手写 prost/tonic 代码即可，无需引入 build.rs。
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SubscribeEntriesRequest {}
+
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Entry {
+    /// 该批 entries 所属的 slot
+    #[prost(uint64, tag = "1")]
+    pub slot: u64,
+    /// bincode 序列化的 `Vec<solana_entry::entry::Entry>`
+    #[prost(bytes = "vec", tag = "2")]
+    pub entries: ::prost::alloc::vec::Vec<u8>,
+}
+
+pub mod shredstream_proxy_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct ShredstreamProxyClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl ShredstreamProxyClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> ShredstreamProxyClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::Body>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        pub async fn subscribe_entries(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SubscribeEntriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::Entry>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/shredstream.ShredstreamProxy/SubscribeEntries",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("shredstream.ShredstreamProxy", "SubscribeEntries"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+    }
+}
+
+/// 服务端定义，用于搭建本地 mock proxy（测试 / 回放）
+pub mod shredstream_proxy_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    #[async_trait]
+    pub trait ShredstreamProxy: std::marker::Send + std::marker::Sync + 'static {
+        /// Server streaming response type for the SubscribeEntries method.
+        type SubscribeEntriesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::Entry, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn subscribe_entries(
+            &self,
+            request: tonic::Request<super::SubscribeEntriesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::SubscribeEntriesStream>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct ShredstreamProxyServer<T> {
+        inner: Arc<T>,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> ShredstreamProxyServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ShredstreamProxyServer<T>
+    where
+        T: ShredstreamProxy,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/shredstream.ShredstreamProxy/SubscribeEntries" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeEntriesSvc<T: ShredstreamProxy>(pub Arc<T>);
+                    impl<
+                        T: ShredstreamProxy,
+                    > tonic::server::ServerStreamingService<super::SubscribeEntriesRequest>
+                    for SubscribeEntriesSvc<T> {
+                        type Response = super::Entry;
+                        type ResponseStream = T::SubscribeEntriesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SubscribeEntriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as ShredstreamProxy>::subscribe_entries(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = SubscribeEntriesSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(
+                            tonic::body::Body::default(),
+                        );
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for ShredstreamProxyServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "shredstream.ShredstreamProxy";
+    impl<T> tonic::server::NamedService for ShredstreamProxyServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
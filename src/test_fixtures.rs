@@ -0,0 +1,132 @@
+//! 测试数据构造 - 各模块测试共用的 PumpFun / Orca 交易、日志与指令
+
+use base64::Engine;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::{Message, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::instr::program_ids::PUMPFUN_PROGRAM_ID;
+
+/// TradeEvent 中的 sol_amount / token_amount
+pub(crate) const TRADE_SOL_AMOUNT: u64 = 1_000;
+pub(crate) const TRADE_TOKEN_AMOUNT: u64 = 2_000;
+
+/// PumpFun TradeEvent 数据（含 discriminator、不含 ix_name，解析为 PumpFunTrade）
+pub(crate) fn pumpfun_trade_event_data(mint: &Pubkey, user: &Pubkey) -> Vec<u8> {
+    let mut data = vec![189, 219, 127, 211, 78, 230, 97, 238];
+    data.extend_from_slice(mint.as_ref());
+    data.extend_from_slice(&TRADE_SOL_AMOUNT.to_le_bytes());
+    data.extend_from_slice(&TRADE_TOKEN_AMOUNT.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(user.as_ref());
+    data.extend_from_slice(&[0u8; 8 * 5]);
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(&[0u8; 8 * 2]);
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(&[0u8; 8 * 2]);
+    data
+}
+
+/// `Program data: <base64>` 日志
+pub(crate) fn program_data_log(data: &[u8]) -> String {
+    format!("Program data: {}", base64::engine::general_purpose::STANDARD.encode(data))
+}
+
+/// 一次 PumpFun 调用的日志：invoke / TradeEvent / success
+pub(crate) fn pumpfun_trade_logs(mint: &Pubkey, user: &Pubkey) -> Vec<String> {
+    vec![
+        format!("Program {} invoke [1]", PUMPFUN_PROGRAM_ID),
+        program_data_log(&pumpfun_trade_event_data(mint, user)),
+        format!("Program {} success", PUMPFUN_PROGRAM_ID),
+    ]
+}
+
+/// PumpFun migrate CPI 事件指令数据
+pub(crate) fn pumpfun_migrate_cpi_data(user: &Pubkey, mint: &Pubkey, bonding_curve: &Pubkey, pool: &Pubkey) -> Vec<u8> {
+    let mut data = vec![228, 69, 165, 46, 81, 203, 154, 29];
+    data.extend_from_slice(&crate::instr::pump::discriminators::MIGRATE_EVENT_LOG);
+    data.extend_from_slice(user.as_ref());
+    data.extend_from_slice(mint.as_ref());
+    data.extend_from_slice(&[0u8; 8 * 3]);
+    data.extend_from_slice(bonding_curve.as_ref());
+    data.extend_from_slice(&[0u8; 8]);
+    data.extend_from_slice(pool.as_ref());
+    data
+}
+
+/// PumpFun buy 指令：mint(2) / bonding_curve(3) / user(6)，其余账户随机
+pub(crate) fn pumpfun_buy_instruction(
+    mint: Pubkey,
+    bonding_curve: Pubkey,
+    user: Pubkey,
+    amount: u64,
+    max_sol_cost: u64,
+) -> Instruction {
+    let mut data = crate::instr::pump::discriminators::BUY.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&max_sol_cost.to_le_bytes());
+    let mut metas: Vec<AccountMeta> =
+        (0..12).map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false)).collect();
+    metas[2] = AccountMeta::new_readonly(mint, false);
+    metas[3] = AccountMeta::new(bonding_curve, false);
+    metas[6] = AccountMeta::new(user, true);
+    Instruction { program_id: PUMPFUN_PROGRAM_ID, accounts: metas, data }
+}
+
+/// 只含一条 PumpFun buy 指令的 legacy 交易
+pub(crate) fn pumpfun_buy_tx(
+    mint: Pubkey,
+    bonding_curve: Pubkey,
+    user: Pubkey,
+    signature: Signature,
+) -> VersionedTransaction {
+    let ix = pumpfun_buy_instruction(mint, bonding_curve, user, 1_000_000, 50_000_000);
+    let message = Message::new(&[ix], Some(&user));
+    VersionedTransaction { signatures: vec![signature], message: VersionedMessage::Legacy(message) }
+}
+
+/// Orca two_hop_swap_v2 指令数据：amount 1_000、阈值 900、第一跳 A -> B、第二跳 B -> A，
+/// remaining accounts 为 1 个输入侧 + 2 个输出侧 transfer hook 账户（共需 27 个账户）
+pub(crate) fn orca_two_hop_swap_v2_data() -> Vec<u8> {
+    use crate::instr::orca_whirlpool::{accounts_type, discriminators};
+    let mut data = discriminators::TWO_HOP_SWAP_V2.to_vec();
+    data.extend_from_slice(&1_000u64.to_le_bytes()); // amount
+    data.extend_from_slice(&900u64.to_le_bytes()); // other_amount_threshold
+    data.extend_from_slice(&[1, 1, 0]); // amount_specified_is_input, a_to_b_one, a_to_b_two
+    data.extend_from_slice(&0u128.to_le_bytes());
+    data.extend_from_slice(&0u128.to_le_bytes());
+    // Some(RemainingAccountsInfo { [TransferHookInput x1, TransferHookOutput x2] })
+    data.extend_from_slice(&[1, 2, 0, 0, 0]);
+    data.extend_from_slice(&[accounts_type::TRANSFER_HOOK_INPUT, 1]);
+    data.extend_from_slice(&[accounts_type::TRANSFER_HOOK_OUTPUT, 2]);
+    data
+}
+
+/// Orca Traded 事件日志：无 transfer fee，lp_fee 固定为 3
+pub(crate) fn orca_traded_log(whirlpool: &Pubkey, a_to_b: bool, input_amount: u64, output_amount: u64) -> String {
+    let mut data = vec![225, 202, 73, 175, 147, 43, 160, 150];
+    data.extend_from_slice(whirlpool.as_ref());
+    data.push(a_to_b as u8);
+    data.extend_from_slice(&[0u8; 16 * 2]); // pre / post sqrt_price
+    data.extend_from_slice(&input_amount.to_le_bytes());
+    data.extend_from_slice(&output_amount.to_le_bytes());
+    data.extend_from_slice(&[0u8; 8 * 2]); // input / output transfer fee
+    data.extend_from_slice(&3u64.to_le_bytes());
+    data.extend_from_slice(&[0u8; 8]);
+    program_data_log(&data)
+}
+
+/// Orca swap_v2 指令数据：amount 1_000（输入）、阈值 900、A -> B，remaining accounts 为 1 个 A 侧 hook 账户（共需 16 个账户）
+pub(crate) fn orca_swap_v2_data() -> Vec<u8> {
+    use crate::instr::orca_whirlpool::{accounts_type, discriminators};
+    let mut data = discriminators::SWAP_V2.to_vec();
+    data.extend_from_slice(&1_000u64.to_le_bytes());
+    data.extend_from_slice(&900u64.to_le_bytes());
+    data.extend_from_slice(&0u128.to_le_bytes());
+    data.extend_from_slice(&[1, 1]); // amount_specified_is_input, a_to_b
+    data.extend_from_slice(&[1, 1, 0, 0, 0]);
+    data.extend_from_slice(&[accounts_type::TRANSFER_HOOK_A, 1]);
+    data
+}